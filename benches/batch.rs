@@ -0,0 +1,130 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+//! Compare batched versus per-message packing and decoding of [`ServerMessage`].
+
+use std::{hint::black_box, io::{self, Write}, net::{TcpListener, TcpStream}, thread::{self, JoinHandle}};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ethos_core::net::{BatchIter, Message, MessageBatch, MessageDecoder, ServerMessage, ServerPayload, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Count of messages per benchmark iteration.
+const COUNTS : [usize; 3] = [16, 128, 1024];
+
+/// Size of each batch sent in bytes.
+const BATCH_BUDGET : usize = 1400;
+
+fn messages(count : usize) -> Vec<ServerMessage> {
+    (0..count).map(|i| ServerMessage::new(i as u64, ServerPayload::Test { p16: i as u16, p32: i as u32 })).collect()
+}
+
+/// Loopback TCP connection drained by a thread.
+/// 
+/// Nagle's algorithm is disabled so each write costs a system call, like a game server flushing messages.
+fn loopback() -> (TcpStream, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let socket = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    socket.set_nodelay(true).unwrap();
+
+    let (mut reader, _) = listener.accept().unwrap();
+    let drain = thread::spawn(move || { io::copy(&mut reader, &mut io::sink()).unwrap(); });
+    (socket, drain)
+}
+
+/// Pack messages and write them to a loopback socket, once per message versus once per batch.
+fn pack(c : &mut Criterion) {
+    let mut group = c.benchmark_group("pack");
+
+    for count in COUNTS {
+        let messages = messages(count);
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("per_message", count), &messages, |b, messages| {
+            let mut buffer = vec![0u8; MESSAGE_SIZE_TYPE_SIZE + ServerMessage::MAX_SIZE];
+            let (mut socket, drain) = loopback();
+            b.iter(|| {
+                for message in messages {
+                    let size = message.pack_bytes(&mut buffer).unwrap();
+                    socket.write_all(&buffer[..MESSAGE_SIZE_TYPE_SIZE + size]).unwrap();
+                }
+            });
+            drop(socket);
+            drain.join().unwrap();
+        });
+
+        group.bench_with_input(BenchmarkId::new("batched", count), &messages, |b, messages| {
+            let mut batch = MessageBatch::new(BATCH_BUDGET);
+            let (mut socket, drain) = loopback();
+            b.iter(|| {
+                for message in messages {
+                    if batch.push(message).is_err() {
+                        socket.write_all(batch.bytes()).unwrap();
+                        batch.clear();
+                        batch.push(message).unwrap();
+                    }
+                }
+                socket.write_all(batch.bytes()).unwrap();
+                batch.clear();
+            });
+            drop(socket);
+            drain.join().unwrap();
+        });
+    }
+
+    group.finish();
+}
+
+/// Decode received bytes, with a streaming decoder versus walking the batch.
+fn decode(c : &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+
+    for count in COUNTS {
+        let mut batch = MessageBatch::new(count * 32);
+        for message in messages(count) {
+            batch.push(&message).unwrap();
+        }
+        let bytes = batch.bytes().to_vec();
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("decoder", count), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut decoder = MessageDecoder::<ServerMessage>::with_capacity(bytes.len());
+                decoder.push(bytes).unwrap();
+                decoder.for_each(|message| { black_box(message.unwrap()); });
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("batch_iter", count), &bytes, |b, bytes| {
+            b.iter(|| {
+                BatchIter::<ServerMessage>::new(bytes).for_each(|message| { black_box(message.unwrap()); });
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, pack, decode);
+criterion_main!(benches);
@@ -0,0 +1,127 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+//! Compare decode throughput of owned payloads versus borrowed views.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ethos_core::net::EthosPayload;
+use ethos_core::tampon::Tampon;
+
+/// Payloads of benchmarks, since test payloads are not part of the protocol.
+#[derive(Debug, PartialEq, Clone, EthosPayload)]
+enum BenchPayload {
+    /// Payload with variable-length fields.
+    #[ethos(id = 1)]
+    Variable { 
+        #[ethos(max = 32)]
+        text : String, 
+        #[ethos(max = 8)]
+        list : Vec<u32>, 
+        #[ethos(max = 64)]
+        blob : Box<[u8]>, 
+        fixed : [u16; 4] 
+    },
+
+    /// Payload with blobs.
+    #[ethos(id = 2)]
+    Large { blobs : Vec<Box<[u8]>> },
+
+    #[ethos(id = 65535)]
+    Invalid,
+}
+
+/// Serialize a payload, validated with `deserialize_size` before decoding like a message.
+fn serialize<P : Tampon>(payload : &P) -> Vec<u8> {
+    let mut buffer = vec![0u8; payload.bytes_size()];
+    payload.serialize(&mut buffer);
+    buffer
+}
+
+/// Payloads carrying blobs of various sizes.
+fn large_payloads() -> Vec<(String, Vec<u8>)> {
+    [(4usize, 16usize), (16, 256), (16, 4000)].into_iter().map(|(count, len)| {
+        let blobs = (0..count).map(|i| vec![i as u8; len].into_boxed_slice()).collect();
+        (format!("{}x{}", count, len), serialize(&BenchPayload::Large { blobs }))
+    }).collect()
+}
+
+/// Decode payloads with blobs, reading every blob.
+fn decode_blobs(c : &mut Criterion) {
+    let mut group = c.benchmark_group("decode_blobs");
+
+    for (name, bytes) in large_payloads() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("owned", &name), &bytes, |b, bytes| {
+            b.iter(|| {
+                BenchPayload::deserialize_size(bytes, 0).unwrap();
+                let (BenchPayload::Large { blobs }, _) = BenchPayload::deserialize(bytes) else { unreachable!() };
+                black_box(blobs.iter().map(|blob| blob.len()).sum::<usize>())
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("view", &name), &bytes, |b, bytes| {
+            b.iter(|| {
+                BenchPayload::deserialize_size(bytes, 0).unwrap();
+                let (BenchPayloadRef::Large { blobs }, _) = BenchPayloadRef::read(bytes) else { unreachable!() };
+                black_box(blobs.iter().map(|blob| blob.len()).sum::<usize>())
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Decode payloads with strings and lists, reading every field.
+fn decode_variable(c : &mut Criterion) {
+    let mut group = c.benchmark_group("decode_variable");
+    let bytes = serialize(&BenchPayload::Variable { 
+        text: "Thirty-two characters long text!".into(), list: (0..8).collect(), blob: vec![7; 64].into_boxed_slice(), fixed: [1, 2, 3, 4] 
+    });
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            BenchPayload::deserialize_size(&bytes, 0).unwrap();
+            let (BenchPayload::Variable { text, list, blob, fixed }, _) = BenchPayload::deserialize(&bytes) else { unreachable!() };
+            black_box((text.len(), list.iter().sum::<u32>(), blob.len(), fixed[0]))
+        });
+    });
+
+    group.bench_function("view", |b| {
+        b.iter(|| {
+            BenchPayload::deserialize_size(&bytes, 0).unwrap();
+            let (BenchPayloadRef::Variable { text, list, blob, fixed }, _) = BenchPayloadRef::read(&bytes) else { unreachable!() };
+            black_box((text.len(), list.iter().sum::<u32>(), blob.len(), fixed.iter().next()))
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, decode_blobs, decode_variable);
+criterion_main!(benches);
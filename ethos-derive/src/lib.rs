@@ -0,0 +1,632 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+//! Derive macros of Ethos-Core.
+//! 
+//! Use them through `ethos_core`, which re-exports them with the types their generated code needs.
+
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, LitInt, Type};
+
+/// Name of the variant receiving unknown ids.
+const INVALID_VARIANT : &str = "Invalid";
+
+/// Derive the payload serialization of an enum, with the same wire format as `write_messages_payloads!`.
+/// 
+/// Each variant needs a unique id given by `#[ethos(id = N)]` and a unit variant `Invalid` receives unknown ids.
+/// Fields must be named and implement `PayloadField`. Variable-length fields can declare a maximum 
+/// length with `#[ethos(max = N)]`.
+/// 
+/// Generates the `Tampon` implementation and inherent `SCHEMA`, `SCHEMA_HASH`, `LAYOUT`, `discriminant()`,
+/// `is_valid()` and `within_limits()`, plus the borrowed view `<Name>Ref<'a>` implementing `PayloadView`,
+/// without lifetime when no variant has fields.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::EthosPayload;
+/// 
+/// #[derive(Debug, PartialEq, Clone, EthosPayload)]
+/// pub enum ChatPayload {
+///     #[ethos(id = 1)]
+///     Say { 
+///         #[ethos(max = 256)]
+///         text : String,
+///         channel : Option<u8>,
+///     },
+/// 
+///     #[ethos(id = 65535)]
+///     Invalid,
+/// }
+/// 
+/// assert_eq!(ChatPayload::Invalid.discriminant(), 65535);
+/// ```
+/// 
+/// Duplicate ids are refused at compile time.
+/// ```compile_fail
+/// use ethos_core::net::EthosPayload;
+/// 
+/// #[derive(EthosPayload)]
+/// pub enum ChatPayload {
+///     #[ethos(id = 1)]
+///     Say { text : String },
+/// 
+///     #[ethos(id = 1)]
+///     Whisper { text : String },
+/// 
+///     #[ethos(id = 65535)]
+///     Invalid,
+/// }
+/// ```
+/// 
+/// So are missing ids.
+/// ```compile_fail
+/// use ethos_core::net::EthosPayload;
+/// 
+/// #[derive(EthosPayload)]
+/// pub enum ChatPayload {
+///     Say { text : String },
+/// 
+///     #[ethos(id = 65535)]
+///     Invalid,
+/// }
+/// ```
+#[proc_macro_derive(EthosPayload, attributes(ethos))]
+pub fn derive_ethos_payload(input : TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Named field of a variant.
+struct PayloadField {
+    docs : Vec<Attribute>,
+    ident : Ident,
+    ty : Type,
+    max : Option<Expr>,
+}
+
+/// Variant of the payload enum.
+struct PayloadVariant {
+    docs : Vec<Attribute>,
+    ident : Ident,
+    id : u16,
+
+    /// [`None`] for unit variants.
+    fields : Option<Vec<PayloadField>>,
+}
+
+/// Parse `#[ethos(...)]` attributes, calling `parse` for each key.
+fn parse_ethos_attrs(attrs : &[syn::Attribute], mut parse : impl FnMut(&syn::meta::ParseNestedMeta) -> Result<(), Error>) -> Result<(), Error> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("ethos")) {
+        attr.parse_nested_meta(|meta| parse(&meta))?;
+    }
+    Ok(())
+}
+
+/// Doc comments copied to the view.
+fn doc_attrs(attrs : &[Attribute]) -> Vec<Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("doc")).cloned().collect()
+}
+
+/// Doc comment of attributes for layouts, same as `write_messages_payloads!`.
+fn schema_doc(krate : &TokenStream2, docs : &[Attribute]) -> TokenStream2 {
+    let metas = docs.iter().map(|attr| &attr.meta);
+    quote!(#krate::schema_doc!( #( [#metas] )* ))
+}
+
+/// Rust type of a field as printed by `stringify!` in `write_messages_payloads!`, without spaces around punctuations.
+fn type_name(tokens : TokenStream2) -> String {
+    let mut name = String::new();
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                name.push_str(open);
+                name.push_str(type_name(group.stream()).trim_end());
+                name.push_str(close);
+            },
+            TokenTree::Punct(punct) => match punct.as_char() {
+                ',' | ';' => name.push_str(&format!("{} ", punct.as_char())),
+                '+' => name.push_str(" + "),
+                c => name.push(c),
+            },
+            other => {
+                // Words are separated, like `dyn Trait` or `&'a mut T`
+                if name.ends_with(|c : char| c.is_alphanumeric() || c == '_') {
+                    name.push(' ');
+                }
+                name.push_str(&other.to_string());
+            },
+        }
+    }
+    name
+}
+
+/// Add an error to accumulated errors.
+fn push_error(errors : &mut Option<Error>, error : Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
+
+/// Parse and validate variants, reporting every error at once.
+fn parse_variants(input : &DeriveInput) -> Result<Vec<PayloadVariant>, Error> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => return Err(Error::new(Span::call_site(), "EthosPayload can only be derived for enums")),
+    };
+
+    let mut errors = None;
+    let mut variants = Vec::new();
+    let mut ids : HashMap<u16, Ident> = HashMap::new();
+
+    for variant in &data.variants {
+        let mut id = None;
+        let result = parse_ethos_attrs(&variant.attrs, |meta| {
+            if meta.path.is_ident("id") {
+                let lit : LitInt = meta.value()?.parse()?;
+                id = Some((lit.base10_parse::<u16>()?, lit.span()));
+                Ok(())
+            } else {
+                Err(meta.error("unknown ethos attribute on variant, expected `id`"))
+            }
+        });
+        if let Err(err) = result {
+            push_error(&mut errors, err);
+            continue;
+        }
+
+        let (id, id_span) = match id {
+            Some(id) => id,
+            None => {
+                push_error(&mut errors, Error::new(variant.ident.span(), 
+                    format!("missing id on variant `{}`, add `#[ethos(id = ...)]` with a unique u16", variant.ident)));
+                continue;
+            },
+        };
+
+        if let Some(previous) = ids.get(&id) {
+            push_error(&mut errors, Error::new(id_span, format!("duplicate id {} on variant `{}`, already used by variant `{}`", id, variant.ident, previous)));
+        } else {
+            ids.insert(id, variant.ident.clone());
+        }
+
+        if variant.discriminant.is_some() {
+            push_error(&mut errors, Error::new(variant.ident.span(), "explicit discriminant is not supported, use `#[ethos(id = ...)]`"));
+        }
+
+        let fields = match &variant.fields {
+            Fields::Unit => None,
+            Fields::Unnamed(fields) => {
+                push_error(&mut errors, Error::new(fields.span(), "EthosPayload variants must have named fields or no field"));
+                continue;
+            },
+            Fields::Named(fields) => {
+                let mut payload_fields = Vec::new();
+                for field in &fields.named {
+                    let mut max = None;
+                    let result = parse_ethos_attrs(&field.attrs, |meta| {
+                        if meta.path.is_ident("max") {
+                            max = Some(meta.value()?.parse::<Expr>()?);
+                            Ok(())
+                        } else {
+                            Err(meta.error("unknown ethos attribute on field, expected `max`"))
+                        }
+                    });
+                    if let Err(err) = result {
+                        push_error(&mut errors, err);
+                    }
+                    payload_fields.push(PayloadField { docs: doc_attrs(&field.attrs), ident: field.ident.clone().unwrap(), ty: field.ty.clone(), max });
+                }
+                Some(payload_fields)
+            },
+        };
+
+        if variant.ident == INVALID_VARIANT && fields.is_some() {
+            push_error(&mut errors, Error::new(variant.ident.span(), "variant `Invalid` must not have fields"));
+        }
+
+        variants.push(PayloadVariant { docs: doc_attrs(&variant.attrs), ident: variant.ident.clone(), id, fields });
+    }
+
+    if !data.variants.iter().any(|variant| variant.ident == INVALID_VARIANT) {
+        push_error(&mut errors, Error::new(input.ident.span(), 
+            format!("EthosPayload requires a unit variant `{}` receiving unknown ids", INVALID_VARIANT)));
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(variants),
+    }
+}
+
+/// Schema of a variant, matching `stringify!` of `write_messages_payloads!` once whitespaces are ignored.
+fn variant_schema(variant : &PayloadVariant) -> String {
+    let mut schema = variant.ident.to_string();
+
+    if let Some(fields) = &variant.fields {
+        let fields = fields.iter().map(|field| {
+            let max = field.max.as_ref().map(|max| format!("[max {}]", max.to_token_stream())).unwrap_or_default();
+            format!("{} : {} {}", field.ident, field.ty.to_token_stream(), max)
+        }).collect::<Vec<_>>();
+        schema.push_str(&format!("{{ {} }}", fields.join(", ")));
+    }
+
+    format!("{} = {}", schema, variant.id)
+}
+
+/// Maximum length of a field.
+fn max_len(field : &PayloadField) -> TokenStream2 {
+    match &field.max {
+        Some(max) => quote!(usize::MAX.min(#max)),
+        None => quote!(usize::MAX),
+    }
+}
+
+/// Generate code of the derive.
+fn expand(input : &DeriveInput) -> Result<TokenStream2, Error> {
+    let variants = parse_variants(input)?;
+    let name = &input.ident;
+    let krate = quote!(::ethos_core);
+    let field_trait = quote!(#krate::net::PayloadField);
+    let tampon = quote!(#krate::tampon);
+    let disc_size = quote!(::core::mem::size_of::<u16>());
+
+    // Every field type must implement PayloadField, which also bounds generic parameters
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut where_clause = where_clause.cloned().unwrap_or_else(|| syn::parse_quote!(where));
+    for field in variants.iter().flat_map(|variant| variant.fields.iter().flatten()) {
+        let ty = &field.ty;
+        where_clause.predicates.push(syn::parse_quote!(#ty : #field_trait));
+    }
+
+    // View borrows bytes for 'a, with every field type viewable for 'a. Without any field, it borrows nothing.
+    let view = format_ident!("{}Ref", name);
+    let view_trait = quote!(#krate::net::FieldRef<'a>);
+    let borrows = variants.iter().any(|variant| variant.fields.as_ref().is_some_and(|fields| !fields.is_empty()));
+    let buffer_lifetime = if borrows { quote!('a) } else { quote!() };
+    let mut view_generics = input.generics.clone();
+    if borrows {
+        view_generics.params.insert(0, syn::parse_quote!('a));
+    }
+    let view_where_clause = view_generics.make_where_clause();
+    for field in variants.iter().flat_map(|variant| variant.fields.iter().flatten()) {
+        let ty = &field.ty;
+        view_where_clause.predicates.push(syn::parse_quote!(#ty : #view_trait));
+    }
+    let (view_impl_generics, view_ty_generics, view_where_clause) = view_generics.split_for_impl();
+
+    // PayloadView is implemented for any 'a, and derives of the view bound generic parameters
+    let mut payload_view_generics = view_generics.clone();
+    if !borrows {
+        payload_view_generics.params.insert(0, syn::parse_quote!('a));
+    }
+    let (payload_view_impl_generics, _, _) = payload_view_generics.split_for_impl();
+    let mut payload_view_where_clause = view_where_clause.cloned().unwrap();
+    payload_view_where_clause.predicates.push(syn::parse_quote!(#view #view_ty_generics : ::core::clone::Clone + ::core::fmt::Debug + ::core::cmp::PartialEq));
+
+    let mut patterns = Vec::new();
+    let mut wildcards = Vec::new();
+    let mut constructors = Vec::new();
+    let mut ids = Vec::new();
+    let mut bytes_size = Vec::new();
+    let mut serialize = Vec::new();
+    let mut deserialize = Vec::new();
+    let mut deserialize_size = Vec::new();
+    let mut within_limits = Vec::new();
+    let mut schema_hashes = Vec::new();
+    let mut view_variants = Vec::new();
+    let mut view_read = Vec::new();
+    let mut view_owned = Vec::new();
+    let mut layouts = Vec::new();
+
+    for variant in &variants {
+        let ident = &variant.ident;
+        let id = LitInt::new(&variant.id.to_string(), ident.span());
+        ids.push(id.clone());
+
+        let docs = &variant.docs;
+        let doc = schema_doc(&krate, docs);
+        let field_layouts = variant.fields.iter().flatten().map(|field| {
+            let (field_ident, ty, field_doc) = (&field.ident, &field.ty, schema_doc(&krate, &field.docs));
+            let ty_name = type_name(ty.to_token_stream());
+            let max = field.max.as_ref().map(|max| quote!(#krate::schema_max!(#max))).unwrap_or_else(|| quote!(#krate::schema_max!()));
+            quote!(#krate::net::FieldSchema {
+                name : stringify!(#field_ident),
+                ty : #ty_name,
+                size : <#ty as #field_trait>::FIXED_SIZE,
+                max : #max,
+                optional : <#ty as #field_trait>::OPTIONAL,
+                nested : <#ty as #field_trait>::NESTED,
+                doc : #field_doc,
+            })
+        }).collect::<Vec<_>>();
+        layouts.push(quote!(#krate::net::VariantSchema {
+            name : stringify!(#ident),
+            discriminant : #id,
+            fields : &[ #( #field_layouts ),* ],
+            doc : #doc,
+        }));
+
+        let Some(fields) = &variant.fields else {
+            view_variants.push(quote!(#( #docs )* #ident));
+            view_read.push(quote!());
+            view_owned.push(quote!(#name::#ident));
+            patterns.push(quote!(Self::#ident));
+            wildcards.push(quote!(Self::#ident));
+            constructors.push(quote!(Self::#ident));
+            bytes_size.push(quote!(#disc_size));
+            serialize.push(quote!());
+            deserialize.push(quote!());
+            deserialize_size.push(quote!());
+            within_limits.push(quote!(true));
+            continue;
+        };
+
+        // Fields are bound to generated names to avoid clashing with local variables
+        let idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+        let bindings = (0..fields.len()).map(|i| format_ident!("__ethos_field_{}", i)).collect::<Vec<_>>();
+        let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+        let max_lens = fields.iter().map(max_len).collect::<Vec<_>>();
+        let bitmap_size = quote!((0 #( + <#types as #field_trait>::OPTIONAL as usize )*).div_ceil(8));
+
+        let field_docs = fields.iter().map(|field| &field.docs).collect::<Vec<_>>();
+        view_variants.push(quote!(#( #docs )* #ident { #( #( #field_docs )* #idents : <#types as #view_trait>::Ref ),* }));
+        view_owned.push(quote!(#name::#ident { #( #idents : <#types as #view_trait>::from_ref(#bindings) ),* }));
+
+        patterns.push(quote!(Self::#ident { #( #idents : #bindings ),* }));
+        wildcards.push(quote!(Self::#ident { .. }));
+        constructors.push(quote!(Self::#ident { #( #idents : #bindings ),* }));
+        bytes_size.push(quote!(#disc_size + #bitmap_size #( + #field_trait::bitmap_field_size(#bindings) )*));
+
+        serialize.push(quote! {
+            let bitmap = size_written;
+            let mut _bit = 0;
+            size_written += #bitmap_size;
+            buffer[bitmap..size_written].fill(0);
+            #(
+                let (_present, field_size) = #field_trait::write_bitmap_field(#bindings, &mut buffer[size_written..]);
+                size_written += field_size;
+                if <#types as #field_trait>::OPTIONAL {
+                    buffer[bitmap + _bit / 8] |= (_present as u8) << (_bit % 8);
+                    _bit += 1;
+                }
+            )*
+        });
+
+        deserialize.push(quote! {
+            let bitmap = bytes_read;
+            let mut _bit = 0;
+            bytes_read += #bitmap_size;
+            #(
+                let present = !<#types as #field_trait>::OPTIONAL || {
+                    let present = buffer[bitmap + _bit / 8] & (1 << (_bit % 8)) != 0;
+                    _bit += 1;
+                    present
+                };
+                let (#bindings, field_size) = <#types as #field_trait>::read_bitmap_field(&buffer[bytes_read..], present);
+                bytes_read += field_size;
+            )*
+        });
+
+        view_read.push(quote! {
+            let bitmap = bytes_read;
+            let mut _bit = 0;
+            bytes_read += #bitmap_size;
+            #(
+                let present = !<#types as #field_trait>::OPTIONAL || {
+                    let present = buffer[bitmap + _bit / 8] & (1 << (_bit % 8)) != 0;
+                    _bit += 1;
+                    present
+                };
+                let (#bindings, field_size) = <#types as #view_trait>::read_bitmap_ref(&buffer[bytes_read..], present);
+                bytes_read += field_size;
+            )*
+        });
+
+        deserialize_size.push(quote! {
+            let bitmap = size;
+            let mut _bit = 0;
+            size += #bitmap_size;
+            if buffer.len() < size {
+                return Err(#tampon::TamponError::DeserializeSizeBufferIncomplete);
+            } else if max_size > 0 && size > max_size {
+                return Err(#tampon::TamponError::DeserializeSizeGreaterThanMax);
+            }
+            #(
+                let present = !<#types as #field_trait>::OPTIONAL || {
+                    let present = buffer[bitmap + _bit / 8] & (1 << (_bit % 8)) != 0;
+                    _bit += 1;
+                    present
+                };
+                size += <#types as #field_trait>::read_bitmap_field_size(&buffer[size..], present, #max_lens)?;
+                if max_size > 0 && size > max_size {
+                    return Err(#tampon::TamponError::DeserializeSizeGreaterThanMax);
+                }
+            )*
+        });
+
+        within_limits.push(quote!(true #( && #field_trait::within_limits(#bindings, #max_lens) )*));
+
+        for ty in types {
+            schema_hashes.push(quote_spanned!(ty.span() => _hash = #krate::net::handshake::combine_hash(_hash, <#ty as #field_trait>::SCHEMA_HASH);));
+        }
+    }
+
+    let doc = schema_doc(&krate, &doc_attrs(&input.attrs));
+    let view_doc = format!("Borrowed view of [`{}`], with fields read straight from the packed bytes.", name);
+    let view_owned_doc = format!("Owned [`{}`] of the view.", name);
+    let vis = &input.vis;
+
+    let schema = std::iter::once(name.to_string()).chain(variants.iter().map(variant_schema)).collect::<Vec<_>>().join(",");
+
+    Ok(quote! {
+        impl #impl_generics #tampon::Tampon for #name #ty_generics #where_clause {
+            fn bytes_size(&self) -> usize {
+                match self {
+                    #( #patterns => #bytes_size, )*
+                }
+            }
+
+            fn serialize(&self, buffer : &mut [u8]) -> usize {
+                let mut size_written = #field_trait::write_field(&self.discriminant(), buffer);
+                match self {
+                    #( #patterns => { #serialize }, )*
+                }
+                size_written
+            }
+
+            fn deserialize(buffer : &[u8]) -> (Self, usize) {
+                let (discriminant, mut bytes_read) = <u16 as #field_trait>::read_field(buffer);
+                match discriminant {
+                    #( #ids => {
+                        #deserialize
+                        (#constructors, bytes_read)
+                    }, )*
+                    _ => (Self::Invalid, 0),
+                }
+            }
+
+            fn deserialize_size(buffer : &[u8], max_size : usize) -> Result<usize, #tampon::TamponError> {
+                if buffer.len() < #disc_size {
+                    return Err(#tampon::TamponError::DeserializeSizeBufferIncomplete);
+                } else if max_size > 0 && max_size < #disc_size {
+                    return Err(#tampon::TamponError::DeserializeSizeGreaterThanMax);
+                }
+
+                let (discriminant, mut size) = <u16 as #field_trait>::read_field(buffer);
+                match discriminant {
+                    #( #ids => {
+                        #deserialize_size
+                        Ok(size)
+                    }, )*
+                    _ => Err(#tampon::TamponError::DeserializeSizeBufferIncomplete),
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Definition of each payload, used to compute [`Self::SCHEMA_HASH`].
+            pub const SCHEMA : &'static str = #schema;
+
+            /// Hash of [`Self::SCHEMA`] and nested field types. Differs as soon as a payload, field or id differs.
+            pub const SCHEMA_HASH : u64 = {
+                let mut _hash = #krate::net::handshake::schema_hash(Self::SCHEMA);
+                #( #schema_hashes )*
+                _hash
+            };
+
+            /// Layout of each payload, see `PayloadSchema`.
+            pub const LAYOUT : #krate::net::PayloadSchema = #krate::net::PayloadSchema {
+                name : stringify!(#name),
+                variants : &[ #( #layouts ),* ],
+                doc : #doc,
+            };
+
+            /// Returns the id of the variant.
+            pub fn discriminant(&self) -> u16 {
+                match self {
+                    #( #wildcards => #ids, )*
+                }
+            }
+
+            /// Returns true if given id is valid, false otherwise.
+            pub const fn is_valid(discriminant : u16) -> bool {
+                matches!(discriminant, #( #ids )|*)
+            }
+
+            /// Returns true if every field is within its maximum length.
+            pub fn within_limits(&self) -> bool {
+                match self {
+                    #( #patterns => #within_limits, )*
+                }
+            }
+        }
+
+        #[doc = #view_doc]
+        #[derive(Debug, PartialEq, Clone)]
+        #vis enum #view #view_impl_generics #view_where_clause {
+            #( #view_variants, )*
+        }
+
+        impl #view_impl_generics #view #view_ty_generics #view_where_clause {
+            /// View of payload packed at the beginning of buffer.
+            /// 
+            /// Buffer must have been validated with `deserialize_size`.
+            /// 
+            /// # Returns
+            /// View and count of bytes read, which is 0 for unknown id.
+            pub fn read(buffer : &#buffer_lifetime [u8]) -> (Self, usize) {
+                let (discriminant, mut bytes_read) = <u16 as #field_trait>::read_field(buffer);
+                match discriminant {
+                    #( #ids => {
+                        #view_read
+                        (#constructors, bytes_read)
+                    }, )*
+                    _ => (Self::Invalid, 0),
+                }
+            }
+
+            /// Returns the id of the variant, same as the owned payload.
+            pub fn discriminant(&self) -> u16 {
+                match self {
+                    #( #wildcards => #ids, )*
+                }
+            }
+
+            #[doc = #view_owned_doc]
+            pub fn into_owned(self) -> #name #ty_generics {
+                match self {
+                    #( #patterns => #view_owned, )*
+                }
+            }
+        }
+
+        impl #payload_view_impl_generics #krate::net::PayloadView<'a> for #name #ty_generics #payload_view_where_clause {
+            type Ref = #view #view_ty_generics;
+
+            fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+                #view::read(buffer)
+            }
+
+            fn from_ref(view : Self::Ref) -> Self {
+                view.into_owned()
+            }
+        }
+    })
+}
@@ -0,0 +1,466 @@
+-- Wireshark dissector of the Ethos protocol, generated from ethos-core payload definitions.
+-- DO NOT EDIT, regenerate with `cargo run --bin ethos-schema -- dissector ethos.lua`.
+--
+-- Protocol version 1, schema hash 0xc23029c5fa79943e.
+-- Copy in the personal Lua plugins folder of Wireshark, then reload Lua plugins.
+--
+-- Message      : [size u16][discriminant u16][optional fields bitmap][fields][extras]
+-- TCP stream   : messages one after the other.
+-- UDP datagram : [sequence u32] then messages.
+
+local TCP_PORT = 3847
+local UDP_PORT = 38467
+local SEQUENCE_SIZE = 4
+
+local ethos = Proto("ethos", "Ethos Protocol")
+
+-- Payloads of each message by discriminant, with fields in packing order.
+local messages = {
+    client = {
+        name = "ClientMessage",
+        abbr = "ethos.client",
+        extras_size = 0,
+        extras = {
+        },
+        payloads = {
+            [65532] = { name = "Hello", fields = {
+                { name = "version", abbr = "ethos.client.hello.version", ty = { kind = "u16" } },
+                { name = "schema", abbr = "ethos.client.hello.schema", ty = { kind = "u64" } },
+            } },
+            [65530] = { name = "Compression", fields = {
+                { name = "codecs", abbr = "ethos.client.compression.codecs", ty = { kind = "u8" } },
+            } },
+            [65529] = { name = "KeyExchange", fields = {
+                { name = "key", abbr = "ethos.client.key_exchange.key", ty = { kind = "array", len = 32, of = { kind = "u8" } } },
+            } },
+            [65528] = { name = "Sealed", fields = {
+                { name = "counter", abbr = "ethos.client.sealed.counter", ty = { kind = "u64" } },
+                { name = "data", abbr = "ethos.client.sealed.data", ty = { kind = "list", of = { kind = "u8" } } },
+            } },
+            [65527] = { name = "Login", fields = {
+                { name = "account", abbr = "ethos.client.login.account", ty = { kind = "string" } },
+            } },
+            [65526] = { name = "Response", fields = {
+                { name = "proof", abbr = "ethos.client.response.proof", ty = { kind = "array", len = 32, of = { kind = "u8" } } },
+            } },
+            [65525] = { name = "Resume", fields = {
+                { name = "token", abbr = "ethos.client.resume.token", ty = { kind = "array", len = 32, of = { kind = "u8" } } },
+            } },
+            [65524] = { name = "Ping", fields = {
+                { name = "id", abbr = "ethos.client.ping.id", ty = { kind = "u32" } },
+            } },
+            [65523] = { name = "Pong", fields = {
+                { name = "id", abbr = "ethos.client.pong.id", ty = { kind = "u32" } },
+            } },
+            [65522] = { name = "Time", fields = {
+                { name = "sent", abbr = "ethos.client.time.sent", ty = { kind = "u64" } },
+            } },
+            [65521] = { name = "Request", fields = {
+                { name = "id", abbr = "ethos.client.request.id", ty = { kind = "u32" } },
+                { name = "data", abbr = "ethos.client.request.data", ty = { kind = "list", of = { kind = "u8" } } },
+            } },
+            [65534] = { name = "Test", fields = {
+                { name = "p16", abbr = "ethos.client.test.p16", ty = { kind = "u16" } },
+                { name = "p32", abbr = "ethos.client.test.p32", ty = { kind = "u32" } },
+            } },
+            [65535] = { name = "Invalid", fields = {
+            } },
+        },
+    },
+    server = {
+        name = "ServerMessage",
+        abbr = "ethos.server",
+        extras_size = 8,
+        extras = {
+            { name = "timestamp", abbr = "ethos.server.timestamp", ty = { kind = "u64" } },
+        },
+        payloads = {
+            [65530] = { name = "Fragment", fields = {
+                { name = "id", abbr = "ethos.server.fragment.id", ty = { kind = "u32" } },
+                { name = "index", abbr = "ethos.server.fragment.index", ty = { kind = "u16" } },
+                { name = "count", abbr = "ethos.server.fragment.count", ty = { kind = "u16" } },
+                { name = "data", abbr = "ethos.server.fragment.data", ty = { kind = "list", of = { kind = "u8" } } },
+            } },
+            [65528] = { name = "Compressed", fields = {
+                { name = "codec", abbr = "ethos.server.compressed.codec", ty = { kind = "u8" } },
+                { name = "size", abbr = "ethos.server.compressed.size", ty = { kind = "u32" } },
+                { name = "data", abbr = "ethos.server.compressed.data", ty = { kind = "list", of = { kind = "u8" } } },
+            } },
+            [65527] = { name = "Compression", fields = {
+                { name = "codec", abbr = "ethos.server.compression.codec", ty = { kind = "u8" } },
+            } },
+            [65526] = { name = "KeyExchange", fields = {
+                { name = "key", abbr = "ethos.server.key_exchange.key", ty = { kind = "array", len = 32, of = { kind = "u8" } } },
+            } },
+            [65525] = { name = "Sealed", fields = {
+                { name = "counter", abbr = "ethos.server.sealed.counter", ty = { kind = "u64" } },
+                { name = "data", abbr = "ethos.server.sealed.data", ty = { kind = "list", of = { kind = "u8" } } },
+            } },
+            [65524] = { name = "Challenge", fields = {
+                { name = "nonce", abbr = "ethos.server.challenge.nonce", ty = { kind = "array", len = 32, of = { kind = "u8" } } },
+            } },
+            [65523] = { name = "Session", fields = {
+                { name = "token", abbr = "ethos.server.session.token", ty = { kind = "array", len = 32, of = { kind = "u8" } } },
+                { name = "expires", abbr = "ethos.server.session.expires", ty = { kind = "u64" } },
+            } },
+            [65522] = { name = "LoginDenied", fields = {
+            } },
+            [65521] = { name = "Ping", fields = {
+                { name = "id", abbr = "ethos.server.ping.id", ty = { kind = "u32" } },
+            } },
+            [65520] = { name = "Pong", fields = {
+                { name = "id", abbr = "ethos.server.pong.id", ty = { kind = "u32" } },
+            } },
+            [65519] = { name = "Time", fields = {
+                { name = "sent", abbr = "ethos.server.time.sent", ty = { kind = "u64" } },
+                { name = "received", abbr = "ethos.server.time.received", ty = { kind = "u64" } },
+            } },
+            [65518] = { name = "Reply", fields = {
+                { name = "id", abbr = "ethos.server.reply.id", ty = { kind = "u32" } },
+                { name = "data", abbr = "ethos.server.reply.data", ty = { kind = "list", of = { kind = "u8" } } },
+            } },
+            [65532] = { name = "Accept", fields = {
+            } },
+            [65531] = { name = "Reject", fields = {
+                { name = "version", abbr = "ethos.server.reject.version", ty = { kind = "u16" } },
+                { name = "schema", abbr = "ethos.server.reject.schema", ty = { kind = "u64" } },
+            } },
+            [65533] = { name = "Error", fields = {
+                { name = "err", abbr = "ethos.server.error.err", ty = { kind = "u32" } },
+            } },
+            [65534] = { name = "Test", fields = {
+                { name = "p16", abbr = "ethos.server.test.p16", ty = { kind = "u16" } },
+                { name = "p32", abbr = "ethos.server.test.p32", ty = { kind = "u32" } },
+            } },
+            [65535] = { name = "Invalid", fields = {
+            } },
+        },
+    },
+}
+
+-- Size in bytes of scalar types.
+local SIZES = { u8 = 1, u16 = 2, u32 = 4, u64 = 8, u128 = 16, i8 = 1, i16 = 2, i32 = 4, i64 = 8, i128 = 16, f32 = 4, f64 = 8, bool = 1 }
+
+-- ProtoField of a value according to its type, optional values use the field of their type.
+local function protofield(abbr, name, ty)
+    local kind = ty.kind
+    if kind == "option" then
+        return protofield(abbr, name, ty.of)
+    elseif kind == "u8" then
+        return ProtoField.uint8(abbr, name, base.DEC)
+    elseif kind == "u16" then
+        return ProtoField.uint16(abbr, name, base.DEC)
+    elseif kind == "u32" then
+        return ProtoField.uint32(abbr, name, base.DEC)
+    elseif kind == "u64" then
+        return ProtoField.uint64(abbr, name, base.DEC)
+    elseif kind == "i8" then
+        return ProtoField.int8(abbr, name, base.DEC)
+    elseif kind == "i16" then
+        return ProtoField.int16(abbr, name, base.DEC)
+    elseif kind == "i32" then
+        return ProtoField.int32(abbr, name, base.DEC)
+    elseif kind == "i64" then
+        return ProtoField.int64(abbr, name, base.DEC)
+    elseif kind == "f32" then
+        return ProtoField.float(abbr, name)
+    elseif kind == "f64" then
+        return ProtoField.double(abbr, name)
+    elseif kind == "bool" then
+        return ProtoField.bool(abbr, name)
+    elseif kind == "string" then
+        return ProtoField.string(abbr, name)
+    else
+        return ProtoField.bytes(abbr, name)
+    end
+end
+
+local size_field = ProtoField.uint16("ethos.size", "Size", base.DEC)
+local sequence_field = ProtoField.uint32("ethos.sequence", "Sequence", base.DEC)
+local fields = { size_field, sequence_field }
+
+-- Fields of values without name, like elements of lists.
+local value_fields = {}
+for _, kind in ipairs({ "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64", "bool", "string", "bytes" }) do
+    value_fields[kind] = protofield("ethos.value." .. kind, "Value", { kind = kind })
+    fields[#fields + 1] = value_fields[kind]
+end
+
+for _, message in pairs(messages) do
+    local names = {}
+    for discriminant, payload in pairs(message.payloads) do
+        names[discriminant] = payload.name
+        for _, field in ipairs(payload.fields) do
+            field.protofield = protofield(field.abbr, field.name, field.ty)
+            fields[#fields + 1] = field.protofield
+        end
+    end
+    message.payload_field = ProtoField.uint16(message.abbr .. ".payload", "Payload", base.DEC, names)
+    fields[#fields + 1] = message.payload_field
+
+    for _, field in ipairs(message.extras) do
+        field.protofield = protofield(field.abbr, field.name, field.ty)
+        fields[#fields + 1] = field.protofield
+    end
+end
+ethos.fields = fields
+
+local malformed = ProtoExpert.new("ethos.malformed", "Malformed message", expert.group.MALFORMED, expert.severity.ERROR)
+ethos.experts = { malformed }
+
+-- Add value of type packed at offset to tree.
+-- Returns offset after value, or nil if buffer is too short or type can't be decoded.
+local function dissect_value(buffer, offset, ty, tree, field, name)
+    local kind = ty.kind
+    local length = buffer:len()
+    local size = SIZES[kind]
+
+    if size then
+        if offset + size > length then
+            return nil
+        end
+        local item = tree:add_le(field or value_fields[kind], buffer(offset, size))
+        if not field then
+            item:prepend_text(name .. " ")
+        end
+        return offset + size
+    elseif kind == "string" then
+        if offset + 2 > length then
+            return nil
+        end
+        local count = buffer(offset, 2):le_uint()
+        if offset + 2 + count > length then
+            return nil
+        end
+        local text = ""
+        if count > 0 then
+            text = buffer(offset + 2, count):string(ENC_UTF_8)
+        end
+        local item = tree:add(field or value_fields.string, buffer(offset, 2 + count), text)
+        if not field then
+            item:prepend_text(name .. " ")
+        end
+        return offset + 2 + count
+    elseif kind == "option" then
+        if offset + 1 > length then
+            return nil
+        end
+        if buffer(offset, 1):uint() == 0 then
+            tree:add(buffer(offset, 1), name .. ": absent")
+            return offset + 1
+        end
+        return dissect_value(buffer, offset + 1, ty.of, tree, field, name)
+    elseif kind == "list" or kind == "array" then
+        local start = offset
+        local count = ty.len
+        if kind == "list" then
+            if offset + 2 > length then
+                return nil
+            end
+            count = buffer(offset, 2):le_uint()
+            offset = offset + 2
+        end
+
+        -- Bytes are shown at once
+        if ty.of.kind == "u8" then
+            if offset + count > length or offset + count == start then
+                return nil
+            end
+            local bytes = ByteArray.new()
+            if count > 0 then
+                bytes = buffer(offset, count):bytes()
+            end
+            local item = tree:add(field or value_fields.bytes, buffer(start, offset + count - start), bytes)
+            item:append_text(" (" .. count .. " bytes)")
+            if not field then
+                item:prepend_text(name .. " ")
+            end
+            return offset + count
+        end
+
+        if start >= length then
+            return nil
+        end
+        local item = tree:add(buffer(start, math.max(offset - start, 1)), name .. ": " .. count .. " element(s)")
+        for index = 0, count - 1 do
+            offset = dissect_value(buffer, offset, ty.of, item, nil, "[" .. index .. "]")
+            if not offset then
+                return nil
+            end
+        end
+        item:set_len(offset - start)
+        return offset
+    else
+        if offset < length then
+            tree:add(buffer(offset), name .. ": " .. ty.name .. ", not decoded")
+        end
+        return nil
+    end
+end
+
+-- Add fields of payload packed at offset to tree, after its discriminant.
+-- Returns offset after payload, or nil if it couldn't be decoded.
+local function dissect_payload(buffer, offset, payload, tree)
+    local optionals = 0
+    for _, field in ipairs(payload.fields) do
+        if field.ty.kind == "option" then
+            optionals = optionals + 1
+        end
+    end
+
+    local bitmap = offset
+    local bitmap_size = math.floor((optionals + 7) / 8)
+    if bitmap_size > 0 then
+        if offset + bitmap_size > buffer:len() then
+            return nil
+        end
+        tree:add(buffer(bitmap, bitmap_size), "Optional fields bitmap")
+        offset = offset + bitmap_size
+    end
+
+    local bit = 0
+    for _, field in ipairs(payload.fields) do
+        local ty = field.ty
+        local present = true
+        if ty.kind == "option" then
+            local byte = buffer(bitmap + math.floor(bit / 8), 1):uint()
+            present = math.floor(byte / 2 ^ (bit % 8)) % 2 == 1
+            bit = bit + 1
+            ty = ty.of
+        end
+
+        if present then
+            offset = dissect_value(buffer, offset, ty, tree, field.protofield, field.name)
+            if not offset then
+                return nil
+            end
+        else
+            tree:add(buffer(bitmap, bitmap_size), field.name .. ": absent")
+        end
+    end
+    return offset
+end
+
+-- Add a single message to tree, size header included.
+-- Returns name of its payload.
+local function dissect_message(buffer, message, tree)
+    local length = buffer:len()
+    local item = tree:add(ethos, buffer(), message.name)
+    item:add_le(size_field, buffer(0, 2))
+    if length < 4 then
+        item:add_tvb_expert_info(malformed, buffer(), "Message too short for its payload")
+        return "Malformed"
+    end
+
+    local discriminant = buffer(2, 2):le_uint()
+    local payload = message.payloads[discriminant]
+    local name = "Unknown " .. discriminant
+    item:add_le(message.payload_field, buffer(2, 2))
+
+    local offset = nil
+    if payload then
+        name = payload.name
+        offset = dissect_payload(buffer, 4, payload, item)
+    end
+    item:append_text(", " .. name)
+
+    -- Extras are at the end of message, even if payload couldn't be decoded
+    local extras = offset or (message.extras_size and length - message.extras_size)
+    if extras and extras >= 4 then
+        for _, field in ipairs(message.extras) do
+            extras = dissect_value(buffer, extras, field.ty, item, field.protofield, field.name)
+            if not extras then
+                break
+            end
+        end
+    end
+
+    if not offset or extras ~= length then
+        item:add_tvb_expert_info(malformed, buffer(), "Payload doesn't match message size")
+    end
+    return name
+end
+
+-- Add messages of a TCP stream to tree, asking for more segments when a message is incomplete.
+local function dissect_stream(buffer, pinfo, tree, message)
+    local names = {}
+    local offset = 0
+    local length = buffer:len()
+
+    while offset < length do
+        if length - offset < 2 then
+            pinfo.desegment_offset = offset
+            pinfo.desegment_len = DESEGMENT_ONE_MORE_SEGMENT
+            break
+        end
+
+        local size = 2 + buffer(offset, 2):le_uint()
+        if length - offset < size then
+            pinfo.desegment_offset = offset
+            pinfo.desegment_len = size - (length - offset)
+            break
+        end
+
+        names[#names + 1] = dissect_message(buffer(offset, size):tvb(), message, tree)
+        offset = offset + size
+    end
+    return names
+end
+
+-- Add sequence and messages of an UDP datagram to tree.
+local function dissect_datagram(buffer, tree, message)
+    local names = {}
+    local length = buffer:len()
+    if length < SEQUENCE_SIZE then
+        return names
+    end
+
+    local root = tree:add(ethos, buffer(), "Ethos datagram")
+    root:add_le(sequence_field, buffer(0, SEQUENCE_SIZE))
+
+    local offset = SEQUENCE_SIZE
+    while offset < length do
+        local size = nil
+        if length - offset >= 2 then
+            size = 2 + buffer(offset, 2):le_uint()
+        end
+        if not size or length - offset < size then
+            local item = root:add(buffer(offset), "Incomplete message")
+            item:add_tvb_expert_info(malformed, buffer(offset), "Datagram ends inside a message")
+            break
+        end
+
+        names[#names + 1] = dissect_message(buffer(offset, size):tvb(), message, root)
+        offset = offset + size
+    end
+    return names
+end
+
+function ethos.dissector(buffer, pinfo, tree)
+    pinfo.cols.protocol = "ETHOS"
+
+    -- Messages sent to server port are client messages
+    local udp = pinfo.src_port == UDP_PORT or pinfo.dst_port == UDP_PORT
+    local server_port = TCP_PORT
+    if udp then
+        server_port = UDP_PORT
+    end
+    local message = messages.server
+    if pinfo.dst_port == server_port then
+        message = messages.client
+    end
+
+    local names
+    if udp then
+        names = dissect_datagram(buffer, tree, message)
+    else
+        names = dissect_stream(buffer, pinfo, tree, message)
+    end
+    if #names > 0 then
+        pinfo.cols.info = message.name .. ": " .. table.concat(names, ", ")
+    end
+    return buffer:len()
+end
+
+DissectorTable.get("tcp.port"):add(TCP_PORT, ethos)
+DissectorTable.get("udp.port"):add(UDP_PORT, ethos)
@@ -0,0 +1,86 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+
+//! Export the protocol schema, check wire compatibility between exported schemas and generate a Wireshark dissector.
+
+use std::{env, fs, process::ExitCode};
+
+use ethos_core::net::{check_compatibility, schema_json, wireshark_dissector, Error, Snapshot};
+
+const USAGE : &str = "usage:
+  ethos-schema export [FILE]     Write schema of this build as JSON, to standard output if no file.
+  ethos-schema check OLD [NEW]   Report changes from OLD to NEW schema, or to this build if no NEW.
+  ethos-schema dissector [FILE]  Write Wireshark Lua dissector of this build, to standard output if no file.
+
+check exits with 1 if any change is breaking.";
+
+/// Read a snapshot file.
+fn read(path : &str) -> Result<Snapshot, String> {
+    let json = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+    Snapshot::parse(&json).map_err(|err : Error| format!("{}: {}", path, err))
+}
+
+/// Print changes between snapshots and returns true if none is breaking.
+fn check(old : &Snapshot, new : &Snapshot) -> bool {
+    let changes = check_compatibility(old, new);
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    let breaking = changes.iter().filter(|change| change.is_breaking()).count();
+    println!("{} change(s), {} breaking", changes.len(), breaking);
+    breaking == 0
+}
+
+fn main() -> ExitCode {
+    let args : Vec<String> = env::args().skip(1).collect();
+    let args : Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["export"] => {
+            print!("{}", schema_json());
+            Ok(true)
+        },
+        ["export", file] => fs::write(file, schema_json()).map(|_| true).map_err(|err| format!("{}: {}", file, err)),
+        ["dissector"] => {
+            print!("{}", wireshark_dissector());
+            Ok(true)
+        },
+        ["dissector", file] => fs::write(file, wireshark_dissector()).map(|_| true).map_err(|err| format!("{}: {}", file, err)),
+        ["check", old] => read(old).map(|old| check(&old, &Snapshot::current())),
+        ["check", old, new] => read(old).and_then(|old| Ok(check(&old, &read(new)?))),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::from(2)
+        },
+    }
+}
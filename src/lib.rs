@@ -27,6 +27,17 @@ SOFTWARE.
 //! ethos-server and ethos-client.
 
 
+// Let code generated by derive macros refer to this crate from inside it.
+extern crate self as ethos_core;
+
 pub mod net;
 
+// Used by code generated by derive macros.
+#[doc(hidden)]
+pub use tampon;
+
+// Used by macros to name generated views.
+#[doc(hidden)]
+pub use concat_idents;
+
 
@@ -0,0 +1,780 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::{collections::{HashMap, HashSet}, fmt::Debug, io::{Read, Write}};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::net::{handshake::{read_message, write_message}, ClientMessage, ClientPayload, Clock, Error, ErrorKind, ServerMessage, ServerPayload};
+
+/// Size in bytes of login challenges.
+pub const CHALLENGE_SIZE : usize = 32;
+
+/// Size in bytes of login proofs.
+pub const PROOF_SIZE : usize = 32;
+
+/// Size in bytes of session tokens.
+pub const TOKEN_SIZE : usize = 32;
+
+/// Default lifetime of session tokens in milliseconds (24 hours).
+pub const TOKEN_LIFETIME : u64 = 24 * 60 * 60 * 1000;
+
+/// Proof of knowledge of the secret of an account, answering a challenge.
+/// 
+/// HMAC-SHA256 of the challenge followed by the account name, keyed with the secret.
+pub fn login_proof(secret : &[u8], account : &str, challenge : &[u8; CHALLENGE_SIZE]) -> [u8; PROOF_SIZE] {
+    mac(secret, account, challenge).finalize().into_bytes().into()
+}
+
+/// Check a proof in constant time.
+fn verify_proof(secret : &[u8], account : &str, challenge : &[u8; CHALLENGE_SIZE], proof : &[u8; PROOF_SIZE]) -> bool {
+    mac(secret, account, challenge).verify_slice(proof).is_ok()
+}
+
+/// HMAC of a challenge and account.
+fn mac(secret : &[u8], account : &str, challenge : &[u8; CHALLENGE_SIZE]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(challenge);
+    mac.update(account.as_bytes());
+    mac
+}
+
+/// Random bytes from the operating system.
+fn random<const N : usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    getrandom::getrandom(&mut bytes).expect("Operating system random source unavailable");
+    bytes
+}
+
+/// Opaque token issued by server, identifying a [`Session`].
+/// 
+/// Its value is hidden from [`Debug`] so it doesn't leak into logs.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionToken([u8; TOKEN_SIZE]);
+
+impl SessionToken {
+    /// Create a [`SessionToken`] from its bytes.
+    pub const fn from_bytes(bytes : [u8; TOKEN_SIZE]) -> SessionToken {
+        SessionToken(bytes)
+    }
+
+    /// Bytes of the token.
+    pub const fn to_bytes(&self) -> [u8; TOKEN_SIZE] {
+        self.0
+    }
+}
+
+impl Debug for SessionToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SessionToken(..)")
+    }
+}
+
+/// Session of an authenticated account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    /// Name of the account.
+    account : String,
+
+    /// Token of the session.
+    token : SessionToken,
+
+    /// Server time in milliseconds when the session was issued.
+    issued : u64,
+
+    /// Server time in milliseconds when the session expires.
+    expires : u64,
+}
+
+impl Session {
+    /// Name of the account.
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Token of the session.
+    pub fn token(&self) -> SessionToken {
+        self.token
+    }
+
+    /// Server time in milliseconds when the session was issued.
+    pub fn issued(&self) -> u64 {
+        self.issued
+    }
+
+    /// Server time in milliseconds when the session expires.
+    pub fn expires(&self) -> u64 {
+        self.expires
+    }
+
+    /// Returns true if the session expired at given server time.
+    pub fn is_expired(&self, now : u64) -> bool {
+        now >= self.expires
+    }
+}
+
+/// Challenge sent to a client, kept by server until its [`ClientPayload::Response`].
+/// 
+/// Created by [`LoginServer::challenge`] and consumed by [`LoginServer::login`], so each challenge is answered once.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Challenge {
+    /// Account login was requested for.
+    account : String,
+
+    /// Random bytes sent to client.
+    nonce : [u8; CHALLENGE_SIZE],
+}
+
+impl Challenge {
+    /// Account login was requested for.
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Random bytes sent to client.
+    pub fn nonce(&self) -> &[u8; CHALLENGE_SIZE] {
+        &self.nonce
+    }
+}
+
+/// Account store of server, checking login proofs.
+/// 
+/// Hooks are called by [`LoginServer`] to keep the store informed of sessions and let it revoke them.
+pub trait Authenticator {
+    /// Returns true if proof of an account answers the challenge, usually compared to [`login_proof`] of its secret.
+    /// 
+    /// Must return false for unknown accounts and compare in constant time.
+    fn verify(&self, account : &str, challenge : &[u8; CHALLENGE_SIZE], proof : &[u8; PROOF_SIZE]) -> bool;
+
+    /// Hook called when a session is issued by login or resume.
+    fn issued(&mut self, _session : &Session) {}
+
+    /// Hook called when a session ends, either expired, revoked or replaced on resume.
+    fn ended(&mut self, _session : &Session) {}
+
+    /// Returns true if the store revoked a session, checked on resume.
+    fn is_revoked(&self, _session : &Session) -> bool {
+        false
+    }
+}
+
+/// [`Authenticator`] keeping secrets of accounts in memory, for tests and tools.
+/// 
+/// Secrets are stored in clear, so persistent stores should implement [`Authenticator`] instead.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryAuthenticator {
+    /// Secrets of accounts.
+    secrets : HashMap<String, Box<[u8]>>,
+
+    /// Server time when sessions of accounts were revoked.
+    revoked : HashMap<String, u64>,
+
+    /// Tokens of sessions issued and not ended.
+    active : HashSet<SessionToken>,
+}
+
+impl InMemoryAuthenticator {
+    /// Create a new empty [`InMemoryAuthenticator`].
+    pub fn new() -> InMemoryAuthenticator {
+        Self::default()
+    }
+
+    /// Add or replace an account and its secret.
+    pub fn add(&mut self, account : &str, secret : &[u8]) {
+        self.secrets.insert(account.to_string(), secret.into());
+    }
+
+    /// Remove an account. Returns true if it existed.
+    /// 
+    /// Sessions of a removed account can't be resumed.
+    pub fn remove(&mut self, account : &str) -> bool {
+        self.secrets.remove(account).is_some()
+    }
+
+    /// Revoke sessions of an account issued up to given server time.
+    pub fn revoke(&mut self, account : &str, now : u64) {
+        self.revoked.insert(account.to_string(), now);
+    }
+
+    /// Count of sessions issued and not ended.
+    pub fn active(&self) -> usize {
+        self.active.len()
+    }
+}
+
+impl Authenticator for InMemoryAuthenticator {
+    fn verify(&self, account : &str, challenge : &[u8; CHALLENGE_SIZE], proof : &[u8; PROOF_SIZE]) -> bool {
+        self.secrets.get(account).is_some_and(|secret| verify_proof(secret, account, challenge, proof))
+    }
+
+    fn issued(&mut self, session : &Session) {
+        self.active.insert(session.token);
+    }
+
+    fn ended(&mut self, session : &Session) {
+        self.active.remove(&session.token);
+    }
+
+    fn is_revoked(&self, session : &Session) -> bool {
+        !self.secrets.contains_key(&session.account) || self.revoked.get(&session.account).is_some_and(|revoked| session.issued <= *revoked)
+    }
+}
+
+/// Server side of logins, issuing and tracking [`Session`] of authenticated accounts.
+/// 
+/// # Login
+/// 1. Client sends [`ClientPayload::Login`], server answers [`ServerPayload::Challenge`] with [`LoginServer::challenge`].
+/// 2. Client sends [`ClientPayload::Response`], server answers [`ServerPayload::Session`] with [`LoginServer::login`].
+/// 
+/// On reconnect, client sends [`ClientPayload::Resume`] and server answers with [`LoginServer::resume`], replacing the token.
+/// Refused logins and resumes are answered with [`ServerPayload::LoginDenied`].
+/// 
+/// # Note(s)
+/// Challenge and token travel in clear unless sealed by a `SecureSession` (feature `secure`).
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ InMemoryAuthenticator, LoginClient, LoginServer, ManualClock, TOKEN_LIFETIME };
+/// 
+/// let mut accounts = InMemoryAuthenticator::new();
+/// accounts.add("alice", b"secret");
+/// let mut server = LoginServer::new(accounts, ManualClock::new(0), TOKEN_LIFETIME);
+/// let client = LoginClient::new("alice", b"secret");
+/// 
+/// let (answer, challenge) = server.challenge(&client.login());
+/// let response = client.respond(&answer).unwrap();
+/// let (answer, session) = server.login(challenge.unwrap(), &response);
+/// 
+/// assert_eq!(client.conclude(&answer).unwrap().token(), session.unwrap().token());
+/// ```
+pub struct LoginServer<A : Authenticator, C : Clock> {
+    /// Account store.
+    authenticator : A,
+
+    /// Clock of server time.
+    clock : C,
+
+    /// Lifetime of sessions in milliseconds.
+    lifetime : u64,
+
+    /// Sessions issued by token.
+    sessions : HashMap<SessionToken, Session>,
+}
+
+impl<A : Authenticator, C : Clock> LoginServer<A, C> {
+    /// Create a new [`LoginServer`].
+    /// 
+    /// # Argument(s)
+    /// * `authenticator` - [`Authenticator`] of the account store.
+    /// * `clock` - [`Clock`] of server time, used for timestamps and expiry.
+    /// * `lifetime` - Lifetime of sessions in milliseconds, usually [`TOKEN_LIFETIME`].
+    pub fn new(authenticator : A, clock : C, lifetime : u64) -> LoginServer<A, C> {
+        LoginServer { authenticator, clock, lifetime, sessions: HashMap::new() }
+    }
+
+    /// Reference to the [`Authenticator`].
+    pub fn authenticator(&self) -> &A {
+        &self.authenticator
+    }
+
+    /// Mutable reference to the [`Authenticator`].
+    pub fn authenticator_mut(&mut self) -> &mut A {
+        &mut self.authenticator
+    }
+
+    /// Count of sessions tracked, including expired ones not purged yet.
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Returns true if no session is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Session of a token, [`None`] if unknown or expired.
+    pub fn session(&self, token : &SessionToken) -> Option<&Session> {
+        let now = self.clock.now();
+        self.sessions.get(token).filter(|session| !session.is_expired(now))
+    }
+
+    /// Server answer to the login request of client.
+    /// 
+    /// A challenge is sent even for unknown accounts so they can't be discovered.
+    /// 
+    /// # Returns
+    /// Answer to send to client and [`Result`] which is:
+    /// - [`Ok`]: [`Challenge`] to keep until the response of client.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ClientPayload::Login`]. Answer is [`ServerPayload::LoginDenied`].
+    pub fn challenge(&mut self, message : &ClientMessage) -> (ServerMessage, Result<Challenge, Error>) {
+        match &message.payload {
+            ClientPayload::Login { account } => {
+                let challenge = Challenge { account: account.clone(), nonce: random() };
+                (ServerMessage::new(self.clock.now(), ServerPayload::Challenge { nonce: challenge.nonce }), Ok(challenge))
+            },
+            _ => (self.denied(), Err(ErrorKind::InvalidMessage.into())),
+        }
+    }
+
+    /// Server check of the response of client to a challenge.
+    /// 
+    /// # Returns
+    /// Answer to send to client and [`Result`] which is:
+    /// - [`Ok`]: [`Session`] issued.
+    /// - [`Err`]: Answer is [`ServerPayload::LoginDenied`] and error is
+    ///     1. [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) if proof is refused by [`Authenticator::verify`].
+    ///     2. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ClientPayload::Response`].
+    pub fn login(&mut self, challenge : Challenge, message : &ClientMessage) -> (ServerMessage, Result<Session, Error>) {
+        match &message.payload {
+            ClientPayload::Response { proof } if self.authenticator.verify(&challenge.account, &challenge.nonce, proof) => 
+                self.issue(challenge.account),
+            ClientPayload::Response { .. } => (self.denied(), Err(ErrorKind::AuthenticationFailed.into())),
+            _ => (self.denied(), Err(ErrorKind::InvalidMessage.into())),
+        }
+    }
+
+    /// Server answer to a client resuming a session on reconnect.
+    /// 
+    /// The resumed session is replaced by a new one, so each token is used once.
+    /// 
+    /// # Returns
+    /// Answer to send to client and [`Result`] which is:
+    /// - [`Ok`]: New [`Session`] of the account.
+    /// - [`Err`]: Answer is [`ServerPayload::LoginDenied`] and error is
+    ///     1. [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) if token is unknown, expired or revoked.
+    ///     2. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ClientPayload::Resume`].
+    pub fn resume(&mut self, message : &ClientMessage) -> (ServerMessage, Result<Session, Error>) {
+        let ClientPayload::Resume { token } = message.payload else {
+            return (self.denied(), Err(ErrorKind::InvalidMessage.into()));
+        };
+
+        let Some(session) = self.sessions.remove(&SessionToken(token)) else {
+            return (self.denied(), Err(ErrorKind::AuthenticationFailed.into()));
+        };
+
+        self.authenticator.ended(&session);
+        if session.is_expired(self.clock.now()) || self.authenticator.is_revoked(&session) {
+            return (self.denied(), Err(ErrorKind::AuthenticationFailed.into()));
+        }
+
+        self.issue(session.account)
+    }
+
+    /// Revoke a session. Returns true if it existed.
+    pub fn revoke(&mut self, token : &SessionToken) -> bool {
+        match self.sessions.remove(token) {
+            Some(session) => {
+                self.authenticator.ended(&session);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Revoke all sessions of an account. Returns the count of sessions revoked.
+    pub fn revoke_account(&mut self, account : &str) -> usize {
+        self.end_where(|session| session.account == account)
+    }
+
+    /// Remove expired sessions. Returns the count of sessions removed.
+    /// 
+    /// Expired sessions are refused anyway, this only frees memory and calls [`Authenticator::ended`].
+    pub fn purge(&mut self) -> usize {
+        let now = self.clock.now();
+        self.end_where(|session| session.is_expired(now))
+    }
+
+    /// Perform the server side of a login or resume on a blocking stream.
+    /// 
+    /// Only messages of the login are read from stream, so it can be wrapped in a 
+    /// [`ServerConnection`](crate::net::ServerConnection) afterward. Answers are sent even if refused.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`Session`] issued.
+    /// - [`Err`]: Any error of [`LoginServer::challenge`], [`LoginServer::login`] or [`LoginServer::resume`], or reading and writing errors.
+    pub fn server<T : Read + Write>(&mut self, stream : &mut T) -> Result<Session, Error> {
+        let message : ClientMessage = read_message(stream)?;
+
+        let (answer, result) = match message.payload {
+            ClientPayload::Resume { .. } => self.resume(&message),
+            _ => {
+                let (answer, challenge) = self.challenge(&message);
+                write_message(stream, &answer)?;
+                let challenge = challenge?;
+                self.login(challenge, &read_message(stream)?)
+            },
+        };
+
+        write_message(stream, &answer)?;
+        result
+    }
+
+    /// Issue a new session of an account.
+    fn issue(&mut self, account : String) -> (ServerMessage, Result<Session, Error>) {
+        let now = self.clock.now();
+        let session = Session { account, token: SessionToken(random()), issued: now, expires: now.saturating_add(self.lifetime) };
+
+        self.authenticator.issued(&session);
+        self.sessions.insert(session.token, session.clone());
+        (ServerMessage::new(now, ServerPayload::Session { token: session.token.0, expires: session.expires }), Ok(session))
+    }
+
+    /// End sessions matching a predicate. Returns the count of sessions ended.
+    fn end_where<F : Fn(&Session) -> bool>(&mut self, predicate : F) -> usize {
+        let tokens : Vec<SessionToken> = self.sessions.values().filter(|session| predicate(session)).map(|session| session.token).collect();
+        for token in &tokens {
+            self.revoke(token);
+        }
+        tokens.len()
+    }
+
+    /// Answer refusing a login or resume.
+    fn denied(&self) -> ServerMessage {
+        ServerMessage::new(self.clock.now(), ServerPayload::LoginDenied)
+    }
+}
+
+/// Client side of logins, proving knowledge of the secret of an account.
+/// 
+/// See [`LoginServer`] for the login exchange.
+pub struct LoginClient {
+    /// Name of the account.
+    account : String,
+
+    /// Secret of the account.
+    secret : Box<[u8]>,
+}
+
+impl LoginClient {
+    /// Create a new [`LoginClient`] of an account and its secret.
+    pub fn new(account : &str, secret : &[u8]) -> LoginClient {
+        LoginClient { account: account.to_string(), secret: secret.into() }
+    }
+
+    /// Name of the account.
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Message sent by client to start a login.
+    pub fn login(&self) -> ClientMessage {
+        ClientMessage::new(ClientPayload::Login { account: self.account.clone() })
+    }
+
+    /// Message sent by client on reconnect to resume a session.
+    pub fn resume(token : SessionToken) -> ClientMessage {
+        ClientMessage::new(ClientPayload::Resume { token: token.0 })
+    }
+
+    /// Client answer to the challenge of server.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`ClientPayload::Response`] to send to server.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) if server denied the login.
+    ///     2. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ServerPayload::Challenge`].
+    pub fn respond(&self, message : &ServerMessage) -> Result<ClientMessage, Error> {
+        match &message.payload {
+            ServerPayload::Challenge { nonce } => Ok(ClientMessage::new(ClientPayload::Response { proof: login_proof(&self.secret, &self.account, nonce) })),
+            ServerPayload::LoginDenied => Err(ErrorKind::AuthenticationFailed.into()),
+            _ => Err(ErrorKind::InvalidMessage.into()),
+        }
+    }
+
+    /// Client check of the session issued by server, after a login or resume.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`Session`] issued, to keep for reconnect.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) if server denied the login or resume.
+    ///     2. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ServerPayload::Session`].
+    pub fn conclude(&self, message : &ServerMessage) -> Result<Session, Error> {
+        match message.payload {
+            ServerPayload::Session { token, expires } => 
+                Ok(Session { account: self.account.clone(), token: SessionToken(token), issued: message.timestamp, expires }),
+            ServerPayload::LoginDenied => Err(ErrorKind::AuthenticationFailed.into()),
+            _ => Err(ErrorKind::InvalidMessage.into()),
+        }
+    }
+
+    /// Perform the client side of a login on a blocking stream, or of a resume if a token is given.
+    /// 
+    /// Only answers of server are read from stream, so it can be wrapped in a 
+    /// [`ClientConnection`](crate::net::ClientConnection) afterward.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`Session`] issued.
+    /// - [`Err`]: Any error of [`LoginClient::respond`] or [`LoginClient::conclude`], or reading and writing errors.
+    pub fn client<T : Read + Write>(&self, stream : &mut T, token : Option<SessionToken>) -> Result<Session, Error> {
+        match token {
+            Some(token) => write_message(stream, &Self::resume(token))?,
+            None => {
+                write_message(stream, &self.login())?;
+                let response = self.respond(&read_message(stream)?)?;
+                write_message(stream, &response)?;
+            },
+        }
+
+        self.conclude(&read_message(stream)?)
+    }
+}
+
+impl Debug for LoginClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoginClient").field("account", &self.account).finish_non_exhaustive()
+    }
+}
+
+
+/// This module test [LoginServer], [LoginClient] and [InMemoryAuthenticator] with a [ManualClock](crate::net::ManualClock).
+/// 
+/// # Verification(s)
+/// V1 : [LoginServer::login] issues a [Session] to client proving its secret, calling [Authenticator::issued].
+/// V2 : [LoginServer::login] denies wrong secrets, unknown accounts and responses to another challenge.
+/// V3 : [LoginServer::resume] replaces the session so each token is used once.
+/// V4 : Expired sessions are refused on resume and removed by [LoginServer::purge].
+/// V5 : Sessions revoked by [LoginServer] or by [Authenticator::is_revoked] are refused on resume, login still works.
+/// V6 : Unexpected messages return [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) and denials [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) on both sides.
+/// V7 : [LoginClient::client] and [LoginServer::server] login then resume over loopback connections.
+#[cfg(test)]
+mod tests {
+    use std::{net::{TcpListener, TcpStream}, thread};
+
+    use crate::net::{ClientMessage, ClientPayload, Clock, Error, ErrorKind, InMemoryAuthenticator, LoginClient, LoginServer, ManualClock, ServerMessage, ServerPayload, 
+        SessionToken, TOKEN_LIFETIME};
+
+    use super::login_proof;
+
+    /// Lifetime of test sessions.
+    const LIFETIME : u64 = 1000;
+
+    /// Server with accounts alice and bob.
+    fn server(clock : &ManualClock) -> LoginServer<InMemoryAuthenticator, ManualClock> {
+        let mut accounts = InMemoryAuthenticator::new();
+        accounts.add("alice", b"alice secret");
+        accounts.add("bob", b"bob secret");
+        LoginServer::new(accounts, clock.clone(), LIFETIME)
+    }
+
+    /// Full login exchange.
+    fn login(server : &mut LoginServer<InMemoryAuthenticator, ManualClock>, client : &LoginClient) -> (ServerMessage, Result<super::Session, Error>) {
+        let (answer, challenge) = server.challenge(&client.login());
+        let response = client.respond(&answer).unwrap();
+        server.login(challenge.unwrap(), &response)
+    }
+
+    #[test]
+    fn v1_login() {
+        // V1 : [LoginServer::login] issues a [Session] to client proving its secret, calling [Authenticator::issued].
+        let clock = ManualClock::new(100);
+        let mut server = server(&clock);
+        let client = LoginClient::new("alice", b"alice secret");
+
+        let (answer, session) = login(&mut server, &client);
+        let session = session.unwrap();
+        assert_eq!(session.account(), "alice");
+        assert_eq!((session.issued(), session.expires()), (100, 100 + LIFETIME));
+        assert_eq!(answer, ServerMessage::new(100, ServerPayload::Session { token: session.token().to_bytes(), expires: 100 + LIFETIME }));
+        assert_eq!(client.conclude(&answer), Ok(session.clone()));
+
+        assert_eq!(server.session(&session.token()), Some(&session));
+        assert_eq!(server.authenticator().active(), 1);
+        assert_eq!(server.len(), 1);
+
+        // Each login has its own token
+        let (_, other) = login(&mut server, &client);
+        assert_ne!(other.unwrap().token(), session.token());
+        assert_eq!(server.authenticator().active(), 2);
+        assert_eq!(format!("{:?}", session.token()), "SessionToken(..)");
+    }
+
+    #[test]
+    fn v2_login_denied() {
+        // V2 : [LoginServer::login] denies wrong secrets, unknown accounts and responses to another challenge.
+        let clock = ManualClock::new(0);
+        let mut server = server(&clock);
+
+        for client in [LoginClient::new("alice", b"bob secret"), LoginClient::new("carol", b"carol secret"), LoginClient::new("bob", b"")] {
+            let (answer, session) = login(&mut server, &client);
+            assert_eq!(answer.payload, ServerPayload::LoginDenied);
+            assert_eq!(session, Err(ErrorKind::AuthenticationFailed.into()));
+        }
+
+        // Response to a previous challenge
+        let client = LoginClient::new("alice", b"alice secret");
+        let (first, _) = server.challenge(&client.login());
+        let (second, challenge) = server.challenge(&client.login());
+        assert_ne!(first, second);
+        let (answer, session) = server.login(challenge.unwrap(), &client.respond(&first).unwrap());
+        assert_eq!((answer.payload, session), (ServerPayload::LoginDenied, Err(ErrorKind::AuthenticationFailed.into())));
+
+        // Proof of another account
+        let (answer, challenge) = server.challenge(&client.login());
+        let ServerPayload::Challenge { nonce } = answer.payload else { panic!("Challenge expected") };
+        let response = ClientMessage::new(ClientPayload::Response { proof: login_proof(b"alice secret", "bob", &nonce) });
+        assert_eq!(server.login(challenge.unwrap(), &response).1, Err(ErrorKind::AuthenticationFailed.into()));
+
+        assert!(server.is_empty());
+        assert_eq!(server.authenticator().active(), 0);
+    }
+
+    #[test]
+    fn v3_resume() {
+        // V3 : [LoginServer::resume] replaces the session so each token is used once.
+        let clock = ManualClock::new(0);
+        let mut server = server(&clock);
+        let client = LoginClient::new("bob", b"bob secret");
+        let session = login(&mut server, &client).1.unwrap();
+
+        clock.advance(LIFETIME / 2);
+        let (answer, resumed) = server.resume(&LoginClient::resume(session.token()));
+        let resumed = resumed.unwrap();
+        assert_eq!(client.conclude(&answer), Ok(resumed.clone()));
+        assert_eq!(resumed.account(), "bob");
+        assert_eq!(resumed.expires(), LIFETIME / 2 + LIFETIME);
+        assert_ne!(resumed.token(), session.token());
+
+        assert_eq!(server.session(&session.token()), None);
+        assert_eq!((server.len(), server.authenticator().active()), (1, 1));
+
+        let (answer, result) = server.resume(&LoginClient::resume(session.token()));
+        assert_eq!((answer.payload, result), (ServerPayload::LoginDenied, Err(ErrorKind::AuthenticationFailed.into())));
+        let (_, result) = server.resume(&LoginClient::resume(SessionToken::from_bytes([0; 32])));
+        assert_eq!(result, Err(ErrorKind::AuthenticationFailed.into()));
+    }
+
+    #[test]
+    fn v4_expiry() {
+        // V4 : Expired sessions are refused on resume and removed by [LoginServer::purge].
+        let clock = ManualClock::new(0);
+        let mut server = server(&clock);
+        let alice = login(&mut server, &LoginClient::new("alice", b"alice secret")).1.unwrap();
+        clock.advance(LIFETIME / 2);
+        let bob = login(&mut server, &LoginClient::new("bob", b"bob secret")).1.unwrap();
+
+        clock.advance(LIFETIME / 2);
+        assert_eq!(server.session(&alice.token()), None);
+        assert!(server.session(&bob.token()).is_some());
+        assert_eq!(server.resume(&LoginClient::resume(alice.token())).1, Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!((server.len(), server.authenticator().active()), (1, 1));
+
+        clock.advance(LIFETIME);
+        assert_eq!(server.purge(), 1);
+        assert_eq!(server.purge(), 0);
+        assert!(server.is_empty());
+        assert_eq!(server.authenticator().active(), 0);
+
+        // Default lifetime
+        let mut server = LoginServer::new(InMemoryAuthenticator::new(), ManualClock::new(0), TOKEN_LIFETIME);
+        server.authenticator_mut().add("alice", b"a");
+        assert_eq!(login(&mut server, &LoginClient::new("alice", b"a")).1.unwrap().expires(), TOKEN_LIFETIME);
+    }
+
+    #[test]
+    fn v5_revoke() {
+        // V5 : Sessions revoked by [LoginServer] or by [Authenticator::is_revoked] are refused on resume, login still works.
+        let clock = ManualClock::new(0);
+        let mut server = server(&clock);
+        let alice = LoginClient::new("alice", b"alice secret");
+        let bob = LoginClient::new("bob", b"bob secret");
+
+        let session = login(&mut server, &alice).1.unwrap();
+        assert!(server.revoke(&session.token()));
+        assert!(!server.revoke(&session.token()));
+        assert_eq!(server.resume(&LoginClient::resume(session.token())).1, Err(ErrorKind::AuthenticationFailed.into()));
+
+        let sessions = [login(&mut server, &alice).1.unwrap(), login(&mut server, &alice).1.unwrap(), login(&mut server, &bob).1.unwrap()];
+        assert_eq!(server.revoke_account("alice"), 2);
+        assert_eq!(server.resume(&LoginClient::resume(sessions[0].token())).1, Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(server.authenticator().active(), 1);
+
+        // Revoked by account store
+        clock.advance(10);
+        server.authenticator_mut().revoke("bob", clock.now());
+        assert_eq!(server.resume(&LoginClient::resume(sessions[2].token())).1, Err(ErrorKind::AuthenticationFailed.into()));
+        clock.advance(10);
+        let session = login(&mut server, &bob).1.unwrap();
+        let session = server.resume(&LoginClient::resume(session.token())).1.unwrap();
+
+        // Removed account
+        assert!(server.authenticator_mut().remove("bob"));
+        assert_eq!(server.resume(&LoginClient::resume(session.token())).1, Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(login(&mut server, &bob).1, Err(ErrorKind::AuthenticationFailed.into()));
+        assert!(server.is_empty());
+        assert_eq!(server.authenticator().active(), 0);
+    }
+
+    #[test]
+    fn v6_invalid() {
+        // V6 : Unexpected messages return [`ErrorKind::InvalidMessage`] and denials [`ErrorKind::AuthenticationFailed`] on both sides.
+        let clock = ManualClock::new(0);
+        let mut server = server(&clock);
+        let client = LoginClient::new("alice", b"alice secret");
+        let unexpected = ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 });
+
+        let (answer, result) = server.challenge(&unexpected);
+        assert_eq!((answer.payload, result), (ServerPayload::LoginDenied, Err(ErrorKind::InvalidMessage.into())));
+        let (_, challenge) = server.challenge(&client.login());
+        assert_eq!(server.login(challenge.unwrap(), &unexpected).1, Err(ErrorKind::InvalidMessage.into()));
+        assert_eq!(server.resume(&unexpected).1, Err(ErrorKind::InvalidMessage.into()));
+
+        let denied = ServerMessage::new(0, ServerPayload::LoginDenied);
+        let accept = ServerMessage::new(0, ServerPayload::Accept);
+        assert_eq!(client.respond(&denied), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(client.respond(&accept), Err(ErrorKind::InvalidMessage.into()));
+        assert_eq!(client.conclude(&denied), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(client.conclude(&accept), Err(ErrorKind::InvalidMessage.into()));
+    }
+
+    #[test]
+    fn v7_loopback() {
+        // V7 : [LoginClient::client] and [LoginServer::server] login then resume over loopback connections.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = thread::spawn(move || {
+            let clock = ManualClock::new(0);
+            let mut server = server(&clock);
+            let login = server.server(&mut listener.accept().unwrap().0).unwrap();
+            let resumed = server.server(&mut listener.accept().unwrap().0).unwrap();
+            let denied = server.server(&mut listener.accept().unwrap().0);
+            (login, resumed, denied)
+        });
+
+        let client = LoginClient::new("alice", b"alice secret");
+        let login = client.client(&mut TcpStream::connect(addr).unwrap(), None).unwrap();
+        let resumed = client.client(&mut TcpStream::connect(addr).unwrap(), Some(login.token())).unwrap();
+        let denied = LoginClient::new("alice", b"wrong").client(&mut TcpStream::connect(addr).unwrap(), None);
+
+        assert_eq!(handle.join().unwrap(), (login, resumed, Err(ErrorKind::AuthenticationFailed.into())));
+        assert_eq!(denied, Err(ErrorKind::AuthenticationFailed.into()));
+    }
+}
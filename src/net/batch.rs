@@ -0,0 +1,295 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::marker::PhantomData;
+
+use crate::net::{decoder::{decode_frame, Frame}, Error, ErrorKind, Message, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Builder packing many [`Message`] into one contiguous buffer, up to a size budget.
+/// 
+/// The buffer can then be sent in a single write and walked with [`BatchIter`] on reception.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ BatchIter, MessageBatch, ServerMessage, ServerPayload, Error };
+/// 
+/// let mut batch = MessageBatch::new(1024);
+/// for i in 0..10 {
+///     batch.push(&ServerMessage::new(i, ServerPayload::Test { p16: 1, p32: 2 })).unwrap();
+/// }
+/// 
+/// // Bytes written to socket in one call
+/// let bytes = batch.bytes();
+/// 
+/// let messages = BatchIter::<ServerMessage>::new(bytes).collect::<Result<Vec<_>, Error>>().unwrap();
+/// assert_eq!(messages.len(), 10);
+/// ```
+pub struct MessageBatch<M : Message> {
+    /// Packed messages.
+    buffer : Vec<u8>,
+
+    /// Maximum size of buffer in bytes.
+    budget : usize,
+
+    /// Count of messages packed.
+    count : usize,
+
+    message : PhantomData<M>,
+}
+
+impl<M : Message> MessageBatch<M> {
+    /// Create a new empty [`MessageBatch`] of maximum `budget` bytes, size headers included.
+    pub fn new(budget : usize) -> MessageBatch<M> {
+        MessageBatch { buffer: Vec::with_capacity(budget), budget, count: 0, message: PhantomData }
+    }
+
+    /// Maximum size of batch in bytes.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Bytes left before reaching budget.
+    pub fn remaining(&self) -> usize {
+        self.budget - self.buffer.len()
+    }
+
+    /// Returns true if not even an empty message fits anymore.
+    pub fn is_full(&self) -> bool {
+        self.remaining() <= MESSAGE_SIZE_TYPE_SIZE
+    }
+
+    /// Returns true if no message was packed.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Count of messages packed.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Packed messages, size headers included.
+    pub fn bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Remove every message, keeping allocated memory.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.count = 0;
+    }
+
+    /// Pack a message at the end of batch.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Message was packed.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::BufferSizeTooSmall`](crate::net::ErrorKind::BufferSizeTooSmall) if message doesn't fit the remaining budget. Batch should be sent and cleared.
+    ///     2. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if message size exceed [`Message::MAX_SIZE`].
+    pub fn push(&mut self, message : &M) -> Result<(), Error> {
+        if message.size() > M::MAX_SIZE {
+            return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, M::MAX_SIZE, message.size()));
+        }
+
+        let frame_size = MESSAGE_SIZE_TYPE_SIZE + message.size();
+        if frame_size > self.remaining() {
+            return Err(Error::size(ErrorKind::BufferSizeTooSmall, frame_size, self.remaining()));
+        }
+
+        let start = self.buffer.len();
+        self.buffer.resize(start + frame_size, 0);
+
+        match message.pack_bytes(&mut self.buffer[start..]) {
+            Ok(_) => {
+                self.count += 1;
+                Ok(())
+            },
+            Err(err) => {
+                self.buffer.truncate(start);
+                Err(err)
+            },
+        }
+    }
+}
+
+/// Iterator over the messages of a batch received.
+/// 
+/// Yields each decoded message or its [`Error`]. Iteration ends after a corrupted size header
+/// or an incomplete last message, since the next message can't be located.
+pub struct BatchIter<'a, M : Message> {
+    /// Bytes not decoded yet.
+    bytes : &'a [u8],
+
+    message : PhantomData<M>,
+}
+
+impl<'a, M : Message> BatchIter<'a, M> {
+    /// Create a new [`BatchIter`] over bytes of a batch.
+    pub fn new(bytes : &'a [u8]) -> BatchIter<'a, M> {
+        BatchIter { bytes, message: PhantomData }
+    }
+}
+
+impl<M : Message> Iterator for BatchIter<'_, M> {
+    type Item = Result<M, Error>;
+
+    /// Decode the next message of batch.
+    /// 
+    /// Errors are the same as [`MessageDecoder`](crate::net::MessageDecoder), plus [`ErrorKind::IncompleteMessage`](crate::net::ErrorKind::IncompleteMessage) 
+    /// if batch ends in the middle of a message.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        match decode_frame::<M>(self.bytes) {
+            Some(Frame::Message(result, size)) => {
+                self.bytes = &self.bytes[size..];
+                Some(result)
+            },
+            Some(Frame::Corrupted(err)) => {
+                self.bytes = &[];
+                Some(Err(err))
+            },
+            None => {
+                self.bytes = &[];
+                Some(Err(ErrorKind::IncompleteMessage.into()))
+            },
+        }
+    }
+}
+
+
+/// This module test [MessageBatch] and [BatchIter].
+/// 
+/// # Verification(s)
+/// V1 : [MessageBatch::push] messages are yielded in order by [BatchIter].
+/// V2 : [MessageBatch::push] returns [`ErrorKind::BufferSizeTooSmall`](crate::net::ErrorKind::BufferSizeTooSmall) when budget is reached, without packing.
+/// V3 : [MessageBatch::push] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when message exceed limit.
+/// V4 : [BatchIter] yields each message error and continues with the next message.
+/// V5 : [BatchIter] yields [`ErrorKind::IncompleteMessage`](crate::net::ErrorKind::IncompleteMessage) for truncated batch and [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) for corrupted size, then ends.
+/// V6 : [MessageBatch::clear] empties batch.
+#[cfg(test)]
+mod tests {
+    use crate::net::{BatchIter, ClientMessage, ClientPayload, ErrorKind, Message, MessageBatch, ServerMessage, ServerPayload, CLIENT_MSG_MAX_SIZE, MESSAGE_SIZE_TYPE_SIZE};
+
+    fn message(i : u16) -> ServerMessage {
+        ServerMessage::new(i as u64, ServerPayload::Test { p16: i, p32: i as u32 })
+    }
+
+    /// Size of a packed test message, size header included.
+    fn frame_size() -> usize {
+        MESSAGE_SIZE_TYPE_SIZE + message(0).size()
+    }
+
+    #[test]
+    fn v1_push_iter() {
+        // V1 : [MessageBatch::push] messages are yielded in order by [BatchIter].
+        let mut batch = MessageBatch::new(100 * frame_size());
+        for i in 0..100 {
+            batch.push(&message(i)).unwrap();
+        }
+
+        assert_eq!(batch.len(), 100);
+        assert!(batch.is_full());
+        assert_eq!(BatchIter::new(batch.bytes()).collect::<Vec<_>>(), (0..100).map(|i| Ok(message(i))).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn v2_budget_reached() {
+        // V2 : [MessageBatch::push] returns [`ErrorKind::BufferSizeTooSmall`] when budget is reached, without packing.
+        let mut batch = MessageBatch::new(2 * frame_size() + 3);
+        batch.push(&message(0)).unwrap();
+        batch.push(&message(1)).unwrap();
+
+        assert!(!batch.is_full());
+        assert_eq!(batch.remaining(), 3);
+        assert_eq!(batch.push(&message(2)), Err(ErrorKind::BufferSizeTooSmall.into()));
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch.bytes().len(), 2 * frame_size());
+    }
+
+    #[test]
+    fn v3_greater_than_limit() {
+        // V3 : [MessageBatch::push] returns [`ErrorKind::MessageSizeGreaterThanLimit`] when message exceed limit.
+        let mut message = ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 });
+        message.size = CLIENT_MSG_MAX_SIZE as u16 + 1;
+        let mut batch = MessageBatch::new(4096);
+
+        assert_eq!(batch.push(&message), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+        assert!(batch.is_empty());
+    }
+
+    #[test]
+    fn v4_message_error() {
+        // V4 : [BatchIter] yields each message error and continues with the next message.
+        let mut batch = MessageBatch::new(1024);
+        batch.push(&message(0)).unwrap();
+        batch.push(&message(1)).unwrap();
+        let mut bytes = batch.bytes().to_vec();
+
+        // Unknown discriminant in first message
+        bytes[MESSAGE_SIZE_TYPE_SIZE] = 0;
+        bytes[MESSAGE_SIZE_TYPE_SIZE + 1] = 0;
+
+        let mut iter = BatchIter::<ServerMessage>::new(&bytes);
+        assert_eq!(iter.next(), Some(Err(ErrorKind::InvalidMessage.into())));
+        assert_eq!(iter.next(), Some(Ok(message(1))));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn v5_truncated_corrupted() {
+        // V5 : [BatchIter] yields [`ErrorKind::IncompleteMessage`] for truncated batch and [`ErrorKind::MessageSizeGreaterThanLimit`] for corrupted size, then ends.
+        let mut batch = MessageBatch::new(1024);
+        batch.push(&message(0)).unwrap();
+        batch.push(&message(1)).unwrap();
+        let bytes = batch.bytes();
+
+        let mut iter = BatchIter::<ServerMessage>::new(&bytes[..bytes.len() - 1]);
+        assert_eq!(iter.next(), Some(Ok(message(0))));
+        assert_eq!(iter.next(), Some(Err(ErrorKind::IncompleteMessage.into())));
+        assert_eq!(iter.next(), None);
+
+        let mut corrupted = (CLIENT_MSG_MAX_SIZE as u16 + 1).to_le_bytes().to_vec();
+        corrupted.extend_from_slice(bytes);
+        let mut iter = BatchIter::<ClientMessage>::new(&corrupted);
+        assert_eq!(iter.next(), Some(Err(ErrorKind::MessageSizeGreaterThanLimit.into())));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn v6_clear() {
+        // V6 : [MessageBatch::clear] empties batch.
+        let mut batch = MessageBatch::new(1024);
+        batch.push(&message(0)).unwrap();
+        batch.clear();
+
+        assert!(batch.is_empty());
+        assert!(batch.bytes().is_empty());
+        assert_eq!(batch.remaining(), 1024);
+    }
+}
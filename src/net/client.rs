@@ -48,12 +48,121 @@ crate::write_messages_payloads!{
     ClientPayload,
    
    
+   /// First message sent after connecting, checked by [`Handshake`](crate::net::Handshake).
+   /// 
+   /// Discriminant MUST never change so that any version can read it.
+   Hello { 
+        /// Protocol version of client.
+        version : u16, 
+        
+        /// Schema hash of client payloads.
+        schema : u64 
+    } = 65532,
+
+   /// Codecs supported by client, sent after handshake to negotiate compression with a [`Compressor`](crate::net::Compressor).
+   Compression { 
+        /// Bitmask of ids of [`Codec`](crate::net::Codec) supported.
+        codecs : u8 
+    } = 65530,
+
+   /// Public key of client starting a secure session with a `KeyExchange` (feature `secure`).
+   KeyExchange { 
+        /// X25519 public key of client.
+        key : [u8; 32] 
+    } = 65529,
+
+   /// Client message sealed by a `SecureSession` (feature `secure`).
+   Sealed { 
+        /// Counter of the message, used as nonce and to reject replays.
+        counter : u64, 
+        
+        /// Encrypted packed message followed by its authentication tag.
+        data : Box<[u8]> 
+    } = 65528,
+
+   /// Account of client starting a login, answered by [`ServerPayload::Challenge`](crate::net::ServerPayload::Challenge).
+   Login { 
+        /// Name of the account.
+        account : String [max crate::net::ACCOUNT_MAX_LEN] 
+    } = 65527,
+
+   /// Proof of client answering [`ServerPayload::Challenge`](crate::net::ServerPayload::Challenge).
+   Response { 
+        /// HMAC-SHA256 of the challenge and account, keyed with the secret of the account.
+        proof : [u8; 32] 
+    } = 65526,
+
+   /// Session token issued by server, sent on reconnect instead of login.
+   Resume { 
+        /// Opaque token of [`ServerPayload::Session`](crate::net::ServerPayload::Session).
+        token : [u8; 32] 
+    } = 65525,
+
+   /// Keep-alive sent by a [`Heartbeat`](crate::net::Heartbeat), answered by [`ServerPayload::Pong`](crate::net::ServerPayload::Pong).
+   Ping { 
+        /// Id of the ping, echoed by the pong.
+        id : u32 
+    } = 65524,
+
+   /// Answer to [`ServerPayload::Ping`](crate::net::ServerPayload::Ping).
+   Pong { 
+        /// Id of the ping answered.
+        id : u32 
+    } = 65523,
+
+   /// Clock synchronisation request of a [`ClockSync`](crate::net::ClockSync), answered by [`ServerPayload::Time`](crate::net::ServerPayload::Time).
+   Time { 
+        /// Client time in milliseconds when the request was sent.
+        sent : u64 
+    } = 65522,
+
+   /// Client message expecting a [`ServerPayload::Reply`](crate::net::ServerPayload::Reply), sent by a [`RpcClient`](crate::net::RpcClient).
+   Request { 
+        /// Id of the request, echoed by the reply.
+        id : u32, 
+        
+        /// Packed client message of the request.
+        data : Box<[u8]> 
+    } = 65521,
+
    /// Test payload used for various unit test case
    Test { p16 : u16, p32 : u32 } = 65534,
 
     /// Invalid or malformed payload that are suspicious.
     /// 
-    /// Since client to server communications are always handled by
-    /// TCP, no loss or modification of data should have arised.
+    /// Client messages are sent by TCP or by UDP datagrams. Datagrams may be lost, duplicated, reordered
+    /// or corrupted, so an invalid payload received by UDP is not necessarily suspicious.
+    /// Counted as a strike by a [`RateLimiter`](crate::net::RateLimiter).
     Invalid = 65535
-}
\ No newline at end of file
+}
+
+
+/// Client message with payloads used for various unit test case, kept out of [`ClientPayload`] and the protocol schema.
+#[cfg(test)]
+pub(crate) mod test_payloads {
+    use tampon::Tampon;
+
+    use crate::{net::CLIENT_MSG_MAX_SIZE, write_messages_struct};
+
+    write_messages_struct!{ CLIENT_MSG_MAX_SIZE,
+        /// Client message of test payloads.
+        TestClientMessage < TestClientPayload >
+    }
+
+    crate::write_messages_payloads!{
+        /// Client payloads used for various unit test case.
+        TestClientPayload,
+
+        /// Test payload with variable-length fields
+        Variable { text : String [max 32], list : Vec<u32> [max 8], blob : Box<[u8]> [max 64], fixed : [u16; 4] } = 65533,
+
+        /// Test payload with optional fields
+        Optional { 
+            o1 : Option<u8>, o2 : Option<u16>, o3 : Option<u32>, o4 : Option<u64>, o5 : Option<i8>, 
+            required : u16, o6 : Option<String> [max 8], o7 : Option<Vec<u16>> [max 4], o8 : Option<bool>, o9 : Option<f32> 
+        } = 65531,
+
+        /// Invalid or malformed payload.
+        Invalid = 65535
+    }
+}
@@ -0,0 +1,109 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::{sync::{atomic::{AtomicU64, Ordering}, Arc}, time::Instant};
+
+/// Source of monotonic time in milliseconds.
+/// 
+/// Components depending on time take a [`Clock`] so they can be tested without sleeping, using a [`ManualClock`].
+pub trait Clock {
+    /// Current time in milliseconds. Must never decrease.
+    fn now(&self) -> u64;
+}
+
+impl<C : Clock> Clock for &C {
+    fn now(&self) -> u64 {
+        (*self).now()
+    }
+}
+
+/// [`Clock`] counting milliseconds elapsed since its creation, using [`Instant`].
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicClock {
+    start : Instant,
+}
+
+impl MonotonicClock {
+    /// Create a new [`MonotonicClock`] starting at 0.
+    pub fn new() -> MonotonicClock {
+        MonotonicClock { start: Instant::now() }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+}
+
+/// [`Clock`] that only moves when told to. Clones share the same time.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ Clock, ManualClock };
+/// 
+/// let clock = ManualClock::new(0);
+/// let shared = clock.clone();
+/// 
+/// clock.advance(100);
+/// assert_eq!(shared.now(), 100);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now : Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Create a new [`ManualClock`] at a given time in milliseconds.
+    pub fn new(now : u64) -> ManualClock {
+        ManualClock { now: Arc::new(AtomicU64::new(now)) }
+    }
+
+    /// Move time forward by milliseconds.
+    pub fn advance(&self, millis : u64) {
+        self.now.fetch_add(millis, Ordering::Relaxed);
+    }
+
+    /// Set time in milliseconds.
+    /// 
+    /// # Panic(s)
+    /// Will panic! if time is smaller than current time since [`Clock`] is monotonic.
+    pub fn set(&self, now : u64) {
+        assert!(now >= self.now(), "ManualClock can't go backward!");
+        self.now.store(now, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> u64 {
+        self.now.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,421 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::collections::VecDeque;
+
+use crate::net::{ClientMessage, ClientPayload, Clock, ServerMessage, ServerPayload};
+
+/// Default count of most recent samples [`ClockSync`] filters.
+pub const CLOCK_SYNC_WINDOW : usize = 8;
+
+/// Answer a [`ClientPayload::Time`] request with a [`ServerPayload::Time`].
+/// 
+/// # Argument(s)
+/// * `request` - Message received from client.
+/// * `received` - Server time in milliseconds when the request was received.
+/// * `now` - Server time in milliseconds when the answer is sent.
+/// 
+/// # Returns
+/// Answer to send, [`None`] if message isn't a time request.
+pub fn time_response(request : &ClientMessage, received : u64, now : u64) -> Option<ServerMessage> {
+    match request.payload {
+        ClientPayload::Time { sent } => Some(ServerMessage::new(now, ServerPayload::Time { sent, received })),
+        _ => None,
+    }
+}
+
+/// Single NTP-style measure of the offset between server and client clocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSample {
+    /// Server time minus client time in milliseconds.
+    pub offset : i64,
+
+    /// Round-trip time in milliseconds, excluding time spent by server.
+    pub delay : u64,
+}
+
+impl TimeSample {
+    /// Compute a sample from the 4 times of an exchange, [`None`] if times are inconsistent.
+    /// 
+    /// # Argument(s)
+    /// * `sent` - Client time when the request was sent.
+    /// * `received` - Server time when the request was received.
+    /// * `answered` - Server time when the answer was sent.
+    /// * `now` - Client time when the answer was received.
+    pub fn new(sent : u64, received : u64, answered : u64, now : u64) -> Option<TimeSample> {
+        let round_trip = now.checked_sub(sent)?;
+        let server = answered.checked_sub(received)?;
+        let delay = round_trip.checked_sub(server)?;
+        let offset = ((received as i128 - sent as i128) + (answered as i128 - now as i128)) / 2;
+
+        Some(TimeSample { offset: i64::try_from(offset).ok()?, delay })
+    }
+}
+
+/// Client side estimation of server time from [`ServerMessage::timestamp`](crate::net::ServerMessage).
+/// 
+/// Each exchange of [`ClientPayload::Time`] and [`ServerPayload::Time`] gives a [`TimeSample`]. Since the offset error of a sample
+/// is at most half its delay, only the sample with the smallest delay among the most recent ones is used, rejecting samples
+/// slowed by congestion or jitter. The estimate then moves smoothly toward it so that server time never jumps.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ ClockSync, ManualClock, time_response };
+/// 
+/// let clock = ManualClock::new(0);
+/// let mut sync = ClockSync::new(&clock);
+/// 
+/// // Server clock is 10 seconds ahead, each way takes 20 ms
+/// let request = sync.request();
+/// clock.advance(20);
+/// let answer = time_response(&request, 10_020, 10_020).unwrap();
+/// clock.advance(20);
+/// assert!(sync.receive(&answer));
+/// 
+/// assert_eq!(sync.offset(), Some(10_000));
+/// assert_eq!(sync.server_time(), Some(10_040));
+/// assert_eq!(sync.uncertainty(), Some(20));
+/// ```
+pub struct ClockSync<C : Clock> {
+    clock : C,
+
+    /// Count of most recent samples filtered.
+    window : usize,
+
+    /// Most recent samples, oldest first.
+    samples : VecDeque<TimeSample>,
+
+    /// Smoothed offset in milliseconds.
+    offset : Option<i64>,
+}
+
+impl<C : Clock> ClockSync<C> {
+    /// Create a new [`ClockSync`] filtering the [`CLOCK_SYNC_WINDOW`] most recent samples.
+    pub fn new(clock : C) -> ClockSync<C> {
+        ClockSync::with_window(clock, CLOCK_SYNC_WINDOW)
+    }
+
+    /// Create a new [`ClockSync`] filtering a count of most recent samples.
+    /// 
+    /// # Panic(s)
+    /// Will panic! if window is 0.
+    pub fn with_window(clock : C, window : usize) -> ClockSync<C> {
+        assert!(window > 0, "ClockSync window can't be 0!");
+        ClockSync { clock, window, samples: VecDeque::with_capacity(window), offset: None }
+    }
+
+    /// Time request to send to server.
+    pub fn request(&self) -> ClientMessage {
+        ClientMessage::new(ClientPayload::Time { sent: self.clock.now() })
+    }
+
+    /// Handle a message received from server.
+    /// 
+    /// # Returns
+    /// True if message was a time answer giving a valid sample.
+    pub fn receive(&mut self, message : &ServerMessage) -> bool {
+        let ServerPayload::Time { sent, received } = message.payload else {
+            return false;
+        };
+
+        match TimeSample::new(sent, received, message.timestamp, self.clock.now()) {
+            Some(sample) => {
+                if self.samples.len() == self.window {
+                    self.samples.pop_front();
+                }
+                self.samples.push_back(sample);
+
+                // Computed in i128 since a forged answer can give an offset far from the current one
+                let best = self.best().expect("samples can't be empty").offset;
+                self.offset = Some(match self.offset {
+                    Some(offset) => (offset as i128 + (best as i128 - offset as i128) / 8) as i64,
+                    None => best,
+                });
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Sample with the smallest delay among the most recent ones.
+    pub fn best(&self) -> Option<TimeSample> {
+        self.samples.iter().min_by_key(|sample| sample.delay).copied()
+    }
+
+    /// Most recent samples, oldest first.
+    pub fn samples(&self) -> impl Iterator<Item = &TimeSample> {
+        self.samples.iter()
+    }
+
+    /// Estimated server time minus client time in milliseconds, [`None`] until a sample is received.
+    pub fn offset(&self) -> Option<i64> {
+        self.offset
+    }
+
+    /// Estimated maximum error of [`offset`](Self::offset) in milliseconds, [`None`] until a sample is received.
+    pub fn uncertainty(&self) -> Option<u64> {
+        let best = self.best()?;
+        Some((best.delay / 2).saturating_add(self.offset?.abs_diff(best.offset)))
+    }
+
+    /// Estimated current server time in milliseconds, [`None`] until a sample is received.
+    pub fn server_time(&self) -> Option<u64> {
+        self.server_time_at(self.clock.now())
+    }
+
+    /// Estimated server time in milliseconds of a client time, [`None`] until a sample is received.
+    pub fn server_time_at(&self, client : u64) -> Option<u64> {
+        Some(client.saturating_add_signed(self.offset?))
+    }
+
+    /// Estimated client time in milliseconds of a [`ServerMessage::timestamp`](crate::net::ServerMessage), [`None`] until a sample is received.
+    pub fn client_time_at(&self, server : u64) -> Option<u64> {
+        Some(server.saturating_add_signed(self.offset?.checked_neg()?))
+    }
+}
+
+
+/// This module test [ClockSync] and [time_response] over a simulated network.
+/// 
+/// # Verification(s)
+/// V1 : [TimeSample::new] computes offset and delay, and rejects inconsistent times.
+/// V2 : [time_response] answers time requests only.
+/// V3 : [ClockSync::receive] ignores other payloads and inconsistent answers.
+/// V4 : [ClockSync] estimates server time within uncertainty with symmetric latency and jitter.
+/// V5 : [ClockSync] estimates server time within uncertainty with asymmetric latency and jitter.
+/// V6 : [ClockSync] filters delay spikes out.
+/// V7 : [ClockSync] only keeps the most recent samples of window.
+/// V8 : [ClockSync::server_time_at] and [ClockSync::client_time_at] convert times both ways.
+/// V9 : [ClockSync::receive] of a forged answer far from the current offset doesn't overflow.
+#[cfg(test)]
+mod tests {
+    use crate::net::{ClientMessage, ClientPayload, Clock, ClockSync, ManualClock, ServerMessage, ServerPayload, TimeSample, time_response};
+
+    /// Server time minus client time.
+    const OFFSET : i64 = 123_456;
+
+    /// Time in milliseconds spent by server answering.
+    const PROCESSING : u64 = 2;
+
+    /// Deterministic network with latency and jitter in milliseconds for each way.
+    struct SimulatedNetwork {
+        clock : ManualClock,
+        up : u64,
+        down : u64,
+        jitter : u64,
+        seed : u64,
+    }
+
+    impl SimulatedNetwork {
+        fn new(up : u64, down : u64, jitter : u64) -> SimulatedNetwork {
+            SimulatedNetwork { clock: ManualClock::new(1_000_000), up, down, jitter, seed: 0x2545F4914F6CDD1D }
+        }
+
+        fn random(&mut self, max : u64) -> u64 {
+            self.seed ^= self.seed << 13;
+            self.seed ^= self.seed >> 7;
+            self.seed ^= self.seed << 17;
+            if max == 0 { 0 } else { self.seed % (max + 1) }
+        }
+
+        fn server_now(&self) -> u64 {
+            self.clock.now().saturating_add_signed(OFFSET)
+        }
+
+        /// Exchange request and answer with extra latency added to the way up.
+        fn exchange(&mut self, sync : &mut ClockSync<ManualClock>, spike : u64) -> bool {
+            let request = sync.request();
+
+            let up = self.up + self.random(self.jitter) + spike;
+            self.clock.advance(up);
+            let received = self.server_now();
+            self.clock.advance(PROCESSING);
+            let answer = time_response(&request, received, self.server_now()).unwrap();
+
+            let down = self.down + self.random(self.jitter);
+            self.clock.advance(down);
+            let valid = sync.receive(&answer);
+
+            self.clock.advance(1000);
+            valid
+        }
+    }
+
+    /// Assert the estimated server time is within uncertainty and return the error.
+    fn assert_within_uncertainty(network : &SimulatedNetwork, sync : &ClockSync<ManualClock>) -> u64 {
+        let error = sync.server_time().unwrap().abs_diff(network.server_now());
+        assert!(error <= sync.uncertainty().unwrap(), "error {} > uncertainty {}", error, sync.uncertainty().unwrap());
+        error
+    }
+
+    #[test]
+    fn v1_time_sample() {
+        // V1 : [TimeSample::new] computes offset and delay, and rejects inconsistent times.
+        assert_eq!(TimeSample::new(100, 1_010, 1_015, 125), Some(TimeSample { offset: 900, delay: 20 }));
+        assert_eq!(TimeSample::new(1_000, 10, 15, 1_025), Some(TimeSample { offset: -1000, delay: 20 }));
+
+        // Answer received before request sent
+        assert_eq!(TimeSample::new(100, 1_010, 1_015, 99), None);
+
+        // Server answered before receiving
+        assert_eq!(TimeSample::new(100, 1_010, 1_009, 125), None);
+
+        // Server spent more time than round trip
+        assert_eq!(TimeSample::new(100, 1_010, 1_050, 125), None);
+    }
+
+    #[test]
+    fn v2_time_response() {
+        // V2 : [time_response] answers time requests only.
+        let answer = time_response(&ClientMessage::new(ClientPayload::Time { sent: 7 }), 50, 52).unwrap();
+        assert_eq!(answer.timestamp, 52);
+        assert!(matches!(answer.payload, ServerPayload::Time { sent: 7, received: 50 }));
+
+        assert!(time_response(&ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 }), 50, 52).is_none());
+    }
+
+    #[test]
+    fn v3_receive_ignores() {
+        // V3 : [ClockSync::receive] ignores other payloads and inconsistent answers.
+        let clock = ManualClock::new(100);
+        let mut sync = ClockSync::new(&clock);
+
+        assert!(!sync.receive(&ServerMessage::new(500, ServerPayload::Test { p16: 1, p32: 2 })));
+        assert!(!sync.receive(&ServerMessage::new(500, ServerPayload::Time { sent: 200, received: 400 })));
+        assert!(!sync.receive(&ServerMessage::new(400, ServerPayload::Time { sent: 50, received: 500 })));
+
+        assert_eq!(sync.samples().count(), 0);
+        assert_eq!(sync.offset(), None);
+        assert_eq!(sync.uncertainty(), None);
+        assert_eq!(sync.server_time(), None);
+    }
+
+    #[test]
+    fn v4_symmetric_latency() {
+        // V4 : [ClockSync] estimates server time within uncertainty with symmetric latency and jitter.
+        let mut network = SimulatedNetwork::new(40, 40, 20);
+        let mut sync = ClockSync::new(network.clock.clone());
+
+        for _ in 0..64 {
+            assert!(network.exchange(&mut sync, 0));
+            assert_within_uncertainty(&network, &sync);
+        }
+
+        // Best samples have little jitter left
+        assert!(assert_within_uncertainty(&network, &sync) <= 10);
+        assert!(sync.uncertainty().unwrap() <= 50);
+    }
+
+    #[test]
+    fn v5_asymmetric_latency() {
+        // V5 : [ClockSync] estimates server time within uncertainty with asymmetric latency and jitter.
+        let mut network = SimulatedNetwork::new(10, 90, 15);
+        let mut sync = ClockSync::new(network.clock.clone());
+
+        for _ in 0..64 {
+            assert!(network.exchange(&mut sync, 0));
+            assert_within_uncertainty(&network, &sync);
+        }
+
+        // Asymmetry can't be seen from client, offset is wrong by about half the difference of ways
+        let error = assert_within_uncertainty(&network, &sync);
+        assert!((30..=50).contains(&error), "error {}", error);
+    }
+
+    #[test]
+    fn v6_delay_spikes() {
+        // V6 : [ClockSync] filters delay spikes out.
+        let mut network = SimulatedNetwork::new(30, 30, 5);
+        let mut sync = ClockSync::new(network.clock.clone());
+
+        for _ in 0..16 {
+            network.exchange(&mut sync, 0);
+        }
+        let offset = sync.offset().unwrap();
+        let uncertainty = sync.uncertainty().unwrap();
+
+        // Spike of 1 second every other exchange
+        for i in 0..16 {
+            network.exchange(&mut sync, if i % 2 == 0 { 1000 } else { 0 });
+            assert!(sync.offset().unwrap().abs_diff(offset) <= 5);
+            assert!(sync.uncertainty().unwrap() <= uncertainty + 5);
+            assert_within_uncertainty(&network, &sync);
+        }
+    }
+
+    #[test]
+    fn v7_window() {
+        // V7 : [ClockSync] only keeps the most recent samples of window.
+        let mut network = SimulatedNetwork::new(30, 30, 0);
+        let mut sync = ClockSync::with_window(network.clock.clone(), 4);
+
+        // Very fast exchange is best while in window
+        network.up = 1;
+        network.down = 1;
+        network.exchange(&mut sync, 0);
+        network.up = 30;
+        network.down = 30;
+        for count in 2..=4 {
+            network.exchange(&mut sync, 0);
+            assert_eq!(sync.samples().count(), count);
+            assert_eq!(sync.best().unwrap().delay, 2);
+        }
+
+        network.exchange(&mut sync, 0);
+        assert_eq!(sync.samples().count(), 4);
+        assert_eq!(sync.best().unwrap().delay, 60);
+    }
+
+    #[test]
+    fn v8_conversions() {
+        // V8 : [ClockSync::server_time_at] and [ClockSync::client_time_at] convert times both ways.
+        let clock = ManualClock::new(0);
+        let mut sync = ClockSync::new(&clock);
+        clock.advance(10);
+        assert!(sync.receive(&ServerMessage::new(100_005, ServerPayload::Time { sent: 0, received: 100_005 })));
+        assert_eq!(sync.offset(), Some(100_000));
+
+        assert_eq!(sync.server_time_at(500), Some(100_500));
+        assert_eq!(sync.client_time_at(100_500), Some(500));
+        assert_eq!(sync.client_time_at(5), Some(0));
+    }
+
+    #[test]
+    fn v9_forged_answer() {
+        // V9 : [ClockSync::receive] of a forged answer far from the current offset doesn't overflow.
+        let clock = ManualClock::new(0);
+        let mut sync = ClockSync::new(&clock);
+        clock.advance(10);
+        assert!(sync.receive(&ServerMessage::new(0, ServerPayload::Time { sent: 0, received: 0 })));
+        assert_eq!(sync.offset(), Some(-5));
+
+        // No delay, so it becomes the best sample
+        let forged = i64::MAX as u64;
+        assert!(sync.receive(&ServerMessage::new(forged, ServerPayload::Time { sent: 10, received: forged })));
+        assert_eq!(sync.best(), Some(TimeSample { offset: i64::MAX - 10, delay: 0 }));
+        assert_eq!(sync.offset(), Some(-5 + (i64::MAX - 5) / 8));
+        assert_eq!(sync.uncertainty(), Some((i64::MAX - 10).abs_diff(-5 + (i64::MAX - 5) / 8)));
+    }
+}
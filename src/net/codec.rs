@@ -0,0 +1,240 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::marker::PhantomData;
+
+use bytes::{Buf, BytesMut};
+use tokio::io::{AsyncRead, AsyncWrite, ReadHalf, WriteHalf};
+use tokio_util::codec::{Decoder, Encoder, FramedRead, FramedWrite};
+
+use crate::net::{decoder::{decode_frame, Frame}, ClientMessage, Error, ErrorKind, Message, ServerMessage, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Codec used by client to encode [`ClientMessage`] and decode [`ServerMessage`].
+pub type ClientCodec = MessageCodec<ClientMessage, ServerMessage>;
+
+/// Codec used by server to encode [`ServerMessage`] and decode [`ClientMessage`].
+pub type ServerCodec = MessageCodec<ServerMessage, ClientMessage>;
+
+/// Reader half yielding decoded messages `R` of an async stream `T`.
+pub type MessageReader<T, S, R> = FramedRead<ReadHalf<T>, MessageCodec<S, R>>;
+
+/// Writer half sending messages `S` to an async stream `T`.
+pub type MessageWriter<T, S, R> = FramedWrite<WriteHalf<T>, MessageCodec<S, R>>;
+
+/// [`tokio_util::codec`] implementation encoding messages `S` and decoding messages `R`.
+/// 
+/// Use [`ClientCodec`] or [`ServerCodec`] according to the side of the connection. 
+/// 
+/// Requires feature `async`.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::ClientCodec;
+/// use tokio::io::{AsyncRead, AsyncWrite};
+/// use tokio_util::codec::Framed;
+/// 
+/// // Stream is usually a tokio::net::TcpStream
+/// fn framed<T : AsyncRead + AsyncWrite>(stream : T) -> Framed<T, ClientCodec> {
+///     Framed::new(stream, ClientCodec::new())
+/// }
+/// ```
+pub struct MessageCodec<S : Message, R : Message> {
+    message : PhantomData<(S, R)>,
+}
+
+impl<S : Message, R : Message> MessageCodec<S, R> {
+    /// Create a new [`MessageCodec`].
+    pub fn new() -> MessageCodec<S, R> {
+        MessageCodec { message: PhantomData }
+    }
+
+    /// Split an async stream into a [`MessageReader`] and a [`MessageWriter`].
+    pub fn split<T : AsyncRead + AsyncWrite>(io : T) -> (MessageReader<T, S, R>, MessageWriter<T, S, R>) {
+        let (reader, writer) = tokio::io::split(io);
+        (FramedRead::new(reader, Self::new()), FramedWrite::new(writer, Self::new()))
+    }
+}
+
+impl<S : Message, R : Message> Default for MessageCodec<S, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S : Message, R : Message> Encoder<S> for MessageCodec<S, R> {
+    type Error = Error;
+
+    /// Pack message at the end of destination.
+    /// 
+    /// Returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if message size exceed [`Message::MAX_SIZE`].
+    fn encode(&mut self, item : S, dst : &mut BytesMut) -> Result<(), Self::Error> {
+        if item.size() > S::MAX_SIZE {
+            return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, S::MAX_SIZE, item.size()));
+        }
+
+        let start = dst.len();
+        dst.resize(start + MESSAGE_SIZE_TYPE_SIZE + item.size(), 0);
+
+        match item.pack_bytes(&mut dst[start..]) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                dst.truncate(start);
+                Err(err)
+            },
+        }
+    }
+}
+
+impl<S : Message, R : Message> Decoder for MessageCodec<S, R> {
+    type Item = R;
+    type Error = Error;
+
+    /// Decode the next message from source.
+    /// 
+    /// Errors are the same as [`MessageDecoder`](crate::net::MessageDecoder) and end the stream.
+    fn decode(&mut self, src : &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match decode_frame::<R>(src) {
+            Some(Frame::Message(result, size)) => {
+                src.advance(size);
+                result.map(Some)
+            },
+            Some(Frame::Corrupted(err)) => {
+                src.clear();
+                Err(err)
+            },
+            None => {
+                // Reserve space for the rest of the message
+                if src.len() >= MESSAGE_SIZE_TYPE_SIZE {
+                    let size = R::size_from_bytes(src[..MESSAGE_SIZE_TYPE_SIZE].try_into().unwrap()) as usize;
+                    src.reserve(MESSAGE_SIZE_TYPE_SIZE + size - src.len());
+                }
+                Ok(None)
+            },
+        }
+    }
+}
+
+
+/// This module test [MessageCodec] over in-memory duplex streams.
+/// 
+/// # Verification(s)
+/// V1 : [ClientCodec] messages are received by [ServerCodec].
+/// V2 : [ServerCodec] messages are received by [ClientCodec].
+/// V3 : [MessageCodec::encode] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when message exceed limit.
+/// V4 : [ServerCodec] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when size header exceed [`CLIENT_MSG_MAX_SIZE`].
+/// V5 : [MessageCodec::split] halves can send and receive concurrently.
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use futures::{SinkExt, StreamExt};
+    use tokio::io::{duplex, AsyncWriteExt};
+    use tokio_util::codec::{Encoder, Framed};
+
+    use crate::net::{ClientCodec, ClientMessage, ClientPayload, ErrorKind, ServerCodec, ServerMessage, ServerPayload, CLIENT_MSG_MAX_SIZE};
+
+    #[tokio::test]
+    async fn v1_client_to_server() {
+        // V1 : [ClientCodec] messages are received by [ServerCodec].
+        let (client, server) = duplex(64);
+        let mut client = Framed::new(client, ClientCodec::new());
+        let mut server = Framed::new(server, ServerCodec::new());
+
+        tokio::spawn(async move {
+            for i in 0..100u16 {
+                client.send(ClientMessage::new(ClientPayload::Test { p16: i, p32: i as u32 })).await.unwrap();
+            }
+        });
+
+        for i in 0..100u16 {
+            assert_eq!(server.next().await, Some(Ok(ClientMessage::new(ClientPayload::Test { p16: i, p32: i as u32 }))));
+        }
+    }
+
+    #[tokio::test]
+    async fn v2_server_to_client() {
+        // V2 : [ServerCodec] messages are received by [ClientCodec].
+        let (client, server) = duplex(64);
+        let mut client = Framed::new(client, ClientCodec::new());
+        let mut server = Framed::new(server, ServerCodec::new());
+
+        tokio::spawn(async move {
+            for i in 0..100u32 {
+                server.send(ServerMessage::new(i as u64, ServerPayload::Error { err: i })).await.unwrap();
+            }
+        });
+
+        for i in 0..100u32 {
+            assert_eq!(client.next().await, Some(Ok(ServerMessage::new(i as u64, ServerPayload::Error { err: i }))));
+        }
+        
+        assert_eq!(client.next().await, None);
+    }
+
+    #[test]
+    fn v3_encode_greater_than_limit() {
+        // V3 : [MessageCodec::encode] returns [`ErrorKind::MessageSizeGreaterThanLimit`] when message exceed limit.
+        let mut message = ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 });
+        message.size = CLIENT_MSG_MAX_SIZE as u16 + 1;
+        let mut dst = BytesMut::new();
+
+        assert_eq!(ClientCodec::new().encode(message, &mut dst), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+        assert!(dst.is_empty());
+    }
+
+    #[tokio::test]
+    async fn v4_decode_greater_than_limit() {
+        // V4 : [ServerCodec] returns [`ErrorKind::MessageSizeGreaterThanLimit`] when size header exceed [`CLIENT_MSG_MAX_SIZE`].
+        let (mut client, server) = duplex(64);
+        let mut server = Framed::new(server, ServerCodec::new());
+
+        client.write_all(&(CLIENT_MSG_MAX_SIZE as u16 + 1).to_le_bytes()).await.unwrap();
+
+        assert_eq!(server.next().await, Some(Err(ErrorKind::MessageSizeGreaterThanLimit.into())));
+    }
+
+    #[tokio::test]
+    async fn v5_split() {
+        // V5 : [MessageCodec::split] halves can send and receive concurrently.
+        let (client, server) = duplex(64);
+        let (mut client_reader, mut client_writer) = ClientCodec::split(client);
+        let (mut server_reader, mut server_writer) = ServerCodec::split(server);
+
+        let echo = tokio::spawn(async move {
+            while let Some(Ok(ClientMessage { payload: ClientPayload::Test { p16, p32 }, .. })) = server_reader.next().await {
+                server_writer.send(ServerMessage::new(p16 as u64, ServerPayload::Test { p16, p32 })).await.unwrap();
+            }
+        });
+
+        for i in 0..100u16 {
+            client_writer.send(ClientMessage::new(ClientPayload::Test { p16: i, p32: 7 })).await.unwrap();
+            assert_eq!(client_reader.next().await, Some(Ok(ServerMessage::new(i as u64, ServerPayload::Test { p16: i, p32: 7 }))));
+        }
+
+        // Both halves must be dropped to close the stream
+        drop(client_writer);
+        drop(client_reader);
+        echo.await.unwrap();
+    }
+}
@@ -0,0 +1,971 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+
+use std::fmt::Display;
+
+use crate::net::{ClientMessage, ClientPayload, Error, ErrorKind, FieldSchema, MessageSchema, NestedSchema, PayloadSchema, ServerMessage, ServerPayload, VariantSchema, PROTOCOL_VERSION};
+
+/// Field of a [`Snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSnapshot {
+    /// Name of the field.
+    pub name : String,
+
+    /// Rust type of the field, which gives its wire format.
+    pub ty : String,
+
+    /// Maximum length, if any.
+    pub max : Option<usize>,
+}
+
+/// Payload of a [`Snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantSnapshot {
+    /// Name of the payload.
+    pub name : String,
+
+    /// Discriminant of the payload.
+    pub discriminant : u16,
+
+    /// Fields in packing order.
+    pub fields : Vec<FieldSnapshot>,
+}
+
+/// Nested type of a [`Snapshot`], generated by [`write_payload_struct!`](crate::write_payload_struct) or 
+/// [`write_payload_enum!`](crate::write_payload_enum).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedSnapshot {
+    /// Name of the type.
+    pub name : String,
+
+    /// Fields of a struct in packing order, empty for an enumeration.
+    pub fields : Vec<FieldSnapshot>,
+
+    /// Variants of an enumeration, empty for a struct.
+    pub variants : Vec<VariantSnapshot>,
+}
+
+/// Message of a [`Snapshot`] with its payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageSnapshot {
+    /// Name of the message.
+    pub name : String,
+
+    /// Name of the payload enumeration.
+    pub payload : String,
+
+    /// Maximum size in bytes of the message.
+    pub max_size : usize,
+
+    /// Extra fields packed after the payload.
+    pub extras : Vec<FieldSnapshot>,
+
+    /// Payloads in declaration order.
+    pub variants : Vec<VariantSnapshot>,
+
+    /// Nested types of fields, in order of first use.
+    pub nested : Vec<NestedSnapshot>,
+}
+
+/// Wire layout of a protocol version, as exported by [`schema_json`](crate::net::schema_json).
+/// 
+/// Doc comments are ignored since they don't change the wire format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Protocol version.
+    pub version : u16,
+
+    /// Messages with their payloads.
+    pub messages : Vec<MessageSnapshot>,
+}
+
+impl From<&FieldSchema> for FieldSnapshot {
+    fn from(field : &FieldSchema) -> Self {
+        FieldSnapshot { name: field.name.to_string(), ty: field.ty.to_string(), max: field.max }
+    }
+}
+
+impl From<&VariantSchema> for VariantSnapshot {
+    fn from(variant : &VariantSchema) -> Self {
+        VariantSnapshot { 
+            name: variant.name.to_string(), 
+            discriminant: variant.discriminant, 
+            fields: variant.fields.iter().map(FieldSnapshot::from).collect() 
+        }
+    }
+}
+
+impl From<&NestedSchema> for NestedSnapshot {
+    fn from(nested : &NestedSchema) -> Self {
+        NestedSnapshot { 
+            name: nested.name.to_string(), 
+            fields: nested.fields.iter().map(FieldSnapshot::from).collect(), 
+            variants: nested.variants.iter().map(VariantSnapshot::from).collect() 
+        }
+    }
+}
+
+impl MessageSnapshot {
+    /// Snapshot of a message layout and its payload layout.
+    pub fn new(message : &MessageSchema, payload : &PayloadSchema) -> MessageSnapshot {
+        MessageSnapshot { 
+            name: message.name.to_string(), 
+            payload: payload.name.to_string(), 
+            max_size: message.max_size, 
+            extras: message.extras.iter().map(FieldSnapshot::from).collect(),
+            variants: payload.variants.iter().map(VariantSnapshot::from).collect(),
+            nested: payload.nested().into_iter().map(NestedSnapshot::from).collect(),
+        }
+    }
+}
+
+impl Snapshot {
+    /// Snapshot of [`ClientMessage`] and [`ServerMessage`] of this build.
+    pub fn current() -> Snapshot {
+        Snapshot { version: PROTOCOL_VERSION, messages: vec![
+            MessageSnapshot::new(&ClientMessage::LAYOUT, &ClientPayload::LAYOUT),
+            MessageSnapshot::new(&ServerMessage::LAYOUT, &ServerPayload::LAYOUT),
+        ]}
+    }
+
+    /// Read a snapshot exported by [`schema_json`](crate::net::schema_json).
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`Snapshot`] read.
+    /// - [`Err`]: [`ErrorKind::InvalidMessage`] with the offset of the error if JSON is malformed or a member is missing.
+    pub fn parse(json : &str) -> Result<Snapshot, Error> {
+        let mut parser = JsonParser { bytes: json.as_bytes(), offset: 0 };
+        let root = parser.value()?;
+        parser.skip_whitespaces();
+        if parser.offset != json.len() {
+            return Err(parser.error());
+        }
+
+        let messages = root.get("messages")?.as_array()?.iter().map(|message| {
+            let payload = message.get("payload")?;
+            Ok(MessageSnapshot {
+                name: message.get("name")?.as_str()?.to_string(),
+                payload: payload.get("name")?.as_str()?.to_string(),
+                max_size: message.get("max_size")?.as_usize()?,
+                extras: fields(message.get("extras")?)?,
+                variants: variants(payload.get("variants")?)?,
+                nested: payload.get("nested")?.as_array()?.iter().map(|nested| Ok(NestedSnapshot {
+                    name: nested.get("name")?.as_str()?.to_string(),
+                    fields: fields(nested.get("fields")?)?,
+                    variants: variants(nested.get("variants")?)?,
+                })).collect::<Result<_, Error>>()?,
+            })
+        }).collect::<Result<_, Error>>()?;
+
+        Ok(Snapshot { version: u16::try_from(root.get("version")?.as_usize()?).map_err(|_| root.error())?, messages })
+    }
+
+    /// Message by name.
+    pub fn message(&self, name : &str) -> Option<&MessageSnapshot> {
+        self.messages.iter().find(|message| message.name == name)
+    }
+}
+
+/// Read payloads or variants of a nested enumeration of a snapshot.
+fn variants(json : &Json) -> Result<Vec<VariantSnapshot>, Error> {
+    json.as_array()?.iter().map(|variant| Ok(VariantSnapshot {
+        name: variant.get("name")?.as_str()?.to_string(),
+        discriminant: u16::try_from(variant.get("discriminant")?.as_usize()?).map_err(|_| variant.error())?,
+        fields: fields(variant.get("fields")?)?,
+    })).collect()
+}
+
+/// Read fields of a snapshot.
+fn fields(json : &Json) -> Result<Vec<FieldSnapshot>, Error> {
+    json.as_array()?.iter().map(|field| Ok(FieldSnapshot {
+        name: field.get("name")?.as_str()?.to_string(),
+        ty: field.get("type")?.as_str()?.to_string(),
+        max: match field.get("max")? {
+            Json { value: JsonValue::Null, .. } => None,
+            max => Some(max.as_usize()?),
+        },
+    })).collect()
+}
+
+/// Kind of a [`Change`] between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Message exists only in older snapshot.
+    RemovedMessage,
+
+    /// Message exists only in newer snapshot.
+    AddedMessage,
+
+    /// Maximum size of message was reduced. Newer peers refuse larger messages.
+    ReducedMaxSize {
+        /// Older maximum size in bytes.
+        old : usize,
+
+        /// Newer maximum size in bytes.
+        new : usize
+    },
+
+    /// Maximum size of message was raised. Older peers refuse larger messages.
+    RaisedMaxSize {
+        /// Older maximum size in bytes.
+        old : usize,
+
+        /// Newer maximum size in bytes.
+        new : usize
+    },
+
+    /// Payload, or variant of a nested enumeration, no longer exists.
+    RemovedPayload {
+        /// Discriminant of payload.
+        discriminant : u16
+    },
+
+    /// Payload was added with an unused discriminant. Older peers read it as `Invalid`.
+    AddedPayload {
+        /// Discriminant of payload.
+        discriminant : u16
+    },
+
+    /// Variant was added to a nested enumeration. Older peers refuse values carrying it.
+    AddedVariant {
+        /// Discriminant of variant.
+        discriminant : u16
+    },
+
+    /// Discriminant of payload, or of variant of a nested enumeration, changed.
+    MovedPayload {
+        /// Older discriminant.
+        old : u16,
+
+        /// Newer discriminant.
+        new : u16
+    },
+
+    /// Discriminant now identifies another payload, or another variant of a nested enumeration.
+    ReusedDiscriminant {
+        /// Discriminant of payload.
+        discriminant : u16,
+
+        /// Older name.
+        old : String,
+
+        /// Newer name.
+        new : String
+    },
+
+    /// Payload, or variant of a nested enumeration, was renamed without changing its fields.
+    RenamedPayload {
+        /// Discriminant of payload.
+        discriminant : u16,
+
+        /// Older name.
+        old : String
+    },
+
+    /// Field exists only in older snapshot.
+    RemovedField {
+        /// Position of field.
+        index : usize
+    },
+
+    /// Field exists only in newer snapshot.
+    AddedField {
+        /// Position of field.
+        index : usize
+    },
+
+    /// Position of field changed.
+    MovedField {
+        /// Older position.
+        old : usize,
+
+        /// Newer position.
+        new : usize
+    },
+
+    /// Field was renamed without changing its type.
+    RenamedField {
+        /// Older name.
+        old : String
+    },
+
+    /// Type of field changed.
+    ChangedType {
+        /// Older type.
+        old : String,
+
+        /// Newer type.
+        new : String
+    },
+
+    /// Maximum length of field was reduced, or added. [`None`] is unlimited. Newer peers refuse longer fields.
+    ReducedLimit {
+        /// Older maximum length.
+        old : Option<usize>,
+
+        /// Newer maximum length.
+        new : Option<usize>
+    },
+
+    /// Maximum length of field was raised, or removed. [`None`] is unlimited. Older peers refuse longer fields.
+    RaisedLimit {
+        /// Older maximum length.
+        old : Option<usize>,
+
+        /// Newer maximum length.
+        new : Option<usize>
+    },
+}
+
+impl ChangeKind {
+    /// Returns true if peers of the older snapshot can't read messages of the newer one, or the opposite.
+    pub fn is_breaking(&self) -> bool {
+        !matches!(self, ChangeKind::AddedMessage | ChangeKind::AddedPayload { .. } | ChangeKind::RenamedPayload { .. } | 
+            ChangeKind::RenamedField { .. })
+    }
+}
+
+/// Limit for display, unlimited if [`None`].
+struct Limit(Option<usize>);
+
+impl Display for Limit {
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(limit) => write!(f, "{}", limit),
+            None => write!(f, "unlimited"),
+        }
+    }
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::RemovedMessage => write!(f, "message removed"),
+            ChangeKind::AddedMessage => write!(f, "message added"),
+            ChangeKind::ReducedMaxSize { old, new } => write!(f, "maximum size reduced from {} to {} bytes", old, new),
+            ChangeKind::RaisedMaxSize { old, new } => write!(f, "maximum size raised from {} to {} bytes", old, new),
+            ChangeKind::RemovedPayload { discriminant } => write!(f, "payload removed, discriminant {} was used", discriminant),
+            ChangeKind::AddedPayload { discriminant } => write!(f, "payload added with discriminant {}", discriminant),
+            ChangeKind::AddedVariant { discriminant } => write!(f, "variant added with discriminant {}", discriminant),
+            ChangeKind::MovedPayload { old, new } => write!(f, "discriminant changed from {} to {}", old, new),
+            ChangeKind::ReusedDiscriminant { discriminant, old, new } => write!(f, "discriminant {} reused by {}, was {}", discriminant, new, old),
+            ChangeKind::RenamedPayload { discriminant, old } => write!(f, "payload with discriminant {} renamed from {}", discriminant, old),
+            ChangeKind::RemovedField { index } => write!(f, "field {} removed", index),
+            ChangeKind::AddedField { index } => write!(f, "field {} added", index),
+            ChangeKind::MovedField { old, new } => write!(f, "field moved from {} to {}", old, new),
+            ChangeKind::RenamedField { old } => write!(f, "field renamed from {}", old),
+            ChangeKind::ChangedType { old, new } => write!(f, "type changed from {} to {}", old, new),
+            ChangeKind::ReducedLimit { old, new } => write!(f, "maximum length reduced from {} to {}", Limit(*old), Limit(*new)),
+            ChangeKind::RaisedLimit { old, new } => write!(f, "maximum length raised from {} to {}", Limit(*old), Limit(*new)),
+        }
+    }
+}
+
+/// Change found between two snapshots by [`check_compatibility`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Path of what changed, like `ClientMessage::Login.account`, using names of the newer snapshot when it exists.
+    pub path : String,
+
+    /// Kind of change.
+    pub kind : ChangeKind,
+}
+
+impl Change {
+    /// Returns true if change breaks wire compatibility, see [`ChangeKind::is_breaking`].
+    pub fn is_breaking(&self) -> bool {
+        self.kind.is_breaking()
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", if self.is_breaking() { "breaking" } else { "compatible" }, self.path, self.kind)
+    }
+}
+
+/// Compare the wire layout of two snapshots.
+/// 
+/// Payloads are matched by discriminant, and fields by position since they are packed in order without names.
+/// Nested types are matched by name and compared once, like payloads for variants of enumerations.
+/// 
+/// # Returns
+/// Every change found, breaking or not, in order of older snapshot.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ check_compatibility, ChangeKind, Snapshot };
+/// 
+/// let old = Snapshot::current();
+/// let mut new = Snapshot::current();
+/// new.messages[0].variants.retain(|variant| variant.name != "Login");
+/// 
+/// let changes = check_compatibility(&old, &new);
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].path, "ClientMessage::Login");
+/// assert!(matches!(changes[0].kind, ChangeKind::RemovedPayload { .. }));
+/// assert!(changes[0].is_breaking());
+/// ```
+pub fn check_compatibility(old : &Snapshot, new : &Snapshot) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for old_message in &old.messages {
+        match new.message(&old_message.name) {
+            Some(new_message) => check_message(old_message, new_message, &mut changes),
+            None => changes.push(Change { path: old_message.name.clone(), kind: ChangeKind::RemovedMessage }),
+        }
+    }
+
+    let mut compared : Vec<&str> = Vec::new();
+    for old_nested in old.messages.iter().flat_map(|message| &message.nested) {
+        let new_nested = new.messages.iter().flat_map(|message| &message.nested).find(|nested| nested.name == old_nested.name);
+        if let Some(new_nested) = new_nested.filter(|_| !compared.contains(&old_nested.name.as_str())) {
+            compared.push(&old_nested.name);
+            check_fields(&new_nested.name, &old_nested.fields, &new_nested.fields, &mut changes);
+            check_variants(&new_nested.name, &old_nested.variants, &new_nested.variants, true, &mut changes);
+        }
+    }
+
+    for new_message in new.messages.iter().filter(|message| old.message(&message.name).is_none()) {
+        changes.push(Change { path: new_message.name.clone(), kind: ChangeKind::AddedMessage });
+    }
+
+    changes
+}
+
+/// Compare a message and its payloads.
+fn check_message(old : &MessageSnapshot, new : &MessageSnapshot, changes : &mut Vec<Change>) {
+    if new.max_size < old.max_size {
+        changes.push(Change { path: new.name.clone(), kind: ChangeKind::ReducedMaxSize { old: old.max_size, new: new.max_size } });
+    } else if new.max_size > old.max_size {
+        changes.push(Change { path: new.name.clone(), kind: ChangeKind::RaisedMaxSize { old: old.max_size, new: new.max_size } });
+    }
+    check_fields(&new.name, &old.extras, &new.extras, changes);
+    check_variants(&new.name, &old.variants, &new.variants, false, changes);
+}
+
+/// Compare payloads, or variants of a nested enumeration if `nested` is true, by discriminant.
+fn check_variants(parent : &str, old : &[VariantSnapshot], new : &[VariantSnapshot], nested : bool, changes : &mut Vec<Change>) {
+    for old_variant in old {
+        let same_discriminant = new.iter().find(|variant| variant.discriminant == old_variant.discriminant);
+        let same_name = new.iter().find(|variant| variant.name == old_variant.name);
+        let path = |name : &str| format!("{}::{}", parent, name);
+
+        if let Some(moved) = same_name.filter(|variant| variant.discriminant != old_variant.discriminant) {
+            changes.push(Change { path: path(&moved.name), kind: ChangeKind::MovedPayload { old: old_variant.discriminant, new: moved.discriminant } });
+        }
+
+        match same_discriminant {
+            Some(new_variant) if new_variant.name == old_variant.name => 
+                check_fields(&path(&new_variant.name), &old_variant.fields, &new_variant.fields, changes),
+            Some(new_variant) if same_name.is_none() && same_wire(&old_variant.fields, &new_variant.fields) => 
+                changes.push(Change { path: path(&new_variant.name), kind: ChangeKind::RenamedPayload { discriminant: old_variant.discriminant, old: old_variant.name.clone() } }),
+            Some(new_variant) => changes.push(Change { path: path(&new_variant.name), kind: ChangeKind::ReusedDiscriminant { 
+                discriminant: old_variant.discriminant, old: old_variant.name.clone(), new: new_variant.name.clone() 
+            } }),
+            None if same_name.is_none() => 
+                changes.push(Change { path: path(&old_variant.name), kind: ChangeKind::RemovedPayload { discriminant: old_variant.discriminant } }),
+            None => {},
+        }
+    }
+
+    for new_variant in new {
+        if !old.iter().any(|variant| variant.discriminant == new_variant.discriminant || variant.name == new_variant.name) {
+            let discriminant = new_variant.discriminant;
+            changes.push(Change { path: format!("{}::{}", parent, new_variant.name), kind: match nested {
+                true => ChangeKind::AddedVariant { discriminant },
+                false => ChangeKind::AddedPayload { discriminant },
+            } });
+        }
+    }
+}
+
+/// Returns true if fields are packed the same way.
+fn same_wire(old : &[FieldSnapshot], new : &[FieldSnapshot]) -> bool {
+    old.len() == new.len() && old.iter().zip(new).all(|(old, new)| old.ty == new.ty && old.max == new.max)
+}
+
+/// Compare fields by position.
+fn check_fields(path : &str, old : &[FieldSnapshot], new : &[FieldSnapshot], changes : &mut Vec<Change>) {
+    for index in 0..old.len().max(new.len()) {
+        match (old.get(index), new.get(index)) {
+            (Some(old_field), Some(new_field)) => {
+                let path = format!("{}.{}", path, new_field.name);
+                let moved = new.iter().position(|field| field.name == old_field.name).filter(|moved| *moved != index);
+
+                if old_field.ty != new_field.ty {
+                    changes.push(Change { path: path.clone(), kind: match moved {
+                        Some(moved) => ChangeKind::MovedField { old: index, new: moved },
+                        None => ChangeKind::ChangedType { old: old_field.ty.clone(), new: new_field.ty.clone() },
+                    } });
+                    continue;
+                }
+
+                if old_field.name != new_field.name {
+                    changes.push(Change { path: path.clone(), kind: match moved {
+                        Some(moved) => ChangeKind::MovedField { old: index, new: moved },
+                        None => ChangeKind::RenamedField { old: old_field.name.clone() },
+                    } });
+                }
+
+                // Unlimited is the largest limit
+                match (old_field.max.unwrap_or(usize::MAX), new_field.max.unwrap_or(usize::MAX)) {
+                    (old_max, new_max) if new_max < old_max => 
+                        changes.push(Change { path, kind: ChangeKind::ReducedLimit { old: old_field.max, new: new_field.max } }),
+                    (old_max, new_max) if new_max > old_max => 
+                        changes.push(Change { path, kind: ChangeKind::RaisedLimit { old: old_field.max, new: new_field.max } }),
+                    _ => {},
+                }
+            },
+            (Some(old_field), None) => changes.push(Change { path: format!("{}.{}", path, old_field.name), kind: ChangeKind::RemovedField { index } }),
+            (None, Some(new_field)) => changes.push(Change { path: format!("{}.{}", path, new_field.name), kind: ChangeKind::AddedField { index } }),
+            (None, None) => {},
+        }
+    }
+}
+
+/// Value of a JSON document.
+#[derive(Debug)]
+enum JsonValue {
+    Null,
+    Bool,
+    Number(u64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// JSON value with its offset in document, for errors.
+#[derive(Debug)]
+struct Json {
+    value : JsonValue,
+    offset : usize,
+}
+
+impl Json {
+    fn error(&self) -> Error {
+        Error::at(ErrorKind::InvalidMessage, self.offset)
+    }
+
+    fn get(&self, key : &str) -> Result<&Json, Error> {
+        match &self.value {
+            JsonValue::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value).ok_or(self.error()),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn as_array(&self) -> Result<&[Json], Error> {
+        match &self.value {
+            JsonValue::Array(values) => Ok(values),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn as_str(&self) -> Result<&str, Error> {
+        match &self.value {
+            JsonValue::String(string) => Ok(string),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn as_usize(&self) -> Result<usize, Error> {
+        match self.value {
+            JsonValue::Number(number) => usize::try_from(number).map_err(|_| self.error()),
+            _ => Err(self.error()),
+        }
+    }
+}
+
+/// Minimal JSON parser of exported schemas. Numbers must be unsigned integers.
+struct JsonParser<'a> {
+    bytes : &'a [u8],
+    offset : usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self) -> Error {
+        Error::at(ErrorKind::InvalidMessage, self.offset)
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self.bytes.get(self.offset).is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.offset += 1;
+        }
+    }
+
+    fn expect(&mut self, byte : u8) -> Result<(), Error> {
+        self.skip_whitespaces();
+        match self.bytes.get(self.offset) {
+            Some(found) if *found == byte => {
+                self.offset += 1;
+                Ok(())
+            },
+            _ => Err(self.error()),
+        }
+    }
+
+    fn keyword(&mut self, keyword : &str, value : JsonValue) -> Result<JsonValue, Error> {
+        match self.bytes[self.offset..].starts_with(keyword.as_bytes()) {
+            true => {
+                self.offset += keyword.len();
+                Ok(value)
+            },
+            false => Err(self.error()),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, Error> {
+        self.skip_whitespaces();
+        let offset = self.offset;
+        let value = match self.bytes.get(self.offset).ok_or(self.error())? {
+            b'n' => self.keyword("null", JsonValue::Null)?,
+            b't' => self.keyword("true", JsonValue::Bool)?,
+            b'f' => self.keyword("false", JsonValue::Bool)?,
+            b'"' => JsonValue::String(self.string()?),
+            b'0'..=b'9' => JsonValue::Number(self.number()?),
+            b'[' => {
+                self.offset += 1;
+                let mut values = Vec::new();
+                if !self.close(b']') {
+                    loop {
+                        values.push(self.value()?);
+                        if self.close(b']') {
+                            break;
+                        }
+                        self.expect(b',')?;
+                    }
+                }
+                JsonValue::Array(values)
+            },
+            b'{' => {
+                self.offset += 1;
+                let mut members = Vec::new();
+                if !self.close(b'}') {
+                    loop {
+                        self.skip_whitespaces();
+                        let key = self.string()?;
+                        self.expect(b':')?;
+                        members.push((key, self.value()?));
+                        if self.close(b'}') {
+                            break;
+                        }
+                        self.expect(b',')?;
+                    }
+                }
+                JsonValue::Object(members)
+            },
+            _ => return Err(self.error()),
+        };
+
+        Ok(Json { value, offset })
+    }
+
+    /// Consume closing bracket if next.
+    fn close(&mut self, bracket : u8) -> bool {
+        self.skip_whitespaces();
+        let closed = self.bytes.get(self.offset) == Some(&bracket);
+        self.offset += closed as usize;
+        closed
+    }
+
+    fn number(&mut self) -> Result<u64, Error> {
+        let start = self.offset;
+        while self.bytes.get(self.offset).is_some_and(u8::is_ascii_digit) {
+            self.offset += 1;
+        }
+
+        std::str::from_utf8(&self.bytes[start..self.offset]).ok().and_then(|digits| digits.parse().ok()).ok_or(Error::at(ErrorKind::InvalidMessage, start))
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        if self.bytes.get(self.offset) != Some(&b'"') {
+            return Err(self.error());
+        }
+        self.offset += 1;
+
+        let mut string = Vec::new();
+        loop {
+            let byte = *self.bytes.get(self.offset).ok_or(self.error())?;
+            self.offset += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = *self.bytes.get(self.offset).ok_or(self.error())?;
+                    self.offset += 1;
+                    match escaped {
+                        b'"' | b'\\' | b'/' => string.push(escaped),
+                        b'n' => string.push(b'\n'),
+                        b'r' => string.push(b'\r'),
+                        b't' => string.push(b'\t'),
+                        b'b' => string.push(0x08),
+                        b'f' => string.push(0x0C),
+                        b'u' => {
+                            let code = self.bytes.get(self.offset..self.offset + 4).and_then(|hex| std::str::from_utf8(hex).ok())
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok()).and_then(char::from_u32).ok_or(self.error())?;
+                            self.offset += 4;
+                            string.extend_from_slice(code.encode_utf8(&mut [0; 4]).as_bytes());
+                        },
+                        _ => return Err(self.error()),
+                    }
+                },
+                byte => string.push(byte),
+            }
+        }
+
+        String::from_utf8(string).map_err(|_| self.error())
+    }
+}
+
+
+/// This module test [Snapshot] and [check_compatibility].
+/// 
+/// # Verification(s)
+/// V1 : [Snapshot::parse] of [schema_json](crate::net::schema_json) gives back [Snapshot::current].
+/// V2 : [Snapshot::parse] returns [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) with offset for malformed or incomplete JSON.
+/// V3 : [check_compatibility] of identical snapshots finds no change.
+/// V4 : [check_compatibility] reports removed, moved and reused payloads as breaking.
+/// V5 : [check_compatibility] reports changed types, moved, added and removed fields as breaking, renamed fields as compatible.
+/// V6 : [check_compatibility] reports reduced and raised limits as breaking, added and renamed payloads as compatible.
+/// V7 : Current payloads match the golden snapshot, without breaking change.
+/// V8 : [check_compatibility] compares nested types once by name, reporting added variants as breaking.
+#[cfg(test)]
+mod tests {
+    use crate::net::{check_compatibility, message_json, schema_json, ChangeKind, ErrorContext, ErrorKind, FieldSnapshot, MessageSnapshot, Snapshot, VariantSnapshot};
+
+    use order::{OrderMessage, OrderPayload};
+
+    /// Message with nested types used for tests, only its layout is read.
+    #[allow(dead_code)]
+    mod order {
+        use tampon::Tampon;
+
+        crate::write_payload_struct!{
+            /// Position used for tests.
+            pub Vec3 { x : f32, y : f32, z : f32 }
+        }
+
+        crate::write_payload_enum!{
+            /// Order of unit used for tests.
+            pub Order,
+            Stop = 0,
+            Move { target : Vec3, path : Vec<Vec3> [max 8] } = 1
+        }
+
+        crate::write_messages_payloads!{
+            /// Payloads with nested fields used for tests.
+            OrderPayload,
+            Command { unit : u32, order : Order } = 1,
+            Invalid = 65535
+        }
+
+        crate::write_messages_struct!{ 256,
+            /// Message with nested fields used for tests.
+            OrderMessage < OrderPayload >
+        }
+    }
+
+    /// Golden snapshot of the protocol, regenerated with `cargo run --bin ethos-schema -- export snapshots/schema.json`.
+    const GOLDEN : &str = include_str!("../../snapshots/schema.json");
+
+    /// Payload of client message by name.
+    fn variant<'a>(snapshot : &'a mut Snapshot, name : &str) -> &'a mut VariantSnapshot {
+        snapshot.messages[0].variants.iter_mut().find(|variant| variant.name == name).unwrap()
+    }
+
+    /// Kinds of changes with their path.
+    fn changes(old : &Snapshot, new : &Snapshot) -> Vec<(String, ChangeKind, bool)> {
+        check_compatibility(old, new).into_iter().map(|change| (change.path.clone(), change.kind.clone(), change.is_breaking())).collect()
+    }
+
+    #[test]
+    fn v1_parse() {
+        // V1 : [Snapshot::parse] of [schema_json](crate::net::schema_json) gives back [Snapshot::current].
+        let snapshot = Snapshot::parse(&schema_json()).unwrap();
+        assert_eq!(snapshot, Snapshot::current());
+        assert_eq!(snapshot.message("ServerMessage").unwrap().extras[0].name, "timestamp");
+        assert!(snapshot.message("Unknown").is_none());
+
+        let escaped = Snapshot::parse(r#"{ "version": 2, "messages": [ { "name": "M\"\u00e9\n", "max_size": 8, "extras": [], 
+            "payload": { "name": "P", "variants": [ { "name": "V", "discriminant": 1, "fields": [ { "name": "f", "type": "u8", "max": null } ] } ], "nested": [] } } ] }"#).unwrap();
+        assert_eq!(escaped.version, 2);
+        assert_eq!(escaped.messages[0].name, "M\"é\n");
+        assert_eq!(escaped.messages[0].variants[0].fields, vec![FieldSnapshot { name: "f".into(), ty: "u8".into(), max: None }]);
+    }
+
+    #[test]
+    fn v2_parse_invalid() {
+        // V2 : [Snapshot::parse] returns [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) with offset for malformed or incomplete JSON.
+        for (json, offset) in [("", 0), ("{ \"version\": 1,", 15), ("[1, 2] x", 7), ("{ \"version\": -1 }", 13), ("{ \"version\": \"\\q\" }", 16), 
+            ("{ \"version\": 1 }", 0), ("{ \"version\": 70000, \"messages\": [] }", 0)] {
+            let err = Snapshot::parse(json).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidMessage, "{}", json);
+            assert_eq!(err.context(), ErrorContext::Offset(offset), "{}", json);
+        }
+    }
+
+    #[test]
+    fn v3_identical() {
+        // V3 : [check_compatibility] of identical snapshots finds no change.
+        assert!(check_compatibility(&Snapshot::current(), &Snapshot::current()).is_empty());
+    }
+
+    #[test]
+    fn v4_payloads_breaking() {
+        // V4 : [check_compatibility] reports removed, moved and reused payloads as breaking.
+        let old = Snapshot::current();
+
+        let mut new = old.clone();
+        new.messages[0].variants.retain(|variant| variant.name != "Ping");
+        assert_eq!(changes(&old, &new), vec![("ClientMessage::Ping".into(), ChangeKind::RemovedPayload { discriminant: 65524 }, true)]);
+
+        let mut new = old.clone();
+        variant(&mut new, "Ping").discriminant = 1;
+        assert_eq!(changes(&old, &new), vec![("ClientMessage::Ping".into(), ChangeKind::MovedPayload { old: 65524, new: 1 }, true)]);
+
+        // Pong takes discriminant of Ping, which moved
+        let mut new = old.clone();
+        variant(&mut new, "Ping").discriminant = 1;
+        variant(&mut new, "Pong").discriminant = 65524;
+        assert_eq!(changes(&old, &new), vec![
+            ("ClientMessage::Ping".into(), ChangeKind::MovedPayload { old: 65524, new: 1 }, true),
+            ("ClientMessage::Pong".into(), ChangeKind::ReusedDiscriminant { discriminant: 65524, old: "Ping".into(), new: "Pong".into() }, true),
+            ("ClientMessage::Pong".into(), ChangeKind::MovedPayload { old: 65523, new: 65524 }, true),
+        ]);
+
+        // Ping removed and its discriminant reused by a new payload
+        let mut new = old.clone();
+        *variant(&mut new, "Ping") = VariantSnapshot { name: "Chat".into(), discriminant: 65524, fields: vec![FieldSnapshot { name: "text".into(), ty: "String".into(), max: Some(64) }] };
+        assert_eq!(changes(&old, &new), vec![("ClientMessage::Chat".into(), ChangeKind::ReusedDiscriminant { discriminant: 65524, old: "Ping".into(), new: "Chat".into() }, true)]);
+
+        let mut new = old.clone();
+        new.messages.remove(1);
+        assert_eq!(changes(&old, &new), vec![("ServerMessage".into(), ChangeKind::RemovedMessage, true)]);
+    }
+
+    #[test]
+    fn v5_fields() {
+        // V5 : [check_compatibility] reports changed types, moved, added and removed fields as breaking, renamed fields as compatible.
+        let old = Snapshot::current();
+
+        let mut new = old.clone();
+        variant(&mut new, "Test").fields[1].ty = "u64".into();
+        assert_eq!(changes(&old, &new), vec![("ClientMessage::Test.p32".into(), ChangeKind::ChangedType { old: "u32".into(), new: "u64".into() }, true)]);
+
+        let mut new = old.clone();
+        variant(&mut new, "Test").fields.reverse();
+        assert_eq!(changes(&old, &new), vec![
+            ("ClientMessage::Test.p32".into(), ChangeKind::MovedField { old: 0, new: 1 }, true),
+            ("ClientMessage::Test.p16".into(), ChangeKind::MovedField { old: 1, new: 0 }, true),
+        ]);
+
+        let mut new = old.clone();
+        variant(&mut new, "Test").fields.push(FieldSnapshot { name: "p8".into(), ty: "u8".into(), max: None });
+        variant(&mut new, "Hello").fields.pop();
+        assert_eq!(changes(&old, &new), vec![
+            ("ClientMessage::Hello.schema".into(), ChangeKind::RemovedField { index: 1 }, true),
+            ("ClientMessage::Test.p8".into(), ChangeKind::AddedField { index: 2 }, true),
+        ]);
+
+        let mut new = old.clone();
+        variant(&mut new, "Test").fields[0].name = "value".into();
+        new.messages[1].extras[0].ty = "u32".into();
+        assert_eq!(changes(&old, &new), vec![
+            ("ClientMessage::Test.value".into(), ChangeKind::RenamedField { old: "p16".into() }, false),
+            ("ServerMessage.timestamp".into(), ChangeKind::ChangedType { old: "u64".into(), new: "u32".into() }, true),
+        ]);
+    }
+
+    #[test]
+    fn v6_limits_and_additions() {
+        // V6 : [check_compatibility] reports reduced and raised limits as breaking, added and renamed payloads as compatible.
+        let old = Snapshot::current();
+
+        let mut new = old.clone();
+        variant(&mut new, "Login").fields[0].max = Some(16);
+        variant(&mut new, "Sealed").fields[1].max = Some(512);
+        new.messages[1].variants.iter_mut().find(|variant| variant.name == "Fragment").unwrap().fields[3].max = None;
+        new.messages[0].max_size = 512;
+        assert_eq!(changes(&old, &new), vec![
+            ("ClientMessage".into(), ChangeKind::ReducedMaxSize { old: 1024, new: 512 }, true),
+            ("ClientMessage::Sealed.data".into(), ChangeKind::ReducedLimit { old: None, new: Some(512) }, true),
+            ("ClientMessage::Login.account".into(), ChangeKind::ReducedLimit { old: Some(64), new: Some(16) }, true),
+            ("ServerMessage::Fragment.data".into(), ChangeKind::RaisedLimit { old: Some(65_000), new: None }, true),
+        ]);
+
+        let mut new = old.clone();
+        new.messages[0].max_size = 2048;
+        assert_eq!(check_compatibility(&old, &new).iter().map(ToString::to_string).collect::<Vec<_>>(), 
+            vec!["breaking: ClientMessage: maximum size raised from 1024 to 2048 bytes"]);
+
+        let mut new = old.clone();
+        new.messages[0].variants.push(VariantSnapshot { name: "Chat".into(), discriminant: 1, fields: Vec::new() });
+        variant(&mut new, "Ping").name = "KeepAlive".into();
+        let changes = check_compatibility(&old, &new);
+        assert!(changes.iter().all(|change| !change.is_breaking()));
+        assert_eq!(changes.iter().map(ToString::to_string).collect::<Vec<_>>(), vec![
+            "compatible: ClientMessage::KeepAlive: payload with discriminant 65524 renamed from Ping",
+            "compatible: ClientMessage::Chat: payload added with discriminant 1",
+        ]);
+    }
+
+    #[test]
+    fn v7_golden() {
+        // V7 : Current payloads match the golden snapshot, without breaking change.
+        let golden = Snapshot::parse(GOLDEN).unwrap();
+        let breaking : Vec<String> = check_compatibility(&golden, &Snapshot::current()).iter().filter(|change| change.is_breaking()).map(ToString::to_string).collect();
+        assert!(breaking.is_empty(), "breaking changes from golden snapshot:\n{}", breaking.join("\n"));
+
+        assert_eq!(schema_json(), GOLDEN, "golden snapshot is outdated, regenerate it with `cargo run --bin ethos-schema -- export snapshots/schema.json`");
+    }
+
+    #[test]
+    fn v8_nested() {
+        // V8 : [check_compatibility] compares nested types once by name, reporting added variants as breaking.
+        let message = MessageSnapshot::new(&OrderMessage::LAYOUT, &OrderPayload::LAYOUT);
+        assert_eq!(message.nested.iter().map(|nested| nested.name.as_str()).collect::<Vec<_>>(), vec!["Order", "Vec3"]);
+
+        let json = format!("{{ \"version\": 1, \"messages\": [ {} ] }}", message_json(&OrderMessage::LAYOUT, &OrderPayload::LAYOUT, OrderPayload::SCHEMA_HASH));
+        let old = Snapshot { version: 1, messages: vec![message.clone(), MessageSnapshot { name: "OtherMessage".into(), ..message }] };
+        assert_eq!(Snapshot::parse(&json).unwrap().messages[0], old.messages[0]);
+
+        let mut new = old.clone();
+        for message in &mut new.messages {
+            let order = &mut message.nested[0];
+            order.variants[0].name = "Halt".into();
+            order.variants[1].fields[1].max = Some(4);
+            order.variants.push(VariantSnapshot { name: "Attack".into(), discriminant: 2, fields: Vec::new() });
+            message.nested[1].fields[1].ty = "f64".into();
+        }
+        assert_eq!(changes(&old, &new), vec![
+            ("Order::Halt".into(), ChangeKind::RenamedPayload { discriminant: 0, old: "Stop".into() }, false),
+            ("Order::Move.path".into(), ChangeKind::ReducedLimit { old: Some(8), new: Some(4) }, true),
+            ("Order::Attack".into(), ChangeKind::AddedVariant { discriminant: 2 }, true),
+            ("Vec3.y".into(), ChangeKind::ChangedType { old: "f32".into(), new: "f64".into() }, true),
+        ]);
+        assert_eq!(check_compatibility(&old, &new)[2].to_string(), "breaking: Order::Attack: variant added with discriminant 2");
+    }
+}
@@ -0,0 +1,468 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use tampon::Tampon;
+
+use crate::net::{ClientMessage, ClientPayload, Error, ErrorKind, ServerMessage, ServerPayload, SERVER_MSG_BUFFER_SIZE};
+
+/// Default size in bytes of packed payloads below which they are sent uncompressed.
+pub const COMPRESSION_THRESHOLD : usize = 256;
+
+/// Compression level of [`Codec::Zstd`].
+#[cfg(feature = "zstd")]
+const ZSTD_LEVEL : i32 = 3;
+
+/// Compression level of [`Codec::Deflate`].
+#[cfg(feature = "deflate")]
+const DEFLATE_LEVEL : u8 = 6;
+
+/// Compression codec of [`ServerPayload::Compressed`].
+/// 
+/// Each codec requires its cargo feature, see [`Codec::is_available`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Codec {
+    /// LZ4 block format, fastest. Requires feature `lz4`.
+    Lz4 = 1,
+
+    /// Zstandard, best ratio. Requires feature `zstd`.
+    Zstd = 2,
+
+    /// Raw deflate, pure Rust. Requires feature `deflate`.
+    Deflate = 3,
+}
+
+impl Codec {
+    /// Codecs by order of preference of server when negotiating.
+    pub const PREFERENCE : [Codec; 3] = [Codec::Zstd, Codec::Lz4, Codec::Deflate];
+
+    /// Id of codec sent in payloads.
+    pub const fn id(self) -> u8 {
+        self as u8
+    }
+
+    /// Codec of an id, [`None`] if unknown.
+    pub const fn from_id(id : u8) -> Option<Codec> {
+        match id {
+            1 => Some(Codec::Lz4),
+            2 => Some(Codec::Zstd),
+            3 => Some(Codec::Deflate),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the cargo feature of codec is enabled.
+    pub const fn is_available(self) -> bool {
+        match self {
+            Codec::Lz4 => cfg!(feature = "lz4"),
+            Codec::Zstd => cfg!(feature = "zstd"),
+            Codec::Deflate => cfg!(feature = "deflate"),
+        }
+    }
+
+    /// Bitmask of ids of available codecs, as sent in [`ClientPayload::Compression`].
+    pub fn available() -> u8 {
+        Self::PREFERENCE.iter().filter(|codec| codec.is_available()).fold(0, |codecs, codec| codecs | 1 << codec.id())
+    }
+
+    /// Compress bytes, [`None`] if codec isn't available or failed.
+    #[cfg_attr(not(any(feature = "lz4", feature = "zstd", feature = "deflate")), allow(unused_variables))]
+    fn compress(self, bytes : &[u8]) -> Option<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            Codec::Lz4 => Some(lz4_flex::block::compress(bytes)),
+            #[cfg(feature = "zstd")]
+            Codec::Zstd => zstd::bulk::compress(bytes, ZSTD_LEVEL).ok(),
+            #[cfg(feature = "deflate")]
+            Codec::Deflate => Some(miniz_oxide::deflate::compress_to_vec(bytes, DEFLATE_LEVEL)),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Decompress data into exactly `size` bytes, never writing more. 
+    /// 
+    /// [`None`] if codec isn't available, data is corrupted or its size differs.
+    #[cfg_attr(not(any(feature = "lz4", feature = "zstd", feature = "deflate")), allow(unused_variables))]
+    fn decompress(self, data : &[u8], size : usize) -> Option<Vec<u8>> {
+        let bytes = match self {
+            #[cfg(feature = "lz4")]
+            Codec::Lz4 => {
+                let mut bytes = vec![0u8; size];
+                lz4_flex::block::decompress_into(data, &mut bytes).ok().map(|len| {
+                    bytes.truncate(len);
+                    bytes
+                })
+            },
+            #[cfg(feature = "zstd")]
+            Codec::Zstd => zstd::bulk::decompress(data, size).ok(),
+            #[cfg(feature = "deflate")]
+            Codec::Deflate => miniz_oxide::inflate::decompress_to_vec_with_limit(data, size).ok(),
+            #[allow(unreachable_patterns)]
+            _ => None::<Vec<u8>>,
+        };
+
+        bytes.filter(|bytes| bytes.len() == size)
+    }
+}
+
+/// Compress [`ServerPayload`] into [`ServerPayload::Compressed`] with a negotiated [`Codec`], and decompress them.
+/// 
+/// The [`ServerPayload::Compressed`] discriminant flags compressed messages. Payloads smaller than threshold,
+/// or that don't shrink, are sent uncompressed. Decompressed size is checked before decompressing and
+/// decompression never writes more than announced, so a tiny message can't expand beyond maximum size.
+/// 
+/// # Negotiation
+/// After [`Handshake`](crate::net::Handshake), client sends [`Compressor::offer`], server answers with [`Compressor::negotiate`]
+/// and client reads the answer with [`Compressor::conclude`].
+/// 
+/// # Note(s)
+/// Large payloads are compressed before [`Fragmenter::split`](crate::net::Fragmenter::split), and decompressed after 
+/// [`Reassembler::push`](crate::net::Reassembler::push).
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ Compressor, ServerMessage, ServerPayload };
+/// 
+/// // Client and server negotiate the best codec they both support
+/// let (answer, server) = Compressor::negotiate(&Compressor::offer(), 0);
+/// let (server, client) = (server.unwrap(), Compressor::conclude(&answer).unwrap());
+/// 
+/// let payload = ServerPayload::Reply { id: 1, data: vec![7u8; 4096].into_boxed_slice() };
+/// let message = ServerMessage::new(42, server.compress(payload.clone()));
+/// 
+/// assert_eq!(client.decompress(message).unwrap().payload, payload);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Compressor {
+    /// Negotiated codec, [`None`] to send uncompressed.
+    codec : Option<Codec>,
+
+    /// Size of packed payloads below which they are sent uncompressed.
+    threshold : usize,
+
+    /// Maximum size of decompressed payloads.
+    max_size : usize,
+}
+
+impl Compressor {
+    /// Create a new [`Compressor`].
+    /// 
+    /// # Argument(s)
+    /// * `codec` - Negotiated [`Codec`], [`None`] to send uncompressed.
+    /// * `threshold` - Size in bytes of packed payloads below which they are sent uncompressed, usually [`COMPRESSION_THRESHOLD`].
+    /// * `max_size` - Maximum size in bytes of decompressed payloads, usually [`SERVER_MSG_BUFFER_SIZE`].
+    pub fn new(codec : Option<Codec>, threshold : usize, max_size : usize) -> Compressor {
+        Compressor { codec, threshold, max_size }
+    }
+
+    /// Create a new [`Compressor`] with [`COMPRESSION_THRESHOLD`], bounded by [`SERVER_MSG_BUFFER_SIZE`].
+    pub fn with_codec(codec : Option<Codec>) -> Compressor {
+        Self::new(codec, COMPRESSION_THRESHOLD, SERVER_MSG_BUFFER_SIZE)
+    }
+
+    /// Negotiated codec, [`None`] if payloads are sent uncompressed.
+    pub fn codec(&self) -> Option<Codec> {
+        self.codec
+    }
+
+    /// Size in bytes of packed payloads below which they are sent uncompressed.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Maximum size in bytes of decompressed payloads.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Message sent by client with its available codecs.
+    pub fn offer() -> ClientMessage {
+        ClientMessage::new(ClientPayload::Compression { codecs: Codec::available() })
+    }
+
+    /// Server selection of the preferred codec available on both sides.
+    /// 
+    /// # Returns
+    /// Answer to send to client and [`Result`] which is:
+    /// - [`Ok`]: [`Compressor`] of server, without codec if none is shared.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ClientPayload::Compression`]. Answer selects no codec.
+    pub fn negotiate(message : &ClientMessage, timestamp : u64) -> (ServerMessage, Result<Compressor, Error>) {
+        let (codec, result) = match message.payload {
+            ClientPayload::Compression { codecs } => {
+                let shared = codecs & Codec::available();
+                let codec = Codec::PREFERENCE.into_iter().find(|codec| shared & 1 << codec.id() != 0);
+                (codec, Ok(Self::with_codec(codec)))
+            },
+            _ => (None, Err(ErrorKind::InvalidMessage.into())),
+        };
+
+        (ServerMessage::new(timestamp, ServerPayload::Compression { codec: codec.map_or(0, Codec::id) }), result)
+    }
+
+    /// Client check of the codec selected by server.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`Compressor`] of client, without codec if none was selected.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ServerPayload::Compression`] or codec isn't available.
+    pub fn conclude(message : &ServerMessage) -> Result<Compressor, Error> {
+        match message.payload {
+            ServerPayload::Compression { codec: 0 } => Ok(Self::with_codec(None)),
+            ServerPayload::Compression { codec } => match Codec::from_id(codec) {
+                Some(codec) if codec.is_available() => Ok(Self::with_codec(Some(codec))),
+                _ => Err(ErrorKind::InvalidMessage.into()),
+            },
+            _ => Err(ErrorKind::InvalidMessage.into()),
+        }
+    }
+
+    /// Compress a payload if it reaches threshold and shrinks.
+    /// 
+    /// # Returns
+    /// [`ServerPayload::Compressed`], or the payload itself if sent uncompressed.
+    pub fn compress(&self, payload : ServerPayload) -> ServerPayload {
+        let Some(codec) = self.codec else {
+            return payload;
+        };
+
+        let size = payload.bytes_size();
+        if size < self.threshold || size > u32::MAX as usize || matches!(payload, ServerPayload::Compressed { .. }) {
+            return payload;
+        }
+
+        let mut bytes = vec![0u8; size];
+        payload.serialize(&mut bytes);
+
+        match codec.compress(&bytes) {
+            Some(data) if data.len() <= u16::MAX as usize => {
+                let compressed = ServerPayload::Compressed { codec: codec.id(), size: size as u32, data: data.into() };
+                if compressed.bytes_size() < size { compressed } else { payload }
+            },
+            _ => payload,
+        }
+    }
+
+    /// Decompress a message received.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Message decompressed, or the message itself if it wasn't compressed.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if codec isn't the negotiated one or decompressed payload is malformed.
+    ///     2. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if decompressed size exceed maximum size. Nothing is decompressed.
+    ///     3. [`ErrorKind::DecompressionFailed`](crate::net::ErrorKind::DecompressionFailed) if data is corrupted or doesn't match decompressed size.
+    pub fn decompress(&self, message : ServerMessage) -> Result<ServerMessage, Error> {
+        let ServerPayload::Compressed { codec, size, data } = message.payload else {
+            return Ok(message);
+        };
+
+        let codec = self.codec.filter(|negotiated| negotiated.id() == codec).ok_or(ErrorKind::InvalidMessage)?;
+        if size as usize > self.max_size {
+            return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, self.max_size, size as usize));
+        }
+
+        let bytes = codec.decompress(&data, size as usize).ok_or(ErrorKind::DecompressionFailed)?;
+        if ServerPayload::deserialize_size(&bytes, 0) != Ok(bytes.len()) {
+            return Err(ErrorKind::InvalidMessage.into());
+        }
+
+        match ServerPayload::deserialize(&bytes).0 {
+            ServerPayload::Compressed { .. } | ServerPayload::Fragment { .. } | ServerPayload::Invalid => Err(ErrorKind::InvalidMessage.into()),
+            payload => Ok(ServerMessage::new(message.timestamp, payload)),
+        }
+    }
+}
+
+
+/// This module test [Codec] and [Compressor].
+/// 
+/// # Verification(s)
+/// V1 : [Codec::from_id] gives back codec of [Codec::id] and [Codec::available] matches enabled features.
+/// V2 : [Compressor::negotiate] selects the preferred shared codec, or none.
+/// V3 : [Compressor::conclude] refuses unknown codecs and other payloads.
+/// V4 : [Compressor::compress] leaves payloads below threshold, without codec or that don't shrink uncompressed.
+/// V5 : [Compressor::decompress] gives back payload compressed by each available codec.
+/// V6 : [Compressor::decompress] refuses decompressed size above maximum size and never expands beyond announced size.
+/// V7 : [Compressor::decompress] refuses corrupted data, codec not negotiated and nested compressed payload.
+#[cfg(test)]
+mod tests {
+    use tampon::Tampon;
+
+    use crate::net::{ClientMessage, ClientPayload, Codec, Compressor, ErrorKind, ServerMessage, ServerPayload, COMPRESSION_THRESHOLD};
+
+    /// Payload compressing well.
+    fn large(len : usize) -> ServerPayload {
+        ServerPayload::Reply { id: 0, data: std::iter::repeat_n(7u8, len).chain((0..len).map(|i| (i % 16) as u8)).collect() }
+    }
+
+    /// Codecs enabled by features.
+    fn available() -> impl Iterator<Item = Codec> {
+        Codec::PREFERENCE.into_iter().filter(|codec| codec.is_available())
+    }
+
+    /// Compressed payload of a codec.
+    fn compressed(codec : Codec, payload : ServerPayload) -> (Vec<u8>, u32) {
+        match Compressor::with_codec(Some(codec)).compress(payload) {
+            ServerPayload::Compressed { data, size, .. } => (data.into_vec(), size),
+            payload => panic!("Payload should be compressed, got {:?}", payload),
+        }
+    }
+
+    #[test]
+    fn v1_codec_ids() {
+        // V1 : [Codec::from_id] gives back codec of [Codec::id] and [Codec::available] matches enabled features.
+        for codec in Codec::PREFERENCE {
+            assert_eq!(Codec::from_id(codec.id()), Some(codec));
+            assert_eq!(Codec::available() & 1 << codec.id() != 0, codec.is_available());
+        }
+        assert_eq!(Codec::from_id(0), None);
+        assert_eq!(Codec::from_id(4), None);
+        assert_eq!(Codec::Lz4.is_available(), cfg!(feature = "lz4"));
+        assert_eq!(Codec::Zstd.is_available(), cfg!(feature = "zstd"));
+        assert_eq!(Codec::Deflate.is_available(), cfg!(feature = "deflate"));
+    }
+
+    #[test]
+    fn v2_negotiate() {
+        // V2 : [Compressor::negotiate] selects the preferred shared codec, or none.
+        let (answer, server) = Compressor::negotiate(&Compressor::offer(), 7);
+        let preferred = available().next();
+        assert_eq!(answer, ServerMessage::new(7, ServerPayload::Compression { codec: preferred.map_or(0, Codec::id) }));
+        assert_eq!(server, Ok(Compressor::with_codec(preferred)));
+        assert_eq!(Compressor::conclude(&answer), Ok(Compressor::with_codec(preferred)));
+
+        for codec in available() {
+            let (answer, server) = Compressor::negotiate(&ClientMessage::new(ClientPayload::Compression { codecs: 1 << codec.id() }), 0);
+            assert_eq!(server.unwrap().codec(), Some(codec));
+            assert_eq!(Compressor::conclude(&answer).unwrap().codec(), Some(codec));
+        }
+
+        // Nothing shared
+        let (answer, server) = Compressor::negotiate(&ClientMessage::new(ClientPayload::Compression { codecs: 0 }), 0);
+        assert_eq!(server.unwrap().codec(), None);
+        assert_eq!(Compressor::conclude(&answer).unwrap().codec(), None);
+
+        let (answer, server) = Compressor::negotiate(&ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 }), 0);
+        assert_eq!(server, Err(ErrorKind::InvalidMessage.into()));
+        assert_eq!(answer.payload, ServerPayload::Compression { codec: 0 });
+    }
+
+    #[test]
+    fn v3_conclude_invalid() {
+        // V3 : [Compressor::conclude] refuses unknown codecs and other payloads.
+        assert_eq!(Compressor::conclude(&ServerMessage::new(0, ServerPayload::Compression { codec: 200 })), Err(ErrorKind::InvalidMessage.into()));
+        assert_eq!(Compressor::conclude(&ServerMessage::new(0, ServerPayload::Accept)), Err(ErrorKind::InvalidMessage.into()));
+
+        for codec in Codec::PREFERENCE.into_iter().filter(|codec| !codec.is_available()) {
+            assert_eq!(Compressor::conclude(&ServerMessage::new(0, ServerPayload::Compression { codec: codec.id() })), Err(ErrorKind::InvalidMessage.into()));
+        }
+    }
+
+    #[test]
+    fn v4_uncompressed() {
+        // V4 : [Compressor::compress] leaves payloads below threshold, without codec or that don't shrink uncompressed.
+        assert_eq!(Compressor::with_codec(None).compress(large(4096)), large(4096));
+
+        for codec in available() {
+            let compressor = Compressor::with_codec(Some(codec));
+            let small = ServerPayload::Test { p16: 1, p32: 2 };
+            assert_eq!(compressor.compress(small.clone()), small);
+            assert_eq!(compressor.compress(large(COMPRESSION_THRESHOLD / 4)), large(COMPRESSION_THRESHOLD / 4));
+
+            // Pseudo-random bytes don't shrink
+            let mut seed = 0x2545F4914F6CDD1Du64;
+            let noise = ServerPayload::Reply { id: 0, data: (0..4096).map(|_| { 
+                seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; seed as u8 
+            }).collect() };
+            assert_eq!(compressor.compress(noise.clone()), noise);
+        }
+    }
+
+    #[test]
+    fn v5_round_trip() {
+        // V5 : [Compressor::decompress] gives back payload compressed by each available codec.
+        for codec in available() {
+            let compressor = Compressor::with_codec(Some(codec));
+            let payload = large(16_384);
+            let message = ServerMessage::new(42, compressor.compress(payload.clone()));
+
+            assert!(matches!(message.payload, ServerPayload::Compressed { .. }), "{:?} should compress", codec);
+            assert!((message.size as usize) < Tampon::bytes_size(&payload) / 4);
+            assert_eq!(compressor.decompress(message), Ok(ServerMessage::new(42, payload)));
+
+            // Uncompressed message go through
+            let message = ServerMessage::new(1, ServerPayload::Accept);
+            assert_eq!(compressor.decompress(message), Ok(ServerMessage::new(1, ServerPayload::Accept)));
+        }
+    }
+
+    #[test]
+    fn v6_output_cap() {
+        // V6 : [Compressor::decompress] refuses decompressed size above maximum size and never expands beyond announced size.
+        for codec in available() {
+            let payload = large(30_000);
+            let (data, size) = compressed(codec, payload.clone());
+
+            let capped = Compressor::new(Some(codec), COMPRESSION_THRESHOLD, size as usize - 1);
+            let message = ServerMessage::new(0, ServerPayload::Compressed { codec: codec.id(), size, data: data.clone().into() });
+            assert_eq!(capped.decompress(message), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+
+            // Announcing a smaller size doesn't let data expand further
+            let compressor = Compressor::with_codec(Some(codec));
+            let message = ServerMessage::new(0, ServerPayload::Compressed { codec: codec.id(), size: 1024, data: data.into() });
+            assert_eq!(compressor.decompress(message), Err(ErrorKind::DecompressionFailed.into()));
+        }
+    }
+
+    #[test]
+    fn v7_invalid() {
+        // V7 : [Compressor::decompress] refuses corrupted data, codec not negotiated and nested compressed payload.
+        let message = || ServerMessage::new(0, ServerPayload::Compressed { codec: Codec::Lz4.id(), size: 4, data: Box::new([1, 2, 3]) });
+        assert_eq!(Compressor::with_codec(None).decompress(message()), Err(ErrorKind::InvalidMessage.into()));
+
+        for codec in available() {
+            let compressor = Compressor::with_codec(Some(codec));
+            let (mut data, size) = compressed(codec, large(4096));
+            data.truncate(data.len() / 2);
+            let message = ServerMessage::new(0, ServerPayload::Compressed { codec: codec.id(), size, data: data.into() });
+            assert_eq!(compressor.decompress(message), Err(ErrorKind::DecompressionFailed.into()));
+
+            let other = Codec::PREFERENCE.into_iter().find(|other| *other != codec).unwrap();
+            let message = ServerMessage::new(0, ServerPayload::Compressed { codec: other.id(), size: 4, data: Box::new([1, 2, 3]) });
+            assert_eq!(compressor.decompress(message), Err(ErrorKind::InvalidMessage.into()));
+
+            // Compressed payload of a compressed payload, forged since compress refuses it
+            let inner = ServerPayload::Compressed { codec: codec.id(), size: 0, data: vec![0u8; 4096].into_boxed_slice() };
+            let mut bytes = vec![0u8; Tampon::bytes_size(&inner)];
+            Tampon::serialize(&inner, &mut bytes);
+            let data = codec.compress(&bytes).unwrap();
+            let message = ServerMessage::new(0, ServerPayload::Compressed { codec: codec.id(), size: bytes.len() as u32, data: data.into() });
+            assert_eq!(compressor.decompress(message), Err(ErrorKind::InvalidMessage.into()));
+        }
+    }
+}
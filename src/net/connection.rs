@@ -0,0 +1,245 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::{io::{Read, Write}, marker::PhantomData, net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs}};
+
+use crate::net::{ClientMessage, Error, ErrorKind, Message, MessageDecoder, ServerMessage, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Blocking connection used by client to send [`ClientMessage`] and receive [`ServerMessage`].
+pub type ClientConnection = Connection<ClientMessage, ServerMessage>;
+
+/// Blocking connection used by server to send [`ServerMessage`] and receive [`ClientMessage`].
+pub type ServerConnection = Connection<ServerMessage, ClientMessage>;
+
+/// Blocking TCP connection between client and server.
+/// 
+/// Use [`ClientConnection`] or [`ServerConnection`] according to the side of the connection.
+/// 
+/// # Example(s)
+/// ```no_run
+/// use ethos_core::net::{ ClientConnection, ClientMessage, ClientPayload, TCP_PORT };
+/// 
+/// let mut connection = ClientConnection::connect(("127.0.0.1", TCP_PORT)).unwrap();
+/// connection.send(&ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 })).unwrap();
+/// let _message = connection.receive().unwrap();
+/// ```
+pub struct Connection<S : Message, R : Message> {
+    /// Underlying TCP stream.
+    stream : TcpStream,
+
+    /// Decoder of received bytes.
+    decoder : MessageDecoder<R>,
+
+    /// Buffer used to read from stream.
+    read_buffer : Vec<u8>,
+
+    /// Buffer used to pack messages sent.
+    write_buffer : Vec<u8>,
+
+    send : PhantomData<S>,
+}
+
+impl<S : Message, R : Message> Connection<S, R> {
+    /// Open a new [`Connection`] to a remote address.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`Connection`] opened.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::Io`](crate::net::ErrorKind::Io) if the connection couldn't be opened.
+    pub fn connect<A : ToSocketAddrs>(addr : A) -> Result<Connection<S, R>, Error> {
+        Ok(Self::from_stream(TcpStream::connect(addr)?))
+    }
+
+    /// Create a new [`Connection`] from an already connected [`TcpStream`].
+    /// 
+    /// Typically used by server with streams accepted by a [`TcpListener`](std::net::TcpListener).
+    pub fn from_stream(stream : TcpStream) -> Connection<S, R> {
+        Connection { 
+            stream, 
+            decoder: MessageDecoder::new(), 
+            read_buffer: vec![0u8; MESSAGE_SIZE_TYPE_SIZE + R::MAX_SIZE], 
+            write_buffer: vec![0u8; MESSAGE_SIZE_TYPE_SIZE + S::MAX_SIZE], 
+            send: PhantomData 
+        }
+    }
+
+    /// Reference to the underlying [`TcpStream`], to set timeouts or options.
+    pub fn stream(&self) -> &TcpStream {
+        &self.stream
+    }
+
+    /// Address of the remote peer.
+    pub fn peer_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.stream.peer_addr()?)
+    }
+
+    /// Send a message to the remote peer.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Message was written entirely.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if message size exceed [`Message::MAX_SIZE`]. Nothing is sent.
+    ///     2. [`ErrorKind::ConnectionClosed`](crate::net::ErrorKind::ConnectionClosed) or [`ErrorKind::Io`](crate::net::ErrorKind::Io) if writing failed.
+    pub fn send(&mut self, message : &S) -> Result<(), Error> {
+        if message.size() > S::MAX_SIZE {
+            return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, S::MAX_SIZE, message.size()));
+        }
+
+        let size = message.pack_bytes(&mut self.write_buffer)?;
+        self.stream.write_all(&self.write_buffer[..MESSAGE_SIZE_TYPE_SIZE + size])?;
+        Ok(())
+    }
+
+    /// Block until a message is received from the remote peer.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Message received.
+    /// - [`Err`]:
+    ///     1. Any error of [`MessageDecoder`]. Connection should be closed on [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit).
+    ///     2. [`ErrorKind::ConnectionClosed`](crate::net::ErrorKind::ConnectionClosed) if the remote peer closed the connection.
+    ///     3. [`ErrorKind::Io`](crate::net::ErrorKind::Io) if reading failed, including read timeouts.
+    pub fn receive(&mut self) -> Result<R, Error> {
+        loop {
+            if let Some(result) = self.decoder.next() {
+                return result;
+            }
+
+            let len = self.decoder.remaining().min(self.read_buffer.len());
+            match self.stream.read(&mut self.read_buffer[..len])? {
+                0 => return Err(ErrorKind::ConnectionClosed.into()),
+                size => self.decoder.push(&self.read_buffer[..size])?,
+            }
+        }
+    }
+
+    /// Shut down both directions of the connection.
+    pub fn shutdown(&self) -> Result<(), Error> {
+        Ok(self.stream.shutdown(Shutdown::Both)?)
+    }
+}
+
+
+/// This module test [Connection] over loopback.
+/// 
+/// # Verification(s)
+/// V1 : [ClientConnection::send] messages are received by [ServerConnection::receive].
+/// V2 : [ServerConnection::send] messages are received by [ClientConnection::receive].
+/// V3 : [Connection::send] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when message exceed limit.
+/// V4 : [Connection::receive] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when size header exceed limit.
+/// V5 : [Connection::receive] returns [`ErrorKind::ConnectionClosed`](crate::net::ErrorKind::ConnectionClosed) when peer closed connection.
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, net::{TcpListener, TcpStream}, thread};
+
+    use crate::net::{ClientConnection, ClientMessage, ClientPayload, ErrorKind, ServerConnection, ServerMessage, ServerPayload, CLIENT_MSG_MAX_SIZE};
+
+    /// Open a loopback TCP connection on an ephemeral port.
+    fn loopback() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        (client, server)
+    }
+
+    #[test]
+    fn v1_client_to_server() {
+        // V1 : [ClientConnection::send] messages are received by [ServerConnection::receive].
+        let (client, server) = loopback();
+
+        let handle = thread::spawn(move || {
+            let mut client = ClientConnection::from_stream(client);
+            for i in 0..100u16 {
+                client.send(&ClientMessage::new(ClientPayload::Test { p16: i, p32: i as u32 * 2 })).unwrap();
+            }
+        });
+
+        let mut server = ServerConnection::from_stream(server);
+        for i in 0..100u16 {
+            assert_eq!(server.receive(), Ok(ClientMessage::new(ClientPayload::Test { p16: i, p32: i as u32 * 2 })));
+        }
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn v2_server_to_client() {
+        // V2 : [ServerConnection::send] messages are received by [ClientConnection::receive].
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = thread::spawn(move || {
+            let mut server = ServerConnection::from_stream(listener.accept().unwrap().0);
+            for i in 0..100u32 {
+                server.send(&ServerMessage::new(i as u64, ServerPayload::Error { err: i })).unwrap();
+            }
+        });
+
+        let mut client = ClientConnection::connect(addr).unwrap();
+        for i in 0..100u32 {
+            assert_eq!(client.receive(), Ok(ServerMessage::new(i as u64, ServerPayload::Error { err: i })));
+        }
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn v3_send_greater_than_limit() {
+        // V3 : [Connection::send] returns [`ErrorKind::MessageSizeGreaterThanLimit`] when message exceed limit.
+        let (client, _server) = loopback();
+        let mut client = ClientConnection::from_stream(client);
+
+        let mut message = ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 });
+        message.size = CLIENT_MSG_MAX_SIZE as u16 + 1;
+
+        assert_eq!(client.send(&message), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+    }
+
+    #[test]
+    fn v4_receive_greater_than_limit() {
+        // V4 : [Connection::receive] returns [`ErrorKind::MessageSizeGreaterThanLimit`] when size header exceed limit.
+        let (mut client, server) = loopback();
+        let mut server = ServerConnection::from_stream(server);
+
+        client.write_all(&(CLIENT_MSG_MAX_SIZE as u16 + 1).to_le_bytes()).unwrap();
+
+        assert_eq!(server.receive(), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+    }
+
+    #[test]
+    fn v5_receive_connection_closed() {
+        // V5 : [Connection::receive] returns [`ErrorKind::ConnectionClosed`] when peer closed connection.
+        let (client, server) = loopback();
+        let mut client = ClientConnection::from_stream(client);
+        let server = ServerConnection::from_stream(server);
+
+        server.shutdown().unwrap();
+        drop(server);
+
+        assert_eq!(client.receive(), Err(ErrorKind::ConnectionClosed.into()));
+    }
+}
@@ -0,0 +1,387 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::marker::PhantomData;
+
+use crate::net::{Error, ErrorKind, Message, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Incremental decoder extracting [`Message`] from a stream of bytes.
+/// 
+/// Bytes can be pushed in chunks of any size (partial size header, many messages
+/// in one read, a message split across many reads). Complete messages are then 
+/// obtained by iterating the decoder.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ MessageDecoder, ServerMessage, SERVER_MSG_BUFFER_SIZE };
+/// 
+/// let mut decoder = MessageDecoder::<ServerMessage>::with_capacity(SERVER_MSG_BUFFER_SIZE);
+/// 
+/// // Bytes read from socket
+/// decoder.push(&[]).unwrap();
+/// 
+/// for message in decoder.by_ref() {
+///     match message {
+///         Ok(_message) => {},
+///         Err(_err) => {},
+///     }
+/// }
+/// ```
+pub struct MessageDecoder<M : Message> {
+    /// Bytes received but not decoded yet.
+    buffer : Vec<u8>,
+
+    /// Index of the first byte not decoded in buffer.
+    start : usize,
+
+    /// Maximum count of bytes kept in buffer.
+    capacity : usize,
+
+    message : PhantomData<M>,
+}
+
+impl<M : Message> MessageDecoder<M> {
+    /// Create a new [`MessageDecoder`] with a capacity of exactly one message of [`Message::MAX_SIZE`].
+    pub fn new() -> MessageDecoder<M> {
+        Self::with_capacity(0)
+    }
+
+    /// Create a new [`MessageDecoder`] with a given capacity in bytes.
+    /// 
+    /// Capacity is raised to the size of one message of [`Message::MAX_SIZE`] if smaller.
+    /// 
+    /// Use [`SERVER_MSG_BUFFER_SIZE`](crate::net::SERVER_MSG_BUFFER_SIZE) to decode [`ServerMessage`](crate::net::ServerMessage).
+    pub fn with_capacity(capacity : usize) -> MessageDecoder<M> {
+        let capacity = capacity.max(MESSAGE_SIZE_TYPE_SIZE + M::MAX_SIZE);
+        MessageDecoder { buffer: Vec::with_capacity(capacity), start: 0, capacity, message: PhantomData }
+    }
+
+    /// Count of bytes that can still be pushed in the decoder.
+    pub fn remaining(&self) -> usize {
+        self.capacity - self.buffered()
+    }
+
+    /// Count of bytes pushed but not decoded yet.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() - self.start
+    }
+
+    /// Discard all bytes not decoded yet.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.start = 0;
+    }
+
+    /// Push received bytes at the end of the decoder.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Bytes were added to the decoder.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::BufferSizeTooSmall`](crate::net::ErrorKind::BufferSizeTooSmall) if bytes length is greater than [`remaining`](Self::remaining). Nothing is pushed.
+    pub fn push(&mut self, bytes : &[u8]) -> Result<(), Error> {
+        if bytes.len() > self.remaining() {
+            return Err(Error::size(ErrorKind::BufferSizeTooSmall, bytes.len(), self.remaining()));
+        }
+
+        // Drop bytes already decoded before appending
+        if self.start > 0 {
+            self.buffer.drain(..self.start);
+            self.start = 0;
+        }
+
+        self.buffer.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl<M : Message> Default for MessageDecoder<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M : Message> Iterator for MessageDecoder<M> {
+    type Item = Result<M, Error>;
+
+    /// Decode the next complete message.
+    /// 
+    /// # Returns
+    /// - [`None`] if no complete message is available yet.
+    /// - [`Some`] with [`Result`] which is:
+    ///     - [`Ok`]: Next [`Message`] decoded.
+    ///     - [`Err`]:
+    ///         1. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when size header exceed [`Message::MAX_SIZE`]. The stream can't be
+    ///            trusted anymore so every byte buffered is discarded and connection should be closed.
+    ///         2. [`ErrorKind::MessageSizeInvalid`](crate::net::ErrorKind::MessageSizeInvalid) when size header is too small to contain a message.
+    ///         3. Any error of [`Message::from_bytes`]. The message is skipped and decoding can continue.
+    fn next(&mut self) -> Option<Self::Item> {
+        match decode_frame(&self.buffer[self.start..]) {
+            Some(Frame::Message(result, size)) => {
+                self.start += size;
+                if self.start == self.buffer.len() {
+                    self.clear();
+                }
+                Some(result)
+            },
+            Some(Frame::Corrupted(err)) => {
+                self.clear();
+                Some(Err(err))
+            },
+            None => None,
+        }
+    }
+}
+
+/// Frame extracted by [`decode_frame`].
+pub(crate) enum Frame<M : Message> {
+    /// Result of message decoding with the size of the frame consumed, size header included.
+    Message(Result<M, Error>, usize),
+
+    /// Stream can't be trusted anymore and must be discarded.
+    Corrupted(Error),
+}
+
+/// Decode the first frame of bytes, size header included.
+/// 
+/// Returns [`None`] if bytes doesn't contain a complete frame yet.
+pub(crate) fn decode_frame<M : Message>(bytes : &[u8]) -> Option<Frame<M>> {
+    if bytes.len() < MESSAGE_SIZE_TYPE_SIZE {
+        return None;
+    }
+
+    let size = M::size_from_bytes(bytes[..MESSAGE_SIZE_TYPE_SIZE].try_into().unwrap()) as usize;
+
+    if size > M::MAX_SIZE {
+        return Some(Frame::Corrupted(Error::size(ErrorKind::MessageSizeGreaterThanLimit, M::MAX_SIZE, size)));
+    }
+
+    if bytes.len() < MESSAGE_SIZE_TYPE_SIZE + size {
+        return None;
+    }
+
+    let result = match M::from_bytes(&bytes[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]) {
+        // Whole message was given, so an incomplete message means the header lied.
+        Err(err) if err.kind() == ErrorKind::IncompleteMessage => Err(ErrorKind::MessageSizeInvalid.into()),
+        result => result,
+    };
+
+    Some(Frame::Message(result, MESSAGE_SIZE_TYPE_SIZE + size))
+}
+
+
+/// This module test the [MessageDecoder].
+/// 
+/// # Verification(s)
+/// V1 : [MessageDecoder] decodes a single message pushed at once.
+/// V2 : [MessageDecoder] decodes many messages pushed in one chunk.
+/// V3 : [MessageDecoder] decodes packed messages split in 2 chunks at every byte boundary.
+/// V4 : [MessageDecoder] decodes packed messages split in 3 chunks at every pair of byte boundaries.
+/// V5 : [MessageDecoder] decodes packed messages pushed byte per byte.
+/// V6 : [MessageDecoder] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when size header exceed limit and discard buffer.
+/// V7 : [MessageDecoder] returns [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) for invalid message and continue decoding.
+/// V8 : [MessageDecoder::push] returns [`ErrorKind::BufferSizeTooSmall`](crate::net::ErrorKind::BufferSizeTooSmall) when capacity is exceeded.
+/// V9 : [MessageDecoder] returns [`ErrorKind::MessageSizeInvalid`](crate::net::ErrorKind::MessageSizeInvalid) when size header is smaller than message.
+#[cfg(test)]
+mod tests {
+    use crate::net::{ClientMessage, ClientPayload, ErrorKind, Message, MessageDecoder, ServerMessage, ServerPayload, CLIENT_MSG_MAX_SIZE, MESSAGE_SIZE_TYPE_SIZE, SERVER_MSG_BUFFER_SIZE};
+
+    fn client_msgs() -> Vec<ClientMessage> {
+        vec![ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 }),
+            ClientMessage::new(ClientPayload::Test { p16: u16::MAX, p32: u32::MAX }),
+            ClientMessage::new(ClientPayload::Test { p16: 3, p32: 4 })]
+    }
+
+    fn server_msgs() -> Vec<ServerMessage> {
+        vec![ServerMessage::new(10, ServerPayload::Error { err: 1 }),
+            ServerMessage::new(u64::MAX, ServerPayload::Test { p16: 5, p32: 6 }),
+            ServerMessage::new(12, ServerPayload::Error { err: u32::MAX })]
+    }
+
+    /// Pack messages one after the other, size header included.
+    fn pack<M : Message>(msgs : &[M]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut buffer = [0u8; 256];
+
+        for msg in msgs {
+            let size = msg.pack_bytes(&mut buffer).unwrap();
+            bytes.extend_from_slice(&buffer[..MESSAGE_SIZE_TYPE_SIZE + size]);
+        }
+
+        bytes
+    }
+
+    /// Push each chunk in decoder and collect all decoded messages.
+    fn decode<M : Message>(decoder : &mut MessageDecoder<M>, chunks : &[&[u8]]) -> Vec<M> {
+        let mut msgs = Vec::new();
+
+        for chunk in chunks {
+            decoder.push(chunk).unwrap();
+            for msg in decoder.by_ref() {
+                msgs.push(msg.unwrap());
+            }
+        }
+
+        msgs
+    }
+
+    #[test]
+    fn v1_decode_single() {
+        // V1 : [MessageDecoder] decodes a single message pushed at once.
+        let msgs = client_msgs();
+        let bytes = pack(&msgs[..1]);
+        let mut decoder = MessageDecoder::<ClientMessage>::new();
+
+        assert_eq!(decode(&mut decoder, &[&bytes]), msgs[..1]);
+        assert_eq!(decoder.buffered(), 0);
+    }
+
+    #[test]
+    fn v2_decode_many_in_one_chunk() {
+        // V2 : [MessageDecoder] decodes many messages pushed in one chunk.
+        let msgs = server_msgs();
+        let bytes = pack(&msgs);
+        let mut decoder = MessageDecoder::<ServerMessage>::with_capacity(SERVER_MSG_BUFFER_SIZE);
+
+        assert_eq!(decode(&mut decoder, &[&bytes]), msgs);
+        assert_eq!(decoder.buffered(), 0);
+    }
+
+    #[test]
+    fn v3_decode_split_every_boundary() {
+        // V3 : [MessageDecoder] decodes packed messages split in 2 chunks at every byte boundary.
+        let client = client_msgs();
+        let client_bytes = pack(&client);
+        let server = server_msgs();
+        let server_bytes = pack(&server);
+
+        for i in 0..=client_bytes.len() {
+            let mut decoder = MessageDecoder::<ClientMessage>::new();
+            assert_eq!(decode(&mut decoder, &[&client_bytes[..i], &client_bytes[i..]]), client, "Split at {}", i);
+        }
+
+        for i in 0..=server_bytes.len() {
+            let mut decoder = MessageDecoder::<ServerMessage>::new();
+            assert_eq!(decode(&mut decoder, &[&server_bytes[..i], &server_bytes[i..]]), server, "Split at {}", i);
+        }
+    }
+
+    #[test]
+    fn v4_decode_split_every_pair_of_boundaries() {
+        // V4 : [MessageDecoder] decodes packed messages split in 3 chunks at every pair of byte boundaries.
+        let client = client_msgs();
+        let client_bytes = pack(&client);
+        let server = server_msgs();
+        let server_bytes = pack(&server);
+
+        for i in 0..=client_bytes.len() {
+            for j in i..=client_bytes.len() {
+                let mut decoder = MessageDecoder::<ClientMessage>::new();
+                let chunks : [&[u8]; 3] = [&client_bytes[..i], &client_bytes[i..j], &client_bytes[j..]];
+                assert_eq!(decode(&mut decoder, &chunks), client, "Split at {} and {}", i, j);
+            }
+        }
+
+        for i in 0..=server_bytes.len() {
+            for j in i..=server_bytes.len() {
+                let mut decoder = MessageDecoder::<ServerMessage>::new();
+                let chunks : [&[u8]; 3] = [&server_bytes[..i], &server_bytes[i..j], &server_bytes[j..]];
+                assert_eq!(decode(&mut decoder, &chunks), server, "Split at {} and {}", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn v5_decode_byte_per_byte() {
+        // V5 : [MessageDecoder] decodes packed messages pushed byte per byte.
+        let server = server_msgs();
+        let server_bytes = pack(&server);
+        let chunks : Vec<&[u8]> = server_bytes.chunks(1).collect();
+        let mut decoder = MessageDecoder::<ServerMessage>::new();
+
+        assert_eq!(decode(&mut decoder, &chunks), server);
+    }
+
+    #[test]
+    fn v6_decode_size_greater_than_limit() {
+        // V6 : [MessageDecoder] returns [`ErrorKind::MessageSizeGreaterThanLimit`] when size header exceed limit and discard buffer.
+        let mut decoder = MessageDecoder::<ClientMessage>::new();
+        let size = (CLIENT_MSG_MAX_SIZE + 1) as u16;
+
+        decoder.push(&size.to_le_bytes()).unwrap();
+        assert_eq!(decoder.next(), Some(Err(ErrorKind::MessageSizeGreaterThanLimit.into())));
+        assert_eq!(decoder.buffered(), 0);
+        assert_eq!(decoder.next(), None);
+    }
+
+    #[test]
+    fn v7_decode_invalid_message() {
+        // V7 : [MessageDecoder] returns [`ErrorKind::InvalidMessage`] for invalid message and continue decoding.
+        let msgs = client_msgs();
+        let mut bytes = pack(&msgs[..1]);
+
+        // Unknown discriminant with a valid size header
+        bytes.extend_from_slice(&[2, 0, 1, 0]);
+        bytes.extend_from_slice(&pack(&msgs[1..2]));
+
+        let mut decoder = MessageDecoder::<ClientMessage>::new();
+        decoder.push(&bytes).unwrap();
+
+        assert_eq!(decoder.next(), Some(Ok(ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 }))));
+        assert_eq!(decoder.next(), Some(Err(ErrorKind::InvalidMessage.into())));
+        assert_eq!(decoder.next(), Some(Ok(ClientMessage::new(ClientPayload::Test { p16: u16::MAX, p32: u32::MAX }))));
+        assert_eq!(decoder.next(), None);
+    }
+
+    #[test]
+    fn v8_push_greater_than_capacity() {
+        // V8 : [MessageDecoder::push] returns [`ErrorKind::BufferSizeTooSmall`] when capacity is exceeded.
+        let mut decoder = MessageDecoder::<ClientMessage>::new();
+        let bytes = [0u8; MESSAGE_SIZE_TYPE_SIZE + CLIENT_MSG_MAX_SIZE + 1];
+
+        assert_eq!(decoder.remaining(), MESSAGE_SIZE_TYPE_SIZE + CLIENT_MSG_MAX_SIZE);
+        assert_eq!(decoder.push(&bytes), Err(ErrorKind::BufferSizeTooSmall.into()));
+        assert_eq!(decoder.buffered(), 0);
+        assert_eq!(decoder.push(&bytes[1..]), Ok(()));
+        assert_eq!(decoder.remaining(), 0);
+    }
+
+    #[test]
+    fn v9_decode_size_invalid() {
+        // V9 : [MessageDecoder] returns [`ErrorKind::MessageSizeInvalid`] when size header is smaller than message.
+        let msgs = client_msgs();
+        let mut bytes = pack(&msgs[..1]);
+
+        // Shrink size header by one and append the remaining byte as a new empty message
+        let size = (msgs[0].size - 1).to_le_bytes();
+        bytes[..MESSAGE_SIZE_TYPE_SIZE].copy_from_slice(&size);
+
+        let mut decoder = MessageDecoder::<ClientMessage>::new();
+        decoder.push(&bytes[..bytes.len() - 1]).unwrap();
+
+        assert_eq!(decoder.next(), Some(Err(ErrorKind::MessageSizeInvalid.into())));
+        assert_eq!(decoder.next(), None);
+    }
+}
@@ -23,10 +23,16 @@ SOFTWARE.
 */
 
 
-/// Possible communication errors.
-#[derive(Debug,PartialEq,Clone)]
+use std::{fmt::Display, sync::Arc};
+
+use tampon::TamponError;
+
+/// Kind of communication [`Error`].
+/// 
+/// Each kind has a stable numeric [`code`](ErrorKind::code) that MUST never change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u16)]
-pub enum Error {
+pub enum ErrorKind {
     /// Server or client received an invalid / malformed message.
     InvalidMessage = 1,
 
@@ -40,7 +46,354 @@ pub enum Error {
     MessageSizeInvalid = 4,
 
     /// Happens when unpacking a message reach the limit
-    MessageSizeGreaterThanLimit = 5
+    MessageSizeGreaterThanLimit = 5,
+
+    /// Happens when the remote peer closed the connection.
+    ConnectionClosed = 6,
+
+    /// Happens when an I/O operation on the underlying socket failed. See [`Error::io_kind`].
+    Io = 7,
+
+    /// Happens when client and server protocol version or payloads schema differ.
+    VersionMismatch = 8,
+
+    /// Happens when too many fragmented messages are partially received.
+    TooManyFragments = 9,
+
+    /// Happens when compressed content is corrupted or doesn't match its announced size.
+    DecompressionFailed = 10,
+
+    /// Happens when a sealed message is forged, altered or replayed, or when a key exchange is refused.
+    AuthenticationFailed = 11,
+
+    /// Happens when a request isn't answered in time.
+    TimedOut = 12,
+
+    /// Happens when too many reliable messages are waiting for acknowledgement, or are received ahead of a missing one.
+    TooManyPending = 13,
+}
+
+impl ErrorKind {
+    /// Every kind, by code.
+    pub const ALL : [ErrorKind; 13] = [ErrorKind::InvalidMessage, ErrorKind::BufferSizeTooSmall, ErrorKind::IncompleteMessage, 
+        ErrorKind::MessageSizeInvalid, ErrorKind::MessageSizeGreaterThanLimit, ErrorKind::ConnectionClosed, ErrorKind::Io, 
+        ErrorKind::VersionMismatch, ErrorKind::TooManyFragments, ErrorKind::DecompressionFailed, ErrorKind::AuthenticationFailed,
+        ErrorKind::TimedOut, ErrorKind::TooManyPending];
+
+    /// Stable numeric code of the kind.
+    pub const fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// Kind of a numeric code, [`None`] if unknown.
+    pub const fn from_code(code : u16) -> Option<ErrorKind> {
+        match code {
+            1..=13 => Some(Self::ALL[code as usize - 1]),
+            _ => None,
+        }
+    }
+
+    /// Short description of the kind.
+    const fn description(self) -> &'static str {
+        match self {
+            ErrorKind::InvalidMessage => "invalid message",
+            ErrorKind::BufferSizeTooSmall => "buffer too small",
+            ErrorKind::IncompleteMessage => "incomplete message",
+            ErrorKind::MessageSizeInvalid => "message size invalid",
+            ErrorKind::MessageSizeGreaterThanLimit => "message size greater than limit",
+            ErrorKind::ConnectionClosed => "connection closed",
+            ErrorKind::Io => "I/O error",
+            ErrorKind::VersionMismatch => "version mismatch",
+            ErrorKind::TooManyFragments => "too many fragments",
+            ErrorKind::DecompressionFailed => "decompression failed",
+            ErrorKind::AuthenticationFailed => "authentication failed",
+            ErrorKind::TimedOut => "timed out",
+            ErrorKind::TooManyPending => "too many pending messages",
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Details of an [`Error`] explaining why a message was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ErrorContext {
+    /// No details.
+    #[default]
+    None,
+
+    /// Size in bytes expected, or allowed for [`ErrorKind::MessageSizeGreaterThanLimit`], and actual size.
+    Size { 
+        /// Size expected or allowed.
+        expected : usize, 
+        
+        /// Actual size.
+        actual : usize 
+    },
+
+    /// Unknown payload discriminant.
+    Discriminant { 
+        /// Discriminant read.
+        value : u16, 
+        
+        /// Offset in bytes of the discriminant.
+        offset : usize 
+    },
+
+    /// Offset in bytes where the error was found.
+    Offset(usize),
+}
+
+/// Possible communication errors.
+/// 
+/// An error has an [`ErrorKind`] with a stable code, optional [`ErrorContext`] and, for I/O errors, 
+/// the underlying [`std::io::Error`] as [`source`](std::error::Error::source).
+/// 
+/// # Note(s)
+/// Errors are equal when of the same kind, and same [`io_kind`](Error::io_kind) for [`ErrorKind::Io`]. Context is informative only.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ ClientMessage, Error, ErrorContext, ErrorKind };
+/// 
+/// let err = ClientMessage::from_bytes(&[0xFF, 0x00]).unwrap_err();
+/// 
+/// assert_eq!(err, Error::from(ErrorKind::InvalidMessage));
+/// assert_eq!(err.context(), ErrorContext::Discriminant { value: 0x00FF, offset: 0 });
+/// assert_eq!(err.to_string(), "invalid message: unknown discriminant 255 at byte 0");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Error {
+    /// Kind of error.
+    kind : ErrorKind,
+
+    /// Details of error.
+    context : ErrorContext,
+
+    /// Underlying I/O error.
+    source : Option<Arc<std::io::Error>>,
+}
+
+impl Error {
+    /// Create a new [`Error`] without context.
+    pub const fn new(kind : ErrorKind) -> Error {
+        Error { kind, context: ErrorContext::None, source: None }
+    }
+
+    /// Create a new [`Error`] with the size expected, or allowed, and actual size.
+    pub const fn size(kind : ErrorKind, expected : usize, actual : usize) -> Error {
+        Error { kind, context: ErrorContext::Size { expected, actual }, source: None }
+    }
+
+    /// Create a new [`ErrorKind::InvalidMessage`] for an unknown discriminant read at an offset.
+    pub const fn discriminant(value : u16, offset : usize) -> Error {
+        Error { kind: ErrorKind::InvalidMessage, context: ErrorContext::Discriminant { value, offset }, source: None }
+    }
+
+    /// Create a new [`Error`] found at an offset in bytes.
+    pub const fn at(kind : ErrorKind, offset : usize) -> Error {
+        Error { kind, context: ErrorContext::Offset(offset), source: None }
+    }
+
+    /// Kind of error.
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Stable numeric code of the kind.
+    pub const fn code(&self) -> u16 {
+        self.kind.code()
+    }
+
+    /// Details of error.
+    pub const fn context(&self) -> ErrorContext {
+        self.context
+    }
+
+    /// Kind of the underlying I/O error, if any.
+    pub fn io_kind(&self) -> Option<std::io::ErrorKind> {
+        self.source.as_ref().map(|err| err.kind())
+    }
+}
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && (self.kind != ErrorKind::Io || self.io_kind() == other.io_kind())
+    }
+}
+
+impl Eq for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        match self.context {
+            ErrorContext::None => {},
+            ErrorContext::Size { expected, actual } if self.kind == ErrorKind::MessageSizeGreaterThanLimit => 
+                write!(f, ": limit is {} bytes, got {}", expected, actual)?,
+            ErrorContext::Size { expected, actual } => write!(f, ": expected {} bytes, got {}", expected, actual)?,
+            ErrorContext::Discriminant { value, offset } => write!(f, ": unknown discriminant {} at byte {}", value, offset)?,
+            ErrorContext::Offset(offset) => write!(f, " at byte {}", offset)?,
+        }
+
+        match &self.source {
+            Some(source) => write!(f, ": {}", source),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|err| err as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind : ErrorKind) -> Self {
+        Error::new(kind)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        let kind = match err.kind() {
+            std::io::ErrorKind::UnexpectedEof => ErrorKind::ConnectionClosed,
+            _ => ErrorKind::Io,
+        };
+
+        Error { kind, context: ErrorContext::None, source: Some(Arc::new(err)) }
+    }
+}
+
+impl From<TamponError> for Error {
+    fn from(err: TamponError) -> Self {
+        match err {
+            TamponError::DeserializeSizeBufferIncomplete => Error::new(ErrorKind::IncompleteMessage),
+            TamponError::DeserializeSizeGreaterThanMax => Error::new(ErrorKind::MessageSizeGreaterThanLimit),
+        }
+    }
+}
+
+
+/// This module test [Error] and [ErrorKind].
+/// 
+/// # Verification(s)
+/// V1 : [ErrorKind::code] is stable and [ErrorKind::from_code] gives back the kind.
+/// V2 : [Error] displays its kind, context and source.
+/// V3 : [std::io::Error] converts to [ErrorKind::ConnectionClosed] or [ErrorKind::Io], kept as source.
+/// V4 : [TamponError] converts to the matching [ErrorKind].
+/// V5 : [Error] equality compares kind and I/O kind, ignoring context.
+/// V6 : Messages rejected by [Message](crate::net::Message) and [MessageDecoder](crate::net::MessageDecoder) explain why with context.
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use tampon::TamponError;
+
+    use crate::net::{ClientMessage, ClientPayload, Error, ErrorContext, ErrorKind, MessageDecoder, CLIENT_MSG_MAX_SIZE, MESSAGE_SIZE_TYPE_SIZE};
+
+    #[test]
+    fn v1_codes() {
+        // V1 : [ErrorKind::code] is stable and [ErrorKind::from_code] gives back the kind.
+        let codes = [(ErrorKind::InvalidMessage, 1), (ErrorKind::BufferSizeTooSmall, 2), (ErrorKind::IncompleteMessage, 3), 
+            (ErrorKind::MessageSizeInvalid, 4), (ErrorKind::MessageSizeGreaterThanLimit, 5), (ErrorKind::ConnectionClosed, 6), 
+            (ErrorKind::Io, 7), (ErrorKind::VersionMismatch, 8), (ErrorKind::TooManyFragments, 9), (ErrorKind::DecompressionFailed, 10), 
+            (ErrorKind::AuthenticationFailed, 11), (ErrorKind::TimedOut, 12), (ErrorKind::TooManyPending, 13)];
+
+        assert_eq!(codes.len(), ErrorKind::ALL.len());
+        for (kind, code) in codes {
+            assert_eq!(kind.code(), code);
+            assert_eq!(Error::new(kind).code(), code);
+            assert_eq!(ErrorKind::from_code(code), Some(kind));
+        }
+        assert_eq!(ErrorKind::from_code(0), None);
+        assert_eq!(ErrorKind::from_code(14), None);
+    }
+
+    #[test]
+    fn v2_display() {
+        // V2 : [Error] displays its kind, context and source.
+        assert_eq!(Error::new(ErrorKind::VersionMismatch).to_string(), "version mismatch");
+        assert_eq!(Error::size(ErrorKind::MessageSizeInvalid, 12, 10).to_string(), "message size invalid: expected 12 bytes, got 10");
+        assert_eq!(Error::size(ErrorKind::MessageSizeGreaterThanLimit, 1024, 2000).to_string(), "message size greater than limit: limit is 1024 bytes, got 2000");
+        assert_eq!(Error::discriminant(42, 7).to_string(), "invalid message: unknown discriminant 42 at byte 7");
+        assert_eq!(Error::at(ErrorKind::IncompleteMessage, 3).to_string(), "incomplete message at byte 3");
+
+        let err = Error::from(std::io::Error::new(std::io::ErrorKind::TimedOut, "read timed out"));
+        assert_eq!(err.to_string(), "I/O error: read timed out");
+    }
+
+    #[test]
+    fn v3_from_io() {
+        // V3 : [std::io::Error] converts to [ErrorKind::ConnectionClosed] or [ErrorKind::Io], kept as source.
+        let err = Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+        assert_eq!(err.kind(), ErrorKind::ConnectionClosed);
+        assert_eq!(err.io_kind(), Some(std::io::ErrorKind::UnexpectedEof));
+
+        let err = Error::from(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset by peer"));
+        assert_eq!(err.kind(), ErrorKind::Io);
+        assert_eq!(err.io_kind(), Some(std::io::ErrorKind::ConnectionReset));
+
+        let source = err.source().unwrap().downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(source.kind(), std::io::ErrorKind::ConnectionReset);
+        assert_eq!(source.to_string(), "reset by peer");
+
+        // Clones share the source
+        assert_eq!(err.clone().io_kind(), Some(std::io::ErrorKind::ConnectionReset));
+        assert!(Error::new(ErrorKind::InvalidMessage).source().is_none());
+    }
+
+    #[test]
+    fn v4_from_tampon() {
+        // V4 : [TamponError] converts to the matching [ErrorKind].
+        assert_eq!(Error::from(TamponError::DeserializeSizeBufferIncomplete).kind(), ErrorKind::IncompleteMessage);
+        assert_eq!(Error::from(TamponError::DeserializeSizeGreaterThanMax).kind(), ErrorKind::MessageSizeGreaterThanLimit);
+    }
+
+    #[test]
+    fn v5_equality() {
+        // V5 : [Error] equality compares kind and I/O kind, ignoring context.
+        assert_eq!(Error::size(ErrorKind::MessageSizeInvalid, 1, 2), Error::new(ErrorKind::MessageSizeInvalid));
+        assert_eq!(Error::discriminant(1, 0), Error::from(ErrorKind::InvalidMessage));
+        assert_ne!(Error::new(ErrorKind::InvalidMessage), Error::new(ErrorKind::IncompleteMessage));
+
+        let io = |kind| Error::from(std::io::Error::from(kind));
+        assert_eq!(io(std::io::ErrorKind::TimedOut), io(std::io::ErrorKind::TimedOut));
+        assert_ne!(io(std::io::ErrorKind::TimedOut), io(std::io::ErrorKind::WouldBlock));
+        assert_eq!(io(std::io::ErrorKind::UnexpectedEof), Error::new(ErrorKind::ConnectionClosed));
+    }
+
+    #[test]
+    fn v6_context() {
+        // V6 : Messages rejected by [Message](crate::net::Message) and [MessageDecoder](crate::net::MessageDecoder) explain why with context.
+        let message = ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 });
+        let size = message.size as usize;
+        let mut buffer = vec![0u8; MESSAGE_SIZE_TYPE_SIZE + size + 1];
+
+        let err = message.pack_bytes(&mut buffer[..4]).unwrap_err();
+        assert_eq!(err.context(), ErrorContext::Size { expected: MESSAGE_SIZE_TYPE_SIZE + size, actual: 4 });
+        message.pack_bytes(&mut buffer).unwrap();
+
+        let err = ClientMessage::from_bytes(&[0x01, 0x00, 0x00]).unwrap_err();
+        assert_eq!(err.context(), ErrorContext::Discriminant { value: 1, offset: 0 });
+
+        let err = ClientMessage::from_bytes(&[0x01]).unwrap_err();
+        assert_eq!((err.kind(), err.context()), (ErrorKind::IncompleteMessage, ErrorContext::Size { expected: 2, actual: 1 }));
 
+        let err = ClientMessage::from_bytes(&buffer[MESSAGE_SIZE_TYPE_SIZE..]).unwrap_err();
+        assert_eq!((err.kind(), err.context()), (ErrorKind::MessageSizeInvalid, ErrorContext::Size { expected: size, actual: size + 1 }));
 
-}
\ No newline at end of file
+        let mut decoder = MessageDecoder::<ClientMessage>::new();
+        decoder.push(&(CLIENT_MSG_MAX_SIZE as u16 + 1).to_le_bytes()).unwrap();
+        let err = decoder.next().unwrap().unwrap_err();
+        assert_eq!(err.context(), ErrorContext::Size { expected: CLIENT_MSG_MAX_SIZE, actual: CLIENT_MSG_MAX_SIZE + 1 });
+        assert_eq!(err.to_string(), format!("message size greater than limit: limit is {} bytes, got {}", CLIENT_MSG_MAX_SIZE, CLIENT_MSG_MAX_SIZE + 1));
+    }
+}
@@ -0,0 +1,356 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::fmt::Display;
+
+use crate::net::{Error, ErrorKind, ServerPayload};
+
+/// Count of codes of each [`ErrorCategory`].
+const CATEGORY_RANGE : u32 = 1000;
+
+/// Category of a [`ServerErrorCode`], given by its thousands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ErrorCategory {
+    /// Malformed, unexpected or incompatible messages. Codes 1000 to 1999.
+    Protocol = 1,
+
+    /// Login, sessions and permissions. Codes 2000 to 2999.
+    Auth = 2,
+
+    /// Client sending too much or too often. Codes 3000 to 3999.
+    RateLimit = 3,
+
+    /// Actions refused by the rules of the game. Codes 4000 to 4999.
+    GameRule = 4,
+
+    /// Failures of the server itself. Codes 5000 to 5999.
+    Server = 5,
+
+    /// Codes outside known categories.
+    Other = 0,
+}
+
+impl ErrorCategory {
+    /// Category of a code, known or not.
+    pub const fn from_code(code : u32) -> ErrorCategory {
+        match code / CATEGORY_RANGE {
+            1 => ErrorCategory::Protocol,
+            2 => ErrorCategory::Auth,
+            3 => ErrorCategory::RateLimit,
+            4 => ErrorCategory::GameRule,
+            5 => ErrorCategory::Server,
+            _ => ErrorCategory::Other,
+        }
+    }
+}
+
+/// Write [`ServerErrorCode`] and its conversions from a table of codes.
+macro_rules! server_error_codes {
+    ($($(#[$attr:meta])* $name : ident = $code : literal, $message : literal, $key : literal;)+) => {
+        /// Error sent by server with [`ServerPayload::Error`], shared by client and server.
+        /// 
+        /// Codes are stable: a code MUST never change nor be reused once released. Codes unknown to this version, 
+        /// sent by a newer server, are kept as [`ServerErrorCode::Unknown`] so they convert back to the same `u32`.
+        /// 
+        /// # Example(s)
+        /// ```
+        /// use ethos_core::net::{ ErrorCategory, ServerErrorCode, ServerPayload };
+        /// 
+        /// let payload = ServerPayload::from(ServerErrorCode::SessionExpired);
+        /// assert_eq!(payload, ServerPayload::Error { err: 2003 });
+        /// 
+        /// let ServerPayload::Error { err } = payload else { unreachable!() };
+        /// let code = ServerErrorCode::from(err);
+        /// assert_eq!(code.category(), ErrorCategory::Auth);
+        /// assert_eq!(code.localisation_key(), Some("error.auth.session_expired"));
+        /// 
+        /// // Future codes are kept
+        /// assert_eq!(u32::from(ServerErrorCode::from(4999)), 4999);
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ServerErrorCode {
+            $(
+                $(#[$attr])*
+                $name,
+            )+
+
+            /// Code unknown to this version of the catalogue.
+            Unknown(u32),
+        }
+
+        impl ServerErrorCode {
+            /// Every known code of the catalogue.
+            pub const ALL : &'static [ServerErrorCode] = &[$(ServerErrorCode::$name),+];
+
+            /// Numeric code sent on the wire.
+            pub const fn code(self) -> u32 {
+                match self {
+                    $(ServerErrorCode::$name => $code,)+
+                    ServerErrorCode::Unknown(code) => code,
+                }
+            }
+
+            /// Code of a numeric code, [`ServerErrorCode::Unknown`] if not in the catalogue.
+            pub const fn from_code(code : u32) -> ServerErrorCode {
+                match code {
+                    $($code => ServerErrorCode::$name,)+
+                    code => ServerErrorCode::Unknown(code),
+                }
+            }
+
+            /// Default human-readable message, in english.
+            pub const fn message(self) -> &'static str {
+                match self {
+                    $(ServerErrorCode::$name => $message,)+
+                    ServerErrorCode::Unknown(_) => "Unknown server error.",
+                }
+            }
+
+            /// Key of the message in localisation files, [`None`] for unknown codes.
+            pub const fn localisation_key(self) -> Option<&'static str> {
+                match self {
+                    $(ServerErrorCode::$name => Some($key),)+
+                    ServerErrorCode::Unknown(_) => None,
+                }
+            }
+        }
+    };
+}
+
+// IMPORTANT : Codes MUST never change nor be reused. Add new codes in the thousands of their category.
+server_error_codes! {
+    /// Message is invalid or malformed.
+    InvalidMessage = 1001, "Message is invalid or malformed.", "error.protocol.invalid_message";
+
+    /// Protocol version or payloads schema differ.
+    VersionMismatch = 1002, "Client version doesn't match server.", "error.protocol.version_mismatch";
+
+    /// Message exceed its maximum size.
+    MessageTooLarge = 1003, "Message is too large.", "error.protocol.message_too_large";
+
+    /// Compressed message couldn't be decompressed.
+    DecompressionFailed = 1004, "Message couldn't be decompressed.", "error.protocol.decompression_failed";
+
+    /// Message is valid but not expected at this point.
+    UnexpectedMessage = 1005, "Message isn't expected now.", "error.protocol.unexpected_message";
+
+    /// Sealed message or key exchange failed authentication.
+    AuthenticationFailed = 2001, "Authentication failed.", "error.auth.authentication_failed";
+
+    /// Account or secret is invalid.
+    InvalidCredentials = 2002, "Account or secret is invalid.", "error.auth.invalid_credentials";
+
+    /// Session token expired.
+    SessionExpired = 2003, "Session expired, please log in again.", "error.auth.session_expired";
+
+    /// Session token was revoked.
+    SessionRevoked = 2004, "Session was revoked, please log in again.", "error.auth.session_revoked";
+
+    /// Action requires a login.
+    NotAuthenticated = 2005, "Please log in first.", "error.auth.not_authenticated";
+
+    /// Account is locked or banned.
+    AccountLocked = 2006, "Account is locked.", "error.auth.account_locked";
+
+    /// Too many messages sent.
+    TooManyRequests = 3001, "Too many requests, please slow down.", "error.rate_limit.too_many_requests";
+
+    /// Too many failed logins.
+    TooManyLoginAttempts = 3002, "Too many login attempts, please wait.", "error.rate_limit.too_many_login_attempts";
+
+    /// Too many connections from the same client.
+    TooManyConnections = 3003, "Too many connections.", "error.rate_limit.too_many_connections";
+
+    /// Action isn't allowed by the rules.
+    ActionNotAllowed = 4001, "Action isn't allowed.", "error.game_rule.action_not_allowed";
+
+    /// Target of the action is invalid or out of reach.
+    InvalidTarget = 4002, "Target is invalid.", "error.game_rule.invalid_target";
+
+    /// Not enough resources for the action.
+    InsufficientResources = 4003, "Not enough resources.", "error.game_rule.insufficient_resources";
+
+    /// Action is on cooldown.
+    Cooldown = 4004, "Action isn't ready yet.", "error.game_rule.cooldown";
+
+    /// Unexpected failure of server.
+    Internal = 5001, "Internal server error.", "error.server.internal";
+
+    /// Server is shutting down or under maintenance.
+    Unavailable = 5002, "Server is unavailable.", "error.server.unavailable";
+}
+
+impl ServerErrorCode {
+    /// Category of the code, also known for [`ServerErrorCode::Unknown`] codes.
+    pub const fn category(self) -> ErrorCategory {
+        ErrorCategory::from_code(self.code())
+    }
+
+    /// Returns true if code isn't in this version of the catalogue.
+    pub const fn is_unknown(self) -> bool {
+        matches!(self, ServerErrorCode::Unknown(_))
+    }
+}
+
+impl From<u32> for ServerErrorCode {
+    fn from(code : u32) -> Self {
+        ServerErrorCode::from_code(code)
+    }
+}
+
+impl From<ServerErrorCode> for u32 {
+    fn from(code : ServerErrorCode) -> Self {
+        code.code()
+    }
+}
+
+impl From<ServerErrorCode> for ServerPayload {
+    fn from(code : ServerErrorCode) -> Self {
+        ServerPayload::Error { err: code.code() }
+    }
+}
+
+/// Code sent to a client whose message caused an [`Error`].
+impl From<&Error> for ServerErrorCode {
+    fn from(err : &Error) -> Self {
+        match err.kind() {
+            ErrorKind::InvalidMessage | ErrorKind::BufferSizeTooSmall | ErrorKind::IncompleteMessage | ErrorKind::MessageSizeInvalid => ServerErrorCode::InvalidMessage,
+            ErrorKind::MessageSizeGreaterThanLimit => ServerErrorCode::MessageTooLarge,
+            ErrorKind::VersionMismatch => ServerErrorCode::VersionMismatch,
+            ErrorKind::DecompressionFailed => ServerErrorCode::DecompressionFailed,
+            ErrorKind::AuthenticationFailed => ServerErrorCode::AuthenticationFailed,
+            ErrorKind::TooManyFragments | ErrorKind::TooManyPending => ServerErrorCode::TooManyRequests,
+            ErrorKind::ConnectionClosed | ErrorKind::Io => ServerErrorCode::Internal,
+            ErrorKind::TimedOut => ServerErrorCode::Unavailable,
+        }
+    }
+}
+
+impl Display for ServerErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (code {})", self.message(), self.code())
+    }
+}
+
+
+/// This module test [ServerErrorCode] and [ErrorCategory].
+/// 
+/// # Verification(s)
+/// V1 : Known codes are unique, in the range of their category, and convert to and from `u32`.
+/// V2 : Unknown codes convert back to the same `u32` and keep their category.
+/// V3 : Known codes have a message and a unique localisation key of their category.
+/// V4 : [Error] converts to the matching [ServerErrorCode].
+/// V5 : [ServerErrorCode] goes through a packed [ServerPayload::Error].
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::net::{Error, ErrorKind, ErrorCategory, ServerErrorCode, ServerMessage, ServerPayload, MESSAGE_SIZE_TYPE_SIZE};
+
+    #[test]
+    fn v1_known_codes() {
+        // V1 : Known codes are unique, in the range of their category, and convert to and from `u32`.
+        let mut codes = HashSet::new();
+        for code in ServerErrorCode::ALL {
+            assert!(codes.insert(code.code()), "{:?} duplicated", code);
+            assert!(!code.is_unknown());
+            assert_ne!(code.category(), ErrorCategory::Other);
+            assert_eq!(ServerErrorCode::from(u32::from(*code)), *code);
+        }
+
+        assert_eq!(ServerErrorCode::InvalidMessage.category(), ErrorCategory::Protocol);
+        assert_eq!(ServerErrorCode::InvalidCredentials.category(), ErrorCategory::Auth);
+        assert_eq!(ServerErrorCode::TooManyRequests.category(), ErrorCategory::RateLimit);
+        assert_eq!(ServerErrorCode::Cooldown.category(), ErrorCategory::GameRule);
+        assert_eq!(ServerErrorCode::Internal.category(), ErrorCategory::Server);
+    }
+
+    #[test]
+    fn v2_unknown_codes() {
+        // V2 : Unknown codes convert back to the same `u32` and keep their category.
+        for (code, category) in [(0, ErrorCategory::Other), (1999, ErrorCategory::Protocol), (2999, ErrorCategory::Auth), 
+            (3500, ErrorCategory::RateLimit), (4999, ErrorCategory::GameRule), (5999, ErrorCategory::Server), (6000, ErrorCategory::Other), (u32::MAX, ErrorCategory::Other)] {
+            let unknown = ServerErrorCode::from(code);
+            assert_eq!(unknown, ServerErrorCode::Unknown(code));
+            assert!(unknown.is_unknown());
+            assert_eq!(u32::from(unknown), code);
+            assert_eq!(unknown.category(), category);
+            assert_eq!(unknown.localisation_key(), None);
+            assert_eq!(unknown.message(), "Unknown server error.");
+        }
+    }
+
+    #[test]
+    fn v3_messages() {
+        // V3 : Known codes have a message and a unique localisation key of their category.
+        let mut keys = HashSet::new();
+        for code in ServerErrorCode::ALL {
+            assert!(!code.message().is_empty());
+            let key = code.localisation_key().unwrap();
+            assert!(keys.insert(key), "{} duplicated", key);
+
+            let prefix = match code.category() {
+                ErrorCategory::Protocol => "error.protocol.",
+                ErrorCategory::Auth => "error.auth.",
+                ErrorCategory::RateLimit => "error.rate_limit.",
+                ErrorCategory::GameRule => "error.game_rule.",
+                ErrorCategory::Server => "error.server.",
+                ErrorCategory::Other => unreachable!(),
+            };
+            assert!(key.starts_with(prefix), "{} not in {:?}", key, code.category());
+        }
+
+        assert_eq!(ServerErrorCode::Cooldown.to_string(), "Action isn't ready yet. (code 4004)");
+    }
+
+    #[test]
+    fn v4_from_error() {
+        // V4 : [Error] converts to the matching [ServerErrorCode].
+        assert_eq!(ServerErrorCode::from(&Error::new(ErrorKind::InvalidMessage)), ServerErrorCode::InvalidMessage);
+        assert_eq!(ServerErrorCode::from(&Error::new(ErrorKind::MessageSizeInvalid)), ServerErrorCode::InvalidMessage);
+        assert_eq!(ServerErrorCode::from(&Error::new(ErrorKind::MessageSizeGreaterThanLimit)), ServerErrorCode::MessageTooLarge);
+        assert_eq!(ServerErrorCode::from(&Error::new(ErrorKind::VersionMismatch)), ServerErrorCode::VersionMismatch);
+        assert_eq!(ServerErrorCode::from(&Error::new(ErrorKind::DecompressionFailed)), ServerErrorCode::DecompressionFailed);
+        assert_eq!(ServerErrorCode::from(&Error::new(ErrorKind::AuthenticationFailed)), ServerErrorCode::AuthenticationFailed);
+        assert_eq!(ServerErrorCode::from(&Error::new(ErrorKind::TooManyFragments)), ServerErrorCode::TooManyRequests);
+        assert_eq!(ServerErrorCode::from(&Error::from(std::io::Error::from(std::io::ErrorKind::Other))), ServerErrorCode::Internal);
+    }
+
+    #[test]
+    fn v5_payload() {
+        // V5 : [ServerErrorCode] goes through a packed [ServerPayload::Error].
+        for code in ServerErrorCode::ALL.iter().copied().chain([ServerErrorCode::Unknown(4242)]) {
+            let message = ServerMessage::new(0, code.into());
+            let mut buffer = vec![0u8; MESSAGE_SIZE_TYPE_SIZE + message.size as usize];
+            message.pack_bytes(&mut buffer).unwrap();
+
+            match ServerMessage::from_bytes(&buffer[MESSAGE_SIZE_TYPE_SIZE..]).unwrap().payload {
+                ServerPayload::Error { err } => assert_eq!(ServerErrorCode::from(err), code),
+                payload => panic!("Error expected, got {:?}", payload),
+            }
+        }
+    }
+}
@@ -0,0 +1,568 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use tampon::TamponError;
+
+use crate::net::NestedSchema;
+
+/// Size of the length prefix of variable-length fields.
+pub const FIELD_LENGTH_TYPE_SIZE : usize = size_of::<u16>();
+
+/// Field of a payload generated by [`write_messages_payloads!`](crate::write_messages_payloads).
+/// 
+/// Scalars are packed in little-endian. [`String`], [`Vec<T>`] and [`Box<[T]>`] are prefixed by their
+/// length as u16 (bytes for [`String`], elements otherwise). Fixed arrays `[T; N]` have no prefix.
+/// 
+/// # Optional fields
+/// [`Option<T>`] fields of a payload have their presence packed in a bitmap after the discriminant, so 
+/// absent fields cost a single bit. Elsewhere, like in [`Vec<Option<T>>`], presence is packed as a u8 before the value.
+/// 
+/// # Length limit
+/// Variable-length fields can declare a maximum length with `name : Type [max N]`. The limit applies to
+/// the outermost length only and is checked by [`PayloadField::read_field_size`] before anything is allocated.
+pub trait PayloadField : Sized {
+    /// Hash of the definition of nested types, so that [`SCHEMA_HASH`](crate::net::SCHEMA_HASH) changes with them. 
+    /// 
+    /// 0 for primitives, which are identified by their name.
+    const SCHEMA_HASH : u64 = 0;
+
+    /// True if presence of field is packed in the bitmap of payload, like [`Option<T>`].
+    const OPTIONAL : bool = false;
+
+    /// Packed size of every value, if constant, so that lists are sized without reading their elements.
+    const FIXED_SIZE : Option<usize> = None;
+
+    /// Layout of nested types generated by [`write_payload_struct!`](crate::write_payload_struct) and 
+    /// [`write_payload_enum!`](crate::write_payload_enum), or of the nested elements of a list or option.
+    /// 
+    /// [`None`] for primitives.
+    const NESTED : Option<&'static NestedSchema> = None;
+
+    /// Packed size of field in bytes.
+    fn field_size(&self) -> usize;
+
+    /// Pack field at the beginning of buffer.
+    /// 
+    /// Buffer must be at least [`field_size`](Self::field_size) long.
+    /// 
+    /// # Returns
+    /// Count of bytes written.
+    fn write_field(&self, buffer : &mut [u8]) -> usize;
+
+    /// Unpack field from the beginning of buffer.
+    /// 
+    /// Buffer must have been validated with [`read_field_size`](Self::read_field_size).
+    /// 
+    /// # Returns
+    /// Field and count of bytes read.
+    fn read_field(buffer : &[u8]) -> (Self, usize);
+
+    /// Size of the field packed at the beginning of buffer.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Size of the packed field.
+    /// - [`Err`]:
+    ///     1. [`TamponError::DeserializeSizeBufferIncomplete`] if buffer is too short.
+    ///     2. [`TamponError::DeserializeSizeGreaterThanMax`] if length exceed `max_len`.
+    fn read_field_size(buffer : &[u8], max_len : usize) -> Result<usize, TamponError>;
+
+    /// Returns true if length of field doesn't exceed `max_len` nor the length prefix.
+    fn within_limits(&self, _max_len : usize) -> bool {
+        true
+    }
+
+    /// Packed size of field in bytes when presence is packed in bitmap.
+    fn bitmap_field_size(&self) -> usize {
+        self.field_size()
+    }
+
+    /// Pack field at the beginning of buffer when presence is packed in bitmap.
+    /// 
+    /// # Returns
+    /// Presence of field and count of bytes written.
+    fn write_bitmap_field(&self, buffer : &mut [u8]) -> (bool, usize) {
+        (true, self.write_field(buffer))
+    }
+
+    /// Unpack field whose presence was read from bitmap.
+    fn read_bitmap_field(buffer : &[u8], _present : bool) -> (Self, usize) {
+        Self::read_field(buffer)
+    }
+
+    /// Size of field whose presence was read from bitmap.
+    fn read_bitmap_field_size(buffer : &[u8], _present : bool, max_len : usize) -> Result<usize, TamponError> {
+        Self::read_field_size(buffer, max_len)
+    }
+
+    /// Value of given length used by generated tests.
+    #[doc(hidden)]
+    fn sample(len : usize) -> Self;
+}
+
+/// Implement [`PayloadField`] for numeric types.
+macro_rules! impl_numeric_field {
+    ($($type : ty),+) => {
+        $(
+            impl PayloadField for $type {
+                const FIXED_SIZE : Option<usize> = Some(size_of::<$type>());
+
+                fn field_size(&self) -> usize {
+                    size_of::<$type>()
+                }
+
+                fn write_field(&self, buffer : &mut [u8]) -> usize {
+                    buffer[..size_of::<$type>()].copy_from_slice(&self.to_le_bytes());
+                    size_of::<$type>()
+                }
+
+                fn read_field(buffer : &[u8]) -> (Self, usize) {
+                    (<$type>::from_le_bytes(buffer[..size_of::<$type>()].try_into().unwrap()), size_of::<$type>())
+                }
+
+                fn read_field_size(buffer : &[u8], _max_len : usize) -> Result<usize, TamponError> {
+                    if buffer.len() < size_of::<$type>() {
+                        Err(TamponError::DeserializeSizeBufferIncomplete)
+                    } else {
+                        Ok(size_of::<$type>())
+                    }
+                }
+
+                fn sample(len : usize) -> Self {
+                    len as $type
+                }
+            }
+        )+
+    };
+}
+
+impl_numeric_field!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl PayloadField for bool {
+    const FIXED_SIZE : Option<usize> = Some(size_of::<u8>());
+
+    fn field_size(&self) -> usize {
+        size_of::<u8>()
+    }
+
+    fn write_field(&self, buffer : &mut [u8]) -> usize {
+        buffer[0] = *self as u8;
+        size_of::<u8>()
+    }
+
+    fn read_field(buffer : &[u8]) -> (Self, usize) {
+        (buffer[0] != 0, size_of::<u8>())
+    }
+
+    fn read_field_size(buffer : &[u8], max_len : usize) -> Result<usize, TamponError> {
+        u8::read_field_size(buffer, max_len)
+    }
+
+    fn sample(len : usize) -> Self {
+        len % 2 == 1
+    }
+}
+
+/// Read the length prefix of a variable-length field.
+fn read_length(buffer : &[u8], max_len : usize) -> Result<usize, TamponError> {
+    if buffer.len() < FIELD_LENGTH_TYPE_SIZE {
+        Err(TamponError::DeserializeSizeBufferIncomplete)
+    } else {
+        let len = u16::read_field(buffer).0 as usize;
+        if len > max_len {
+            Err(TamponError::DeserializeSizeGreaterThanMax)
+        } else {
+            Ok(len)
+        }
+    }
+}
+
+/// Returns true if length fits the limit and the length prefix.
+fn length_within_limits(len : usize, max_len : usize) -> bool {
+    len <= max_len && len <= u16::MAX as usize
+}
+
+/// Pack elements after their length prefix.
+fn write_elements<T : PayloadField>(elements : &[T], buffer : &mut [u8]) -> usize {
+    let mut size = (elements.len() as u16).write_field(buffer);
+    for element in elements {
+        size += element.write_field(&mut buffer[size..]);
+    }
+    size
+}
+
+/// Unpack elements prefixed by their length.
+fn read_elements<T : PayloadField>(buffer : &[u8]) -> (Vec<T>, usize) {
+    let (len, mut size) = u16::read_field(buffer);
+    let mut elements = Vec::with_capacity(len as usize);
+    for _ in 0..len {
+        let (element, element_size) = T::read_field(&buffer[size..]);
+        elements.push(element);
+        size += element_size;
+    }
+    (elements, size)
+}
+
+/// Size of elements prefixed by their length.
+fn read_elements_size<T : PayloadField>(buffer : &[u8], max_len : usize) -> Result<usize, TamponError> {
+    let len = read_length(buffer, max_len)?;
+    match T::FIXED_SIZE {
+        Some(element_size) if buffer.len() < FIELD_LENGTH_TYPE_SIZE + element_size * len => Err(TamponError::DeserializeSizeBufferIncomplete),
+        Some(element_size) => Ok(FIELD_LENGTH_TYPE_SIZE + element_size * len),
+        None => {
+            let mut size = FIELD_LENGTH_TYPE_SIZE;
+            for _ in 0..len {
+                size += T::read_field_size(&buffer[size..], usize::MAX)?;
+            }
+            Ok(size)
+        },
+    }
+}
+
+impl PayloadField for String {
+    fn field_size(&self) -> usize {
+        FIELD_LENGTH_TYPE_SIZE + self.len()
+    }
+
+    fn write_field(&self, buffer : &mut [u8]) -> usize {
+        (self.len() as u16).write_field(buffer);
+        buffer[FIELD_LENGTH_TYPE_SIZE..self.field_size()].copy_from_slice(self.as_bytes());
+        self.field_size()
+    }
+
+    /// Invalid UTF-8 sequences are replaced by [`char::REPLACEMENT_CHARACTER`].
+    fn read_field(buffer : &[u8]) -> (Self, usize) {
+        let size = FIELD_LENGTH_TYPE_SIZE + u16::read_field(buffer).0 as usize;
+        (String::from_utf8_lossy(&buffer[FIELD_LENGTH_TYPE_SIZE..size]).into_owned(), size)
+    }
+
+    fn read_field_size(buffer : &[u8], max_len : usize) -> Result<usize, TamponError> {
+        let size = FIELD_LENGTH_TYPE_SIZE + read_length(buffer, max_len)?;
+        if buffer.len() < size {
+            Err(TamponError::DeserializeSizeBufferIncomplete)
+        } else {
+            Ok(size)
+        }
+    }
+
+    fn within_limits(&self, max_len : usize) -> bool {
+        length_within_limits(self.len(), max_len)
+    }
+
+    fn sample(len : usize) -> Self {
+        (0..len).map(|i| (b'a' + (i % 26) as u8) as char).collect()
+    }
+}
+
+impl<T : PayloadField> PayloadField for Vec<T> {
+    const SCHEMA_HASH : u64 = T::SCHEMA_HASH;
+
+    const NESTED : Option<&'static NestedSchema> = T::NESTED;
+
+    fn field_size(&self) -> usize {
+        FIELD_LENGTH_TYPE_SIZE + self.iter().map(T::field_size).sum::<usize>()
+    }
+
+    fn write_field(&self, buffer : &mut [u8]) -> usize {
+        write_elements(self, buffer)
+    }
+
+    fn read_field(buffer : &[u8]) -> (Self, usize) {
+        read_elements(buffer)
+    }
+
+    fn read_field_size(buffer : &[u8], max_len : usize) -> Result<usize, TamponError> {
+        read_elements_size::<T>(buffer, max_len)
+    }
+
+    fn within_limits(&self, max_len : usize) -> bool {
+        length_within_limits(self.len(), max_len) && self.iter().all(|element| element.within_limits(usize::MAX))
+    }
+
+    fn sample(len : usize) -> Self {
+        (0..len).map(T::sample).collect()
+    }
+}
+
+impl<T : PayloadField> PayloadField for Box<[T]> {
+    const SCHEMA_HASH : u64 = T::SCHEMA_HASH;
+
+    const NESTED : Option<&'static NestedSchema> = T::NESTED;
+
+    fn field_size(&self) -> usize {
+        FIELD_LENGTH_TYPE_SIZE + self.iter().map(T::field_size).sum::<usize>()
+    }
+
+    fn write_field(&self, buffer : &mut [u8]) -> usize {
+        write_elements(self, buffer)
+    }
+
+    fn read_field(buffer : &[u8]) -> (Self, usize) {
+        let (elements, size) = read_elements(buffer);
+        (elements.into_boxed_slice(), size)
+    }
+
+    fn read_field_size(buffer : &[u8], max_len : usize) -> Result<usize, TamponError> {
+        read_elements_size::<T>(buffer, max_len)
+    }
+
+    fn within_limits(&self, max_len : usize) -> bool {
+        length_within_limits(self.len(), max_len) && self.iter().all(|element| element.within_limits(usize::MAX))
+    }
+
+    fn sample(len : usize) -> Self {
+        Vec::sample(len).into_boxed_slice()
+    }
+}
+
+impl<T : PayloadField, const N : usize> PayloadField for [T; N] {
+    const SCHEMA_HASH : u64 = T::SCHEMA_HASH;
+
+    const NESTED : Option<&'static NestedSchema> = T::NESTED;
+
+    const FIXED_SIZE : Option<usize> = match T::FIXED_SIZE {
+        Some(size) => Some(size * N),
+        None => None,
+    };
+
+    fn field_size(&self) -> usize {
+        self.iter().map(T::field_size).sum()
+    }
+
+    fn write_field(&self, buffer : &mut [u8]) -> usize {
+        let mut size = 0;
+        for element in self {
+            size += element.write_field(&mut buffer[size..]);
+        }
+        size
+    }
+
+    fn read_field(buffer : &[u8]) -> (Self, usize) {
+        let mut size = 0;
+        let elements = std::array::from_fn(|_| {
+            let (element, element_size) = T::read_field(&buffer[size..]);
+            size += element_size;
+            element
+        });
+        (elements, size)
+    }
+
+    /// Fixed arrays have no length, so `max_len` is ignored.
+    fn read_field_size(buffer : &[u8], _max_len : usize) -> Result<usize, TamponError> {
+        if let Some(size) = Self::FIXED_SIZE {
+            return if buffer.len() < size { Err(TamponError::DeserializeSizeBufferIncomplete) } else { Ok(size) };
+        }
+
+        let mut size = 0;
+        for _ in 0..N {
+            size += T::read_field_size(&buffer[size..], usize::MAX)?;
+        }
+        Ok(size)
+    }
+
+    fn within_limits(&self, _max_len : usize) -> bool {
+        self.iter().all(|element| element.within_limits(usize::MAX))
+    }
+
+    fn sample(_len : usize) -> Self {
+        std::array::from_fn(T::sample)
+    }
+}
+
+impl<T : PayloadField> PayloadField for Option<T> {
+    const SCHEMA_HASH : u64 = T::SCHEMA_HASH;
+
+    const NESTED : Option<&'static NestedSchema> = T::NESTED;
+
+    const OPTIONAL : bool = true;
+
+    fn field_size(&self) -> usize {
+        size_of::<u8>() + self.bitmap_field_size()
+    }
+
+    fn write_field(&self, buffer : &mut [u8]) -> usize {
+        let (present, size) = self.write_bitmap_field(&mut buffer[size_of::<u8>()..]);
+        present.write_field(buffer) + size
+    }
+
+    fn read_field(buffer : &[u8]) -> (Self, usize) {
+        let (present, size) = bool::read_field(buffer);
+        let (field, field_size) = Self::read_bitmap_field(&buffer[size..], present);
+        (field, size + field_size)
+    }
+
+    fn read_field_size(buffer : &[u8], max_len : usize) -> Result<usize, TamponError> {
+        let size = bool::read_field_size(buffer, max_len)?;
+        Ok(size + Self::read_bitmap_field_size(&buffer[size..], bool::read_field(buffer).0, max_len)?)
+    }
+
+    fn within_limits(&self, max_len : usize) -> bool {
+        self.as_ref().is_none_or(|field| field.within_limits(max_len))
+    }
+
+    fn bitmap_field_size(&self) -> usize {
+        self.as_ref().map_or(0, T::field_size)
+    }
+
+    fn write_bitmap_field(&self, buffer : &mut [u8]) -> (bool, usize) {
+        match self {
+            Some(field) => (true, field.write_field(buffer)),
+            None => (false, 0),
+        }
+    }
+
+    fn read_bitmap_field(buffer : &[u8], present : bool) -> (Self, usize) {
+        if present {
+            let (field, size) = T::read_field(buffer);
+            (Some(field), size)
+        } else {
+            (None, 0)
+        }
+    }
+
+    fn read_bitmap_field_size(buffer : &[u8], present : bool, max_len : usize) -> Result<usize, TamponError> {
+        if present {
+            T::read_field_size(buffer, max_len)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Always present, since absent fields are covered by default values.
+    fn sample(len : usize) -> Self {
+        Some(T::sample(len))
+    }
+}
+
+
+/// This module test [PayloadField] implementations.
+/// 
+/// # Verification(s)
+/// V1 : [PayloadField::write_field] then [PayloadField::read_field] give back the original field with same size.
+/// V2 : [PayloadField::read_field_size] returns [`TamponError::DeserializeSizeBufferIncomplete`] for every truncation.
+/// V3 : [PayloadField::read_field_size] returns [`TamponError::DeserializeSizeGreaterThanMax`] when length exceed max.
+/// V4 : [PayloadField::within_limits] checks max length and length prefix.
+/// V5 : [String] invalid UTF-8 is replaced instead of failing.
+/// V6 : [Option] fields of payload cost only a bit of the presence bitmap when absent.
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use tampon::{Tampon, TamponError};
+
+    use crate::net::client::test_payloads::TestClientPayload;
+
+    use super::PayloadField;
+
+    fn round_trip<T : PayloadField + PartialEq + Debug>(field : T) {
+        let mut buffer = vec![0u8; field.field_size()];
+        assert_eq!(field.write_field(&mut buffer), field.field_size());
+        assert_eq!(T::read_field_size(&buffer, usize::MAX), Ok(field.field_size()));
+        assert_eq!(T::read_field(&buffer), (field, buffer.len()));
+
+        for cut in 0..buffer.len() {
+            assert_eq!(T::read_field_size(&buffer[..cut], usize::MAX), Err(TamponError::DeserializeSizeBufferIncomplete));
+        }
+    }
+
+    #[test]
+    fn v1_v2_round_trip() {
+        // V1 : [PayloadField::write_field] then [PayloadField::read_field] give back the original field with same size.
+        // V2 : [PayloadField::read_field_size] returns [`TamponError::DeserializeSizeBufferIncomplete`] for every truncation.
+        round_trip(u8::MAX);
+        round_trip(i16::MIN);
+        round_trip(u128::MAX / 3);
+        round_trip(-1.5f64);
+        round_trip(true);
+        round_trip(String::from("Héllo ethos"));
+        round_trip(String::new());
+        round_trip(vec![1u32, 2, 3]);
+        round_trip(vec![String::from("a"), String::from("bc")]);
+        round_trip(vec![vec![1u8], vec![], vec![2, 3]]);
+        round_trip(Box::<[u8]>::from([4u8, 5, 6].as_slice()));
+        round_trip([7u16, 8, 9, 10]);
+        round_trip([String::from("x"), String::from("yz")]);
+        round_trip(Some(5u32));
+        round_trip(None::<String>);
+        round_trip(vec![Some(1u8), None, Some(2)]);
+    }
+
+    #[test]
+    fn v3_read_greater_than_max() {
+        // V3 : [PayloadField::read_field_size] returns [`TamponError::DeserializeSizeGreaterThanMax`] when length exceed max.
+        let mut buffer = vec![0u8; 16];
+        String::from("12345").write_field(&mut buffer);
+        assert_eq!(String::read_field_size(&buffer, 5), Ok(7));
+        assert_eq!(String::read_field_size(&buffer, 4), Err(TamponError::DeserializeSizeGreaterThanMax));
+
+        // Length is checked before content is available
+        assert_eq!(Vec::<u64>::read_field_size(&u16::MAX.to_le_bytes(), 10), Err(TamponError::DeserializeSizeGreaterThanMax));
+        assert_eq!(Box::<[u8]>::read_field_size(&11u16.to_le_bytes(), 10), Err(TamponError::DeserializeSizeGreaterThanMax));
+    }
+
+    #[test]
+    fn v4_within_limits() {
+        // V4 : [PayloadField::within_limits] checks max length and length prefix.
+        assert!(String::sample(10).within_limits(10));
+        assert!(!String::sample(11).within_limits(10));
+        assert!(!Vec::<u8>::sample(u16::MAX as usize + 1).within_limits(usize::MAX));
+        assert!(!vec![Vec::<u8>::sample(u16::MAX as usize + 1)].within_limits(usize::MAX));
+        assert!(42u32.within_limits(0));
+    }
+
+    #[test]
+    fn v5_invalid_utf8() {
+        // V5 : [String] invalid UTF-8 is replaced instead of failing.
+        let buffer = [2u8, 0, 0xFF, b'a'];
+        assert_eq!(String::read_field_size(&buffer, usize::MAX), Ok(4));
+        assert_eq!(String::read_field(&buffer), (String::from("\u{FFFD}a"), 4));
+    }
+
+    #[test]
+    fn v6_optional_bitmap() {
+        // V6 : [Option] fields of payload cost only a bit of the presence bitmap when absent.
+        let absent = TestClientPayload::Optional { o1: None, o2: None, o3: None, o4: None, o5: None, required: 7, o6: None, o7: None, o8: None, o9: None };
+
+        // Discriminant, 2 bytes of bitmap for 9 optional fields and required field
+        assert_eq!(absent.bytes_size(), 2 + 2 + 2);
+
+        let partial = TestClientPayload::Optional { 
+            o1: None, o2: Some(2), o3: None, o4: None, o5: None, required: 7, o6: None, o7: Some(vec![1, 2]), o8: None, o9: Some(0.5) 
+        };
+        assert_eq!(partial.bytes_size(), 2 + 2 + 2 + 2 + (2 + 4) + 4);
+
+        // Bits follow the order of optional fields
+        let mut buffer = vec![0u8; partial.bytes_size()];
+        partial.serialize(&mut buffer);
+        assert_eq!(buffer[2..4], [0b0100_0010, 0b0000_0001]);
+
+        for payload in [absent, partial] {
+            let mut buffer = vec![0u8; payload.bytes_size()];
+            assert_eq!(payload.serialize(&mut buffer), buffer.len());
+            assert_eq!(TestClientPayload::deserialize_size(&buffer, 0), Ok(buffer.len()));
+            assert_eq!(TestClientPayload::deserialize(&buffer), (payload, buffer.len()));
+        }
+    }
+}
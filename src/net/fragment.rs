@@ -0,0 +1,487 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::collections::HashMap;
+
+use tampon::Tampon;
+
+use crate::net::{Clock, Error, ErrorKind, ServerMessage, ServerPayload, SERVER_MSG_BUFFER_SIZE};
+
+/// Maximum bytes of payload carried by a single [`ServerPayload::Fragment`].
+/// 
+/// Leaves room for the fragment fields within a [`ServerMessage`].
+pub const FRAGMENT_MAX_DATA_SIZE : usize = 65_000;
+
+/// Split [`ServerPayload`] too large for a single [`ServerMessage`] into numbered [`ServerPayload::Fragment`].
+/// 
+/// Fragments are reassembled by a [`Reassembler`] on client.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ Fragmenter, Reassembler, ManualClock, ServerPayload };
+/// 
+/// let mut fragmenter = Fragmenter::new(16);
+/// let mut reassembler = Reassembler::new(ManualClock::new(0), 1000, 1024, 8);
+/// 
+/// let payload = ServerPayload::Reply { id: 1, data: vec![7u8; 40].into_boxed_slice() };
+/// let messages = fragmenter.split(42, payload.clone()).unwrap();
+/// assert_eq!(messages.len(), 3);
+/// 
+/// let mut reassembled = None;
+/// for message in messages {
+///     reassembled = reassembler.push(message).unwrap();
+/// }
+/// assert_eq!(reassembled.unwrap().payload, payload);
+/// ```
+pub struct Fragmenter {
+    /// Id of the next fragmented message.
+    next_id : u32,
+
+    /// Maximum bytes of payload per fragment.
+    fragment_size : usize,
+}
+
+impl Fragmenter {
+    /// Create a new [`Fragmenter`] splitting payloads larger than `fragment_size` bytes.
+    /// 
+    /// `fragment_size` is clamped between 1 and [`FRAGMENT_MAX_DATA_SIZE`].
+    pub fn new(fragment_size : usize) -> Fragmenter {
+        Fragmenter { next_id: 0, fragment_size: fragment_size.clamp(1, FRAGMENT_MAX_DATA_SIZE) }
+    }
+
+    /// Maximum bytes of payload per fragment.
+    pub fn fragment_size(&self) -> usize {
+        self.fragment_size
+    }
+
+    /// Split a payload into fragments if it is larger than fragment size.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Messages to send in order, a single message with the payload itself if not fragmented.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if payload needs more than [`u16::MAX`] fragments or isn't within limits.
+    pub fn split(&mut self, timestamp : u64, payload : ServerPayload) -> Result<Vec<ServerMessage>, Error> {
+        if !payload.within_limits() {
+            return Err(ErrorKind::MessageSizeGreaterThanLimit.into());
+        }
+
+        if payload.bytes_size() <= self.fragment_size {
+            return Ok(vec![ServerMessage::new(timestamp, payload)]);
+        }
+
+        let mut bytes = vec![0u8; payload.bytes_size()];
+        payload.serialize(&mut bytes);
+
+        let count = bytes.len().div_ceil(self.fragment_size);
+        if count > u16::MAX as usize {
+            return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, u16::MAX as usize * self.fragment_size, bytes.len()));
+        }
+
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+
+        Ok(bytes.chunks(self.fragment_size).enumerate().map(|(index, data)| {
+            ServerMessage::new(timestamp, ServerPayload::Fragment { id, index: index as u16, count: count as u16, data: data.into() })
+        }).collect())
+    }
+}
+
+impl Default for Fragmenter {
+    fn default() -> Self {
+        Self::new(FRAGMENT_MAX_DATA_SIZE)
+    }
+}
+
+/// Fragmented message partially received.
+struct Partial {
+    /// Timestamp of the first fragment received.
+    timestamp : u64,
+
+    /// Time the first fragment was received.
+    started_at : u64,
+
+    /// Fragments received, by index.
+    fragments : Vec<Option<Box<[u8]>>>,
+
+    /// Count of fragments received.
+    received : usize,
+
+    /// Bytes received and slots of fragments.
+    size : usize,
+}
+
+/// Reassemble [`ServerPayload::Fragment`] produced by a [`Fragmenter`].
+/// 
+/// Bytes of partial messages, slots of their fragments included, are bounded, partial messages are dropped after a timeout and
+/// new fragmented messages are refused when too many are pending, so fragment floods can't exhaust memory.
+/// 
+/// # Note(s)
+/// * Reassembled [`ServerMessage::size`] saturates at [`u16::MAX`] since it can't be represented.
+/// * A duplicated fragment arriving after its message was reassembled starts a new partial message that times out.
+pub struct Reassembler<C : Clock> {
+    clock : C,
+
+    /// Milliseconds before a partial message is dropped.
+    timeout : u64,
+
+    /// Maximum bytes of all partial messages.
+    max_size : usize,
+
+    /// Maximum count of partial messages.
+    max_pending : usize,
+
+    /// Partial messages by id.
+    partials : HashMap<u32, Partial>,
+
+    /// Bytes of all partial messages.
+    buffered : usize,
+}
+
+impl<C : Clock> Reassembler<C> {
+    /// Create a new [`Reassembler`].
+    /// 
+    /// # Argument(s)
+    /// * `clock` - [`Clock`] used for timeouts.
+    /// * `timeout` - Milliseconds before a partial message is dropped.
+    /// * `max_size` - Maximum bytes of all partial messages, usually [`SERVER_MSG_BUFFER_SIZE`].
+    /// * `max_pending` - Maximum count of partial messages.
+    pub fn new(clock : C, timeout : u64, max_size : usize, max_pending : usize) -> Reassembler<C> {
+        Reassembler { clock, timeout, max_size, max_pending, partials: HashMap::new(), buffered: 0 }
+    }
+
+    /// Create a new [`Reassembler`] bounded by [`SERVER_MSG_BUFFER_SIZE`] with 4 partial messages.
+    pub fn with_clock(clock : C, timeout : u64) -> Reassembler<C> {
+        Self::new(clock, timeout, SERVER_MSG_BUFFER_SIZE, 4)
+    }
+
+    /// Count of partial messages.
+    pub fn pending(&self) -> usize {
+        self.partials.len()
+    }
+
+    /// Bytes of all partial messages, slots of their fragments included.
+    pub fn buffered(&self) -> usize {
+        self.buffered
+    }
+
+    /// Drop partial messages older than timeout.
+    /// 
+    /// # Returns
+    /// Count of partial messages dropped.
+    pub fn expire(&mut self) -> usize {
+        let now = self.clock.now();
+        let before = self.partials.len();
+        let mut buffered = self.buffered;
+
+        self.partials.retain(|_, partial| {
+            let alive = now.saturating_sub(partial.started_at) < self.timeout;
+            if !alive {
+                buffered -= partial.size;
+            }
+            alive
+        });
+
+        self.buffered = buffered;
+        before - self.partials.len()
+    }
+
+    /// Remove a partial message.
+    fn drop_partial(&mut self, id : u32) {
+        if let Some(partial) = self.partials.remove(&id) {
+            self.buffered -= partial.size;
+        }
+    }
+
+    /// Push a message received.
+    /// 
+    /// Stale partial messages are dropped first. Duplicated fragments are ignored.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`Some`] with the message if it wasn't a fragment or if its last fragment was received, [`None`] otherwise.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if fragment is inconsistent with previous fragments or reassembled payload is malformed. Partial message is dropped.
+    ///     2. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if partial messages would exceed maximum size, 
+    ///        including the slots of fragments a new partial message needs. Partial message is dropped.
+    ///     3. [`ErrorKind::TooManyFragments`](crate::net::ErrorKind::TooManyFragments) if fragment starts a new partial message when maximum count is reached.
+    pub fn push(&mut self, message : ServerMessage) -> Result<Option<ServerMessage>, Error> {
+        let ServerPayload::Fragment { id, index, count, data } = message.payload else {
+            return Ok(Some(message));
+        };
+
+        self.expire();
+
+        if index >= count {
+            self.drop_partial(id);
+            return Err(ErrorKind::InvalidMessage.into());
+        }
+
+        if !self.partials.contains_key(&id) {
+            if self.partials.len() >= self.max_pending {
+                return Err(ErrorKind::TooManyFragments.into());
+            }
+
+            // Slots are counted before allocation so that a forged count can't exceed maximum size
+            let slots = count as usize * size_of::<Option<Box<[u8]>>>();
+            if self.buffered + slots > self.max_size {
+                return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, self.max_size, self.buffered + slots));
+            }
+
+            self.buffered += slots;
+            self.partials.insert(id, Partial { 
+                timestamp: message.timestamp, started_at: self.clock.now(), 
+                fragments: vec![None; count as usize], received: 0, size: slots 
+            });
+        }
+
+        let partial = self.partials.get_mut(&id).unwrap();
+        if partial.fragments.len() != count as usize {
+            self.drop_partial(id);
+            return Err(ErrorKind::InvalidMessage.into());
+        }
+
+        if partial.fragments[index as usize].is_some() {
+            return Ok(None);
+        }
+
+        if self.buffered + data.len() > self.max_size {
+            self.drop_partial(id);
+            return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, self.max_size, self.buffered + data.len()));
+        }
+
+        self.buffered += data.len();
+        partial.size += data.len();
+        partial.received += 1;
+        partial.fragments[index as usize] = Some(data);
+
+        if partial.received < partial.fragments.len() {
+            return Ok(None);
+        }
+
+        // Every fragment received
+        let partial = self.partials.remove(&id).unwrap();
+        self.buffered -= partial.size;
+        let bytes = partial.fragments.into_iter().flatten().flat_map(|data| data.into_vec()).collect::<Vec<_>>();
+
+        if ServerPayload::deserialize_size(&bytes, 0) != Ok(bytes.len()) {
+            return Err(ErrorKind::InvalidMessage.into());
+        }
+
+        match ServerPayload::deserialize(&bytes).0 {
+            ServerPayload::Fragment { .. } | ServerPayload::Invalid => Err(ErrorKind::InvalidMessage.into()),
+            payload => Ok(Some(ServerMessage::new(partial.timestamp, payload))),
+        }
+    }
+}
+
+
+/// This module test [Fragmenter] and [Reassembler].
+/// 
+/// # Verification(s)
+/// V1 : [Fragmenter::split] doesn't fragment payloads within fragment size and [Reassembler::push] passes them through.
+/// V2 : [Reassembler::push] reassembles fragments received out of order.
+/// V3 : [Reassembler::push] ignores duplicated fragments.
+/// V4 : [Reassembler::expire] drops partial messages with missing fragments after timeout.
+/// V5 : [Reassembler::push] returns [`ErrorKind::TooManyFragments`](crate::net::ErrorKind::TooManyFragments) on fragment floods.
+/// V6 : [Reassembler::push] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when partial messages exceed maximum size.
+/// V7 : [Reassembler::push] returns [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) for inconsistent fragments.
+/// V8 : [Fragmenter::split] payload larger than 64 KiB is sent through messages within limits.
+/// V9 : [Reassembler::push] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) for a count of fragments whose slots exceed maximum size.
+#[cfg(test)]
+mod tests {
+    use tampon::Tampon;
+
+    use crate::net::{ErrorKind, Fragmenter, ManualClock, Message, Reassembler, ServerMessage, ServerPayload, FRAGMENT_MAX_DATA_SIZE};
+
+    const TIMEOUT : u64 = 1000;
+
+    /// Bytes of the slot of a fragment.
+    const SLOT : usize = size_of::<Option<Box<[u8]>>>();
+
+    /// Payload carrying len bytes.
+    fn large(len : usize) -> ServerPayload {
+        ServerPayload::Reply { id: 0, data: (0..len).map(|i| (i / 1000) as u8).collect() }
+    }
+
+    fn reassembler(clock : &ManualClock) -> Reassembler<ManualClock> {
+        Reassembler::new(clock.clone(), TIMEOUT, 16 * 1024, 2)
+    }
+
+    fn fragment(id : u32, index : u16, count : u16, len : usize) -> ServerMessage {
+        ServerMessage::new(0, ServerPayload::Fragment { id, index, count, data: vec![0u8; len].into_boxed_slice() })
+    }
+
+    #[test]
+    fn v1_not_fragmented() {
+        // V1 : [Fragmenter::split] doesn't fragment payloads within fragment size and [Reassembler::push] passes them through.
+        let clock = ManualClock::new(0);
+        let payload = ServerPayload::Test { p16: 1, p32: 2 };
+        let mut messages = Fragmenter::default().split(42, payload.clone()).unwrap();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(reassembler(&clock).push(messages.remove(0)), Ok(Some(ServerMessage::new(42, payload))));
+    }
+
+    #[test]
+    fn v2_out_of_order() {
+        // V2 : [Reassembler::push] reassembles fragments received out of order.
+        let clock = ManualClock::new(0);
+        let mut reassembler = reassembler(&clock);
+        let payload = large(5000);
+        let mut messages = Fragmenter::new(1024).split(42, payload.clone()).unwrap();
+        assert_eq!(messages.len(), 5);
+
+        messages.reverse();
+        messages.swap(1, 3);
+        let last = messages.pop().unwrap();
+        for message in messages {
+            assert_eq!(reassembler.push(message), Ok(None));
+        }
+        assert_eq!(reassembler.push(last), Ok(Some(ServerMessage::new(42, payload))));
+        assert_eq!(reassembler.pending(), 0);
+        assert_eq!(reassembler.buffered(), 0);
+    }
+
+    #[test]
+    fn v3_duplicated() {
+        // V3 : [Reassembler::push] ignores duplicated fragments.
+        let clock = ManualClock::new(0);
+        let mut reassembler = reassembler(&clock);
+        let payload = large(3000);
+        let messages = Fragmenter::new(1024).split(42, payload.clone()).unwrap();
+
+        let duplicate = |message : &ServerMessage| ServerMessage::new(message.timestamp, message.payload.clone());
+        assert_eq!(reassembler.push(duplicate(&messages[0])), Ok(None));
+        assert_eq!(reassembler.push(duplicate(&messages[0])), Ok(None));
+        assert_eq!(reassembler.push(duplicate(&messages[1])), Ok(None));
+        assert_eq!(reassembler.push(duplicate(&messages[0])), Ok(None));
+        assert_eq!(reassembler.buffered(), 2048 + 3 * SLOT);
+        assert_eq!(reassembler.push(duplicate(&messages[2])), Ok(Some(ServerMessage::new(42, payload))));
+    }
+
+    #[test]
+    fn v4_missing_timeout() {
+        // V4 : [Reassembler::expire] drops partial messages with missing fragments after timeout.
+        let clock = ManualClock::new(0);
+        let mut reassembler = reassembler(&clock);
+        let mut messages = Fragmenter::new(1024).split(42, large(3000)).unwrap();
+        messages.remove(1);
+
+        for message in messages {
+            assert_eq!(reassembler.push(message), Ok(None));
+        }
+
+        clock.advance(TIMEOUT - 1);
+        assert_eq!(reassembler.expire(), 0);
+        assert_eq!(reassembler.pending(), 1);
+
+        clock.advance(1);
+        assert_eq!(reassembler.expire(), 1);
+        assert_eq!(reassembler.pending(), 0);
+        assert_eq!(reassembler.buffered(), 0);
+    }
+
+    #[test]
+    fn v5_flood() {
+        // V5 : [Reassembler::push] returns [`ErrorKind::TooManyFragments`] on fragment floods.
+        let clock = ManualClock::new(0);
+        let mut reassembler = reassembler(&clock);
+
+        assert_eq!(reassembler.push(fragment(1, 0, 2, 10)), Ok(None));
+        assert_eq!(reassembler.push(fragment(2, 0, 2, 10)), Ok(None));
+        assert_eq!(reassembler.push(fragment(3, 0, 2, 10)), Err(ErrorKind::TooManyFragments.into()));
+
+        // Room is made after timeout
+        clock.advance(TIMEOUT);
+        assert_eq!(reassembler.push(fragment(3, 0, 2, 10)), Ok(None));
+        assert_eq!(reassembler.pending(), 1);
+    }
+
+    #[test]
+    fn v6_max_size() {
+        // V6 : [Reassembler::push] returns [`ErrorKind::MessageSizeGreaterThanLimit`] when partial messages exceed maximum size.
+        let clock = ManualClock::new(0);
+        let mut reassembler = reassembler(&clock);
+
+        assert_eq!(reassembler.push(fragment(1, 0, 3, 8 * 1024 - 3 * SLOT)), Ok(None));
+        assert_eq!(reassembler.push(fragment(2, 0, 3, 8 * 1024 - 3 * SLOT)), Ok(None));
+        assert_eq!(reassembler.push(fragment(2, 1, 3, 1)), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+        assert_eq!(reassembler.pending(), 1);
+        assert_eq!(reassembler.buffered(), 8 * 1024);
+    }
+
+    #[test]
+    fn v7_inconsistent() {
+        // V7 : [Reassembler::push] returns [`ErrorKind::InvalidMessage`] for inconsistent fragments.
+        let clock = ManualClock::new(0);
+        let mut reassembler = reassembler(&clock);
+
+        assert_eq!(reassembler.push(fragment(1, 2, 2, 10)), Err(ErrorKind::InvalidMessage.into()));
+        assert_eq!(reassembler.push(fragment(1, 0, 0, 10)), Err(ErrorKind::InvalidMessage.into()));
+
+        assert_eq!(reassembler.push(fragment(1, 0, 2, 10)), Ok(None));
+        assert_eq!(reassembler.push(fragment(1, 1, 3, 10)), Err(ErrorKind::InvalidMessage.into()));
+        assert_eq!(reassembler.pending(), 0);
+        assert_eq!(reassembler.buffered(), 0);
+    }
+
+    #[test]
+    fn v8_larger_than_64k() {
+        // V8 : [Fragmenter::split] payload larger than 64 KiB is sent through messages within limits.
+        let clock = ManualClock::new(0);
+        let mut reassembler = Reassembler::with_clock(clock.clone(), TIMEOUT);
+        let payload = large(u16::MAX as usize);
+        assert!(payload.bytes_size() > 64 * 1024);
+        let messages = Fragmenter::default().split(42, payload.clone()).unwrap();
+        assert_eq!(messages.len(), payload.bytes_size().div_ceil(FRAGMENT_MAX_DATA_SIZE));
+
+        let mut reassembled = None;
+        for message in messages {
+            assert!(message.size() <= ServerMessage::MAX_SIZE);
+
+            let mut buffer = vec![0u8; 2 + message.size()];
+            message.pack_bytes(&mut buffer).unwrap();
+            reassembled = reassembler.push(ServerMessage::from_bytes(&buffer[2..]).unwrap()).unwrap();
+        }
+        assert_eq!(reassembled.unwrap().payload, payload);
+    }
+
+    #[test]
+    fn v9_count_greater_than_max_size() {
+        // V9 : [Reassembler::push] returns [`ErrorKind::MessageSizeGreaterThanLimit`] for a count of fragments whose slots exceed maximum size.
+        let clock = ManualClock::new(0);
+        let mut reassembler = reassembler(&clock);
+
+        assert_eq!(reassembler.push(fragment(1, 0, u16::MAX, 10)), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+        assert_eq!(reassembler.pending(), 0);
+        assert_eq!(reassembler.buffered(), 0);
+
+        // Count fitting with its data is accepted
+        let count = (16 * 1024 / SLOT) as u16 - 1;
+        assert_eq!(reassembler.push(fragment(1, 0, count, 10)), Ok(None));
+        assert_eq!(reassembler.buffered(), count as usize * SLOT + 10);
+    }
+}
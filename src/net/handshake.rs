@@ -0,0 +1,357 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::io::{Read, Write};
+
+use crate::net::{ClientMessage, ClientPayload, Error, ErrorKind, Message, ServerMessage, ServerPayload, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Version of the protocol. Increase when messages framing or handshake change.
+pub const PROTOCOL_VERSION : u16 = 1;
+
+/// Hash of both [`ClientPayload`] and [`ServerPayload`] definitions.
+pub const SCHEMA_HASH : u64 = ClientPayload::SCHEMA_HASH ^ ServerPayload::SCHEMA_HASH.rotate_left(32);
+
+/// FNV-1a offset basis.
+const FNV_OFFSET : u64 = 0xcbf29ce484222325;
+
+/// FNV-1a prime.
+const FNV_PRIME : u64 = 0x100000001b3;
+
+/// FNV-1a hash of a schema, ignoring whitespaces.
+/// 
+/// Whitespaces are ignored since `stringify!` formatting may vary between compilers.
+#[doc(hidden)]
+pub const fn schema_hash(schema : &str) -> u64 {
+    let bytes = schema.as_bytes();
+    let mut hash : u64 = FNV_OFFSET;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_whitespace() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        i += 1;
+    }
+
+    hash
+}
+
+/// Continue a schema hash with the hash of a nested type.
+#[doc(hidden)]
+pub const fn combine_hash(hash : u64, nested : u64) -> u64 {
+    let bytes = nested.to_le_bytes();
+    let mut hash = hash;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+
+    hash
+}
+
+/// Handshake exchanged right after connecting, making sure client and server were built
+/// with the same protocol version and payloads.
+/// 
+/// Client sends [`ClientPayload::Hello`] and server answers [`ServerPayload::Accept`] or [`ServerPayload::Reject`].
+/// 
+/// # Example(s)
+/// ```no_run
+/// use std::net::TcpStream;
+/// use ethos_core::net::{ ClientConnection, Handshake, TCP_PORT };
+/// 
+/// let mut stream = TcpStream::connect(("127.0.0.1", TCP_PORT)).unwrap();
+/// Handshake::default().client(&mut stream).unwrap();
+/// let connection = ClientConnection::from_stream(stream);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handshake {
+    /// Protocol version.
+    version : u16,
+
+    /// Payloads schema hash.
+    schema : u64,
+}
+
+impl Handshake {
+    /// Create a new [`Handshake`] with given version and schema hash.
+    /// 
+    /// Use [`Handshake::default`] for [`PROTOCOL_VERSION`] and [`SCHEMA_HASH`].
+    pub const fn new(version : u16, schema : u64) -> Handshake {
+        Handshake { version, schema }
+    }
+
+    /// Protocol version.
+    pub const fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Payloads schema hash.
+    pub const fn schema(&self) -> u64 {
+        self.schema
+    }
+
+    /// Message sent by client to start the handshake.
+    pub fn hello(&self) -> ClientMessage {
+        ClientMessage::new(ClientPayload::Hello { version: self.version, schema: self.schema })
+    }
+
+    /// Server answer to the first message of client.
+    /// 
+    /// # Returns
+    /// Answer to send to client and [`Result`] which is:
+    /// - [`Ok`]: Client was accepted.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::VersionMismatch`](crate::net::ErrorKind::VersionMismatch) if client version or schema differ.
+    ///     2. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ClientPayload::Hello`].
+    pub fn respond(&self, message : &ClientMessage, timestamp : u64) -> (ServerMessage, Result<(), Error>) {
+        let reject = ServerMessage::new(timestamp, ServerPayload::Reject { version: self.version, schema: self.schema });
+
+        match message.payload {
+            ClientPayload::Hello { version, schema } if version == self.version && schema == self.schema => 
+                (ServerMessage::new(timestamp, ServerPayload::Accept), Ok(())),
+            ClientPayload::Hello { .. } => (reject, Err(ErrorKind::VersionMismatch.into())),
+            _ => (reject, Err(ErrorKind::InvalidMessage.into())),
+        }
+    }
+
+    /// Client check of the server answer.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Server accepted client.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::VersionMismatch`](crate::net::ErrorKind::VersionMismatch) if server rejected client.
+    ///     2. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message is neither [`ServerPayload::Accept`] nor [`ServerPayload::Reject`].
+    pub fn conclude(&self, message : &ServerMessage) -> Result<(), Error> {
+        match message.payload {
+            ServerPayload::Accept => Ok(()),
+            ServerPayload::Reject { .. } => Err(ErrorKind::VersionMismatch.into()),
+            _ => Err(ErrorKind::InvalidMessage.into()),
+        }
+    }
+
+    /// Perform the client side of the handshake on a blocking stream.
+    /// 
+    /// Only the answer of server is read from stream, so it can be wrapped in a 
+    /// [`ClientConnection`](crate::net::ClientConnection) afterward.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Server accepted client.
+    /// - [`Err`]: Any error of [`Handshake::conclude`], or reading and writing errors.
+    pub fn client<T : Read + Write>(&self, stream : &mut T) -> Result<(), Error> {
+        write_message(stream, &self.hello())?;
+        self.conclude(&read_message(stream)?)
+    }
+
+    /// Perform the server side of the handshake on a blocking stream.
+    /// 
+    /// Only the first message of client is read from stream, so it can be wrapped in a 
+    /// [`ServerConnection`](crate::net::ServerConnection) afterward. The answer is sent even if client is rejected.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Client was accepted.
+    /// - [`Err`]: Any error of [`Handshake::respond`], or reading and writing errors.
+    pub fn server<T : Read + Write>(&self, stream : &mut T, timestamp : u64) -> Result<(), Error> {
+        let (answer, result) = self.respond(&read_message(stream)?, timestamp);
+        write_message(stream, &answer)?;
+        result
+    }
+}
+
+impl Default for Handshake {
+    fn default() -> Self {
+        Self::new(PROTOCOL_VERSION, SCHEMA_HASH)
+    }
+}
+
+/// Write a single message to stream.
+pub(crate) fn write_message<M : Message, T : Write>(stream : &mut T, message : &M) -> Result<(), Error> {
+    let mut buffer = vec![0u8; MESSAGE_SIZE_TYPE_SIZE + message.size()];
+    message.pack_bytes(&mut buffer)?;
+    stream.write_all(&buffer)?;
+    Ok(stream.flush()?)
+}
+
+/// Read exactly a single message from stream.
+pub(crate) fn read_message<M : Message, T : Read>(stream : &mut T) -> Result<M, Error> {
+    let mut header = [0u8; MESSAGE_SIZE_TYPE_SIZE];
+    stream.read_exact(&mut header)?;
+
+    let size = M::size_from_bytes(&header) as usize;
+    if size > M::MAX_SIZE {
+        return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, M::MAX_SIZE, size));
+    }
+
+    let mut content = vec![0u8; size];
+    stream.read_exact(&mut content)?;
+    M::from_bytes(&content)
+}
+
+
+/// This module test [Handshake] with in-memory buffers.
+/// 
+/// # Verification(s)
+/// V1 : [schema_hash] ignores whitespaces and differs for different schemas.
+/// V2 : [Handshake::respond] and [Handshake::conclude] accept same version and schema.
+/// V3 : [Handshake::respond] and [Handshake::conclude] reject different version or schema with [`ErrorKind::VersionMismatch`](crate::net::ErrorKind::VersionMismatch).
+/// V4 : [Handshake::respond] and [Handshake::conclude] return [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) for unexpected messages.
+/// V5 : [Handshake::client] writes hello and reads only the answer of server.
+/// V6 : [Handshake::server] reads only hello and writes the answer, even when rejecting.
+/// V7 : [Handshake::client] returns [`ErrorKind::ConnectionClosed`](crate::net::ErrorKind::ConnectionClosed) if stream ends before answer.
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read, Write};
+
+    use crate::net::{ClientMessage, ClientPayload, ErrorKind, Handshake, ServerMessage, ServerPayload, PROTOCOL_VERSION, SCHEMA_HASH};
+
+    use super::{read_message, schema_hash, write_message};
+
+    /// In-memory stream reading from input and writing to output.
+    struct Pipe {
+        input : Cursor<Vec<u8>>,
+        output : Vec<u8>,
+    }
+
+    impl Pipe {
+        fn new(input : Vec<u8>) -> Pipe {
+            Pipe { input: Cursor::new(input), output: Vec::new() }
+        }
+    }
+
+    impl Read for Pipe {
+        fn read(&mut self, buf : &mut [u8]) -> std::io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for Pipe {
+        fn write(&mut self, buf : &[u8]) -> std::io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn packed<M : crate::net::Message>(messages : &[M]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for message in messages {
+            write_message(&mut bytes, message).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn v1_schema_hash() {
+        // V1 : [schema_hash] ignores whitespaces and differs for different schemas.
+        assert_eq!(schema_hash("Test { p16 : u16 } = 1"), schema_hash("Test{p16:u16}=1"));
+        assert_ne!(schema_hash("Test { p16 : u16 } = 1"), schema_hash("Test { p16 : u16 } = 2"));
+        assert_ne!(schema_hash("Test { p16 : u16 } = 1"), schema_hash("Test { p16 : u32 } = 1"));
+        assert_ne!(ClientPayload::SCHEMA_HASH, ServerPayload::SCHEMA_HASH);
+        assert!(ClientPayload::SCHEMA.replace(' ', "").contains("Hello{version:u16,schema:u64}=65532"));
+    }
+
+    #[test]
+    fn v2_accept() {
+        // V2 : [Handshake::respond] and [Handshake::conclude] accept same version and schema.
+        let handshake = Handshake::default();
+        let (answer, result) = handshake.respond(&handshake.hello(), 42);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(answer, ServerMessage::new(42, ServerPayload::Accept));
+        assert_eq!(handshake.conclude(&answer), Ok(()));
+    }
+
+    #[test]
+    fn v3_version_mismatch() {
+        // V3 : [Handshake::respond] and [Handshake::conclude] reject different version or schema with [`ErrorKind::VersionMismatch`].
+        let server = Handshake::default();
+
+        for client in [Handshake::new(PROTOCOL_VERSION + 1, SCHEMA_HASH), Handshake::new(PROTOCOL_VERSION, SCHEMA_HASH ^ 1)] {
+            let (answer, result) = server.respond(&client.hello(), 42);
+
+            assert_eq!(result, Err(ErrorKind::VersionMismatch.into()));
+            assert_eq!(answer, ServerMessage::new(42, ServerPayload::Reject { version: PROTOCOL_VERSION, schema: SCHEMA_HASH }));
+            assert_eq!(client.conclude(&answer), Err(ErrorKind::VersionMismatch.into()));
+        }
+    }
+
+    #[test]
+    fn v4_unexpected_message() {
+        // V4 : [Handshake::respond] and [Handshake::conclude] return [`ErrorKind::InvalidMessage`] for unexpected messages.
+        let handshake = Handshake::default();
+
+        let (answer, result) = handshake.respond(&ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 }), 42);
+        assert_eq!(result, Err(ErrorKind::InvalidMessage.into()));
+        assert!(matches!(answer.payload, ServerPayload::Reject { .. }));
+
+        assert_eq!(handshake.conclude(&ServerMessage::new(42, ServerPayload::Test { p16: 1, p32: 2 })), Err(ErrorKind::InvalidMessage.into()));
+    }
+
+    #[test]
+    fn v5_client_stream() {
+        // V5 : [Handshake::client] writes hello and reads only the answer of server.
+        let handshake = Handshake::default();
+        let next = || ServerMessage::new(43, ServerPayload::Test { p16: 1, p32: 2 });
+        let mut pipe = Pipe::new(packed(&[ServerMessage::new(42, ServerPayload::Accept), next()]));
+
+        assert_eq!(handshake.client(&mut pipe), Ok(()));
+        assert_eq!(pipe.output, packed(&[handshake.hello()]));
+        assert_eq!(read_message::<ServerMessage, _>(&mut pipe), Ok(next()));
+
+        let mut pipe = Pipe::new(packed(&[ServerMessage::new(42, ServerPayload::Reject { version: 0, schema: 0 })]));
+        assert_eq!(handshake.client(&mut pipe), Err(ErrorKind::VersionMismatch.into()));
+    }
+
+    #[test]
+    fn v6_server_stream() {
+        // V6 : [Handshake::server] reads only hello and writes the answer, even when rejecting.
+        let handshake = Handshake::default();
+        let next = || ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 });
+        let mut pipe = Pipe::new(packed(&[handshake.hello(), next()]));
+
+        assert_eq!(handshake.server(&mut pipe, 42), Ok(()));
+        assert_eq!(pipe.output, packed(&[ServerMessage::new(42, ServerPayload::Accept)]));
+        assert_eq!(read_message::<ClientMessage, _>(&mut pipe), Ok(next()));
+
+        let mut pipe = Pipe::new(packed(&[Handshake::new(0, 0).hello()]));
+        assert_eq!(handshake.server(&mut pipe, 42), Err(ErrorKind::VersionMismatch.into()));
+        assert_eq!(pipe.output, packed(&[ServerMessage::new(42, ServerPayload::Reject { version: PROTOCOL_VERSION, schema: SCHEMA_HASH })]));
+    }
+
+    #[test]
+    fn v7_client_stream_closed() {
+        // V7 : [Handshake::client] returns [`ErrorKind::ConnectionClosed`] if stream ends before answer.
+        let mut pipe = Pipe::new(Vec::new());
+        assert_eq!(Handshake::default().client(&mut pipe), Err(ErrorKind::ConnectionClosed.into()));
+    }
+}
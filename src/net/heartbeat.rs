@@ -0,0 +1,410 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::{collections::VecDeque, marker::PhantomData};
+
+use crate::net::{ClientMessage, ClientPayload, Clock, Message, ServerMessage, ServerPayload};
+
+/// Default interval in milliseconds between pings.
+pub const HEARTBEAT_INTERVAL : u64 = 5_000;
+
+/// Default time in milliseconds without receiving anything before a peer is timed out.
+pub const HEARTBEAT_TIMEOUT : u64 = 15_000;
+
+/// Heartbeat used by client to ping server with [`ClientPayload::Ping`].
+pub type ClientHeartbeat<C> = Heartbeat<ClientMessage, ServerMessage, C>;
+
+/// Heartbeat used by server to ping a client with [`ServerPayload::Ping`].
+pub type ServerHeartbeat<C> = Heartbeat<ServerMessage, ClientMessage, C>;
+
+/// Message carrying ping and pong payloads.
+/// 
+/// Implemented by [`ClientMessage`] and [`ServerMessage`].
+pub trait KeepAlive : Message {
+    /// Ping message, with a timestamp for messages that have one.
+    fn ping(id : u32, timestamp : u64) -> Self;
+
+    /// Pong message answering a ping, with a timestamp for messages that have one.
+    fn pong(id : u32, timestamp : u64) -> Self;
+
+    /// Id of the ping carried, [`None`] if message isn't a ping.
+    fn as_ping(&self) -> Option<u32>;
+
+    /// Id of the ping answered, [`None`] if message isn't a pong.
+    fn as_pong(&self) -> Option<u32>;
+}
+
+impl KeepAlive for ClientMessage {
+    fn ping(id : u32, _timestamp : u64) -> Self {
+        ClientMessage::new(ClientPayload::Ping { id })
+    }
+
+    fn pong(id : u32, _timestamp : u64) -> Self {
+        ClientMessage::new(ClientPayload::Pong { id })
+    }
+
+    fn as_ping(&self) -> Option<u32> {
+        match self.payload {
+            ClientPayload::Ping { id } => Some(id),
+            _ => None,
+        }
+    }
+
+    fn as_pong(&self) -> Option<u32> {
+        match self.payload {
+            ClientPayload::Pong { id } => Some(id),
+            _ => None,
+        }
+    }
+}
+
+impl KeepAlive for ServerMessage {
+    fn ping(id : u32, timestamp : u64) -> Self {
+        ServerMessage::new(timestamp, ServerPayload::Ping { id })
+    }
+
+    fn pong(id : u32, timestamp : u64) -> Self {
+        ServerMessage::new(timestamp, ServerPayload::Pong { id })
+    }
+
+    fn as_ping(&self) -> Option<u32> {
+        match self.payload {
+            ServerPayload::Ping { id } => Some(id),
+            _ => None,
+        }
+    }
+
+    fn as_pong(&self) -> Option<u32> {
+        match self.payload {
+            ServerPayload::Pong { id } => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// Health of the remote peer, according to the last time something was received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    /// Something was received within the ping interval.
+    Alive,
+
+    /// Nothing was received for more than the ping interval, peer may be dead.
+    Silent,
+
+    /// Nothing was received for the timeout, connection should be closed.
+    TimedOut,
+}
+
+/// Keep-alive state machine of a single connection, detecting silently dead peers.
+/// 
+/// Use [`ClientHeartbeat`] or [`ServerHeartbeat`] according to the side of the connection.
+/// 
+/// Messages returned by [`poll`](Self::poll) and [`receive`](Self::receive) must be sent to the remote peer, and every
+/// message received must be given to [`receive`](Self::receive). Any message received proves the peer alive, pongs
+/// also measure round-trip time. Time is given by a [`Clock`], so a [`ManualClock`](crate::net::ManualClock) tests it without sleeping.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ ClientHeartbeat, Health, ManualClock, ServerHeartbeat };
+/// 
+/// let clock = ManualClock::new(0);
+/// let mut client = ClientHeartbeat::new(&clock, 1000, 3000);
+/// let mut server = ServerHeartbeat::new(&clock, 1000, 3000);
+/// 
+/// // Server answers the ping of client
+/// clock.advance(1000);
+/// let ping = client.poll().unwrap();
+/// let pong = server.receive(&ping).unwrap();
+/// 
+/// clock.advance(40);
+/// client.receive(&pong);
+/// assert_eq!(client.rtt(), Some(40));
+/// 
+/// // Client stops answering
+/// clock.advance(3000);
+/// assert_eq!(server.health(), Health::TimedOut);
+/// ```
+pub struct Heartbeat<S : KeepAlive, R : KeepAlive, C : Clock> {
+    clock : C,
+
+    /// Interval in milliseconds between pings.
+    interval : u64,
+
+    /// Time in milliseconds without receiving anything before peer is timed out.
+    timeout : u64,
+
+    /// Time the last message was received, or of creation.
+    last_received : u64,
+
+    /// Time the last ping was sent, or of creation.
+    last_ping : u64,
+
+    /// Id of the next ping.
+    next_id : u32,
+
+    /// Id and time of pings waiting for a pong, oldest first.
+    pending : VecDeque<(u32, u64)>,
+
+    /// Smoothed round-trip time in milliseconds.
+    rtt : Option<u64>,
+
+    messages : PhantomData<(S, R)>,
+}
+
+impl<S : KeepAlive, R : KeepAlive, C : Clock> Heartbeat<S, R, C> {
+    /// Create a new [`Heartbeat`], considering the peer alive now.
+    /// 
+    /// # Argument(s)
+    /// * `clock` - [`Clock`] of pings and timeouts.
+    /// * `interval` - Interval in milliseconds between pings, usually [`HEARTBEAT_INTERVAL`].
+    /// * `timeout` - Time in milliseconds without receiving anything before peer is timed out, usually [`HEARTBEAT_TIMEOUT`].
+    pub fn new(clock : C, interval : u64, timeout : u64) -> Heartbeat<S, R, C> {
+        let now = clock.now();
+        Heartbeat { clock, interval, timeout, last_received: now, last_ping: now, next_id: 0, pending: VecDeque::new(), rtt: None, messages: PhantomData }
+    }
+
+    /// Smoothed round-trip time in milliseconds, [`None`] until a pong is received.
+    pub fn rtt(&self) -> Option<u64> {
+        self.rtt
+    }
+
+    /// Time in milliseconds since something was received.
+    pub fn idle(&self) -> u64 {
+        self.clock.now().saturating_sub(self.last_received)
+    }
+
+    /// Count of pings waiting for a pong.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Health of the remote peer.
+    pub fn health(&self) -> Health {
+        match self.idle() {
+            idle if idle >= self.timeout => Health::TimedOut,
+            idle if idle > self.interval => Health::Silent,
+            _ => Health::Alive,
+        }
+    }
+
+    /// Returns true if nothing was received for the timeout.
+    pub fn is_timed_out(&self) -> bool {
+        self.health() == Health::TimedOut
+    }
+
+    /// Ping to send if the interval elapsed since the last one.
+    /// 
+    /// Pings unanswered for the timeout are forgotten.
+    pub fn poll(&mut self) -> Option<S> {
+        let now = self.clock.now();
+
+        while self.pending.front().is_some_and(|(_, sent)| now.saturating_sub(*sent) >= self.timeout) {
+            self.pending.pop_front();
+        }
+
+        if now.saturating_sub(self.last_ping) < self.interval {
+            return None;
+        }
+
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.last_ping = now;
+        self.pending.push_back((id, now));
+        Some(S::ping(id, now))
+    }
+
+    /// Handle a message received from the remote peer.
+    /// 
+    /// # Returns
+    /// Pong to send if message is a ping.
+    pub fn receive(&mut self, message : &R) -> Option<S> {
+        let now = self.clock.now();
+        self.last_received = now;
+
+        if let Some(id) = message.as_pong() {
+            // Pongs of forgotten or unknown pings are ignored
+            if let Some(index) = self.pending.iter().position(|(pending, _)| *pending == id) {
+                let sample = now.saturating_sub(self.pending[index].1);
+                self.pending.drain(..=index);
+                self.rtt = Some(match self.rtt {
+                    Some(rtt) => (rtt * 7 + sample) / 8,
+                    None => sample,
+                });
+            }
+        }
+
+        message.as_ping().map(|id| S::pong(id, now))
+    }
+}
+
+
+/// This module test [Heartbeat] with a [ManualClock](crate::net::ManualClock).
+/// 
+/// # Verification(s)
+/// V1 : [Heartbeat::poll] sends a ping each interval only.
+/// V2 : [Heartbeat::receive] answers pings with a pong of the same id.
+/// V3 : [Heartbeat::receive] measures and smooths round-trip time from pongs.
+/// V4 : [Heartbeat::health] goes from alive to silent to timed out without messages received.
+/// V5 : [Heartbeat::receive] of any message keeps peer alive.
+/// V6 : [Heartbeat::receive] ignores pongs of unknown or forgotten pings.
+/// V7 : Ping and pong payloads pack and unpack.
+#[cfg(test)]
+mod tests {
+    use crate::net::{ClientHeartbeat, ClientMessage, ClientPayload, Clock, Health, KeepAlive, ManualClock, MESSAGE_SIZE_TYPE_SIZE, ServerHeartbeat, ServerMessage, ServerPayload};
+
+    const INTERVAL : u64 = 1000;
+    const TIMEOUT : u64 = 3000;
+
+    fn client(clock : &ManualClock) -> ClientHeartbeat<ManualClock> {
+        ClientHeartbeat::new(clock.clone(), INTERVAL, TIMEOUT)
+    }
+
+    fn server(clock : &ManualClock) -> ServerHeartbeat<ManualClock> {
+        ServerHeartbeat::new(clock.clone(), INTERVAL, TIMEOUT)
+    }
+
+    #[test]
+    fn v1_poll_each_interval() {
+        // V1 : [Heartbeat::poll] sends a ping each interval only.
+        let clock = ManualClock::new(0);
+        let mut client = client(&clock);
+
+        assert!(client.poll().is_none());
+        clock.advance(INTERVAL - 1);
+        assert!(client.poll().is_none());
+
+        clock.advance(1);
+        assert_eq!(client.poll().unwrap().as_ping(), Some(0));
+        assert!(client.poll().is_none());
+
+        clock.advance(INTERVAL);
+        assert_eq!(client.poll().unwrap().as_ping(), Some(1));
+        assert_eq!(client.pending(), 2);
+    }
+
+    #[test]
+    fn v2_pong_to_ping() {
+        // V2 : [Heartbeat::receive] answers pings with a pong of the same id.
+        let clock = ManualClock::new(0);
+        let mut server = server(&clock);
+
+        clock.advance(25);
+        let pong = server.receive(&ClientMessage::ping(7, 0)).unwrap();
+        assert_eq!(pong.as_pong(), Some(7));
+        assert_eq!(pong.timestamp, 25);
+
+        assert!(server.receive(&ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 })).is_none());
+        assert!(server.receive(&ClientMessage::pong(7, 0)).is_none());
+    }
+
+    #[test]
+    fn v3_rtt() {
+        // V3 : [Heartbeat::receive] measures and smooths round-trip time from pongs.
+        let clock = ManualClock::new(0);
+        let mut client = client(&clock);
+        let mut server = server(&clock);
+        assert_eq!(client.rtt(), None);
+
+        for (index, sample) in [80, 160].iter().enumerate() {
+            clock.advance(INTERVAL);
+            let ping = client.poll().unwrap();
+            assert_eq!(ping.as_ping(), Some(index as u32));
+            let pong = server.receive(&ping).unwrap();
+            clock.advance(*sample);
+            assert!(client.receive(&pong).is_none());
+        }
+
+        assert_eq!(client.rtt(), Some((80 * 7 + 160) / 8));
+        assert_eq!(client.pending(), 0);
+    }
+
+    #[test]
+    fn v4_health() {
+        // V4 : [Heartbeat::health] goes from alive to silent to timed out without messages received.
+        let clock = ManualClock::new(0);
+        let server = server(&clock);
+        assert_eq!(server.health(), Health::Alive);
+
+        clock.advance(INTERVAL);
+        assert_eq!(server.health(), Health::Alive);
+
+        clock.advance(1);
+        assert_eq!(server.health(), Health::Silent);
+        assert!(!server.is_timed_out());
+
+        clock.set(TIMEOUT);
+        assert_eq!(server.health(), Health::TimedOut);
+        assert!(server.is_timed_out());
+        assert_eq!(server.idle(), TIMEOUT);
+    }
+
+    #[test]
+    fn v5_receive_keeps_alive() {
+        // V5 : [Heartbeat::receive] of any message keeps peer alive.
+        let clock = ManualClock::new(0);
+        let mut client = client(&clock);
+
+        for _ in 0..10 {
+            clock.advance(TIMEOUT - 1);
+            client.receive(&ServerMessage::new(clock.now(), ServerPayload::Test { p16: 1, p32: 2 }));
+            assert_eq!(client.health(), Health::Alive);
+            assert_eq!(client.idle(), 0);
+        }
+    }
+
+    #[test]
+    fn v6_unknown_pong() {
+        // V6 : [Heartbeat::receive] ignores pongs of unknown or forgotten pings.
+        let clock = ManualClock::new(0);
+        let mut client = client(&clock);
+
+        clock.advance(INTERVAL);
+        client.poll().unwrap();
+        client.receive(&ServerMessage::pong(42, 0));
+        assert_eq!(client.rtt(), None);
+        assert_eq!(client.pending(), 1);
+
+        // Ping 0 is forgotten after timeout
+        clock.advance(TIMEOUT);
+        assert_eq!(client.poll().unwrap().as_ping(), Some(1));
+        assert_eq!(client.pending(), 1);
+        client.receive(&ServerMessage::pong(0, 0));
+        assert_eq!(client.rtt(), None);
+    }
+
+    #[test]
+    fn v7_pack_unpack() {
+        // V7 : Ping and pong payloads pack and unpack.
+        let mut buffer = [0u8; 64];
+
+        let size = ClientMessage::ping(u32::MAX, 0).pack_bytes(&mut buffer).unwrap();
+        assert_eq!(ClientMessage::from_bytes(&buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]).unwrap().as_ping(), Some(u32::MAX));
+
+        let size = ServerMessage::pong(3, 99).pack_bytes(&mut buffer).unwrap();
+        let unpacked = ServerMessage::from_bytes(&buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]).unwrap();
+        assert_eq!(unpacked.as_pong(), Some(3));
+        assert_eq!(unpacked.timestamp, 99);
+    }
+}
@@ -1,4 +1,6 @@
 
+use crate::net::{Error, MESSAGE_SIZE_TYPE_SIZE};
+
 /// This macro generate message code since client and server share same code but with differents parameters.
 ///
 ///
@@ -8,13 +10,13 @@
 #[macro_export]
 macro_rules! write_messages_struct {
 
-     ( $max_size:expr, $(#[$comment:meta])* $struct_name:ident <$payload_type:ident> $(, $(#[$ex_attr:meta])* $ex_vis : vis $ex_pname : ident : $ex_ptype : ident )* ) => {       
+     ( $max_size:expr, $(#[$($comment:tt)*])* $struct_name:ident <$payload_type:ident> $(, $(#[$($ex_attr:tt)*])* $ex_vis : vis $ex_pname : ident : $ex_ptype : ident )* ) => {       
 
-        $( #[$comment] )*
+        $( #[$($comment)*] )*
         #[derive(Debug, PartialEq)]
         pub struct $struct_name {
 
-            /// Packed size of the message in bytes including payload and size itself.
+            /// Packed size of the message in bytes including payload and extra fields, excluding size itself.
             pub size : u16,
 
             /// Message content sent between client and server.
@@ -23,7 +25,7 @@ macro_rules! write_messages_struct {
             // Extra fields
             $(
                 $(
-                    #[$ex_attr]
+                    #[$($ex_attr)*]
                 )*
                 $ex_vis $ex_pname : $ex_ptype,
             )*
@@ -31,6 +33,28 @@ macro_rules! write_messages_struct {
         }
 
         impl $struct_name {
+            /// Layout of the message with its extra fields, see [`schema_json`](crate::net::schema_json).
+            #[allow(dead_code)]
+            pub const LAYOUT : $crate::net::MessageSchema = $crate::net::MessageSchema {
+                name : stringify!($struct_name),
+                payload : stringify!($payload_type),
+                max_size : $max_size,
+                extras : &[
+                    $(
+                        $crate::net::FieldSchema {
+                            name : stringify!($ex_pname),
+                            ty : stringify!($ex_ptype),
+                            size : <$ex_ptype as $crate::net::PayloadField>::FIXED_SIZE,
+                            max : None,
+                            optional : false,
+                            nested : <$ex_ptype as $crate::net::PayloadField>::NESTED,
+                            doc : $crate::schema_doc!($( [$($ex_attr)*] )*),
+                        }
+                    ),*
+                ],
+                doc : $crate::schema_doc!($( [$($comment)*] )*),
+            };
+
             /// Create a new [`Message`](Self) from payload.
             /// 
             /// Size is automatically calculated.
@@ -38,7 +62,8 @@ macro_rules! write_messages_struct {
             /// # Returns
             ///  [`Message`](Self) created.
             pub fn new($($ex_pname  : $ex_ptype,)* payload : $payload_type ) -> $struct_name {
-                let size : u16 = payload.bytes_size() as u16 + (0 $(+ size_of::<$ex_ptype>())*) as u16;
+                // Saturate so that oversized message are refused by pack_bytes instead of wrapping
+                let size : u16 = (payload.bytes_size() + (0 $(+ size_of::<$ex_ptype>())*)).min(u16::MAX as usize) as u16;
                 $struct_name { size, $($ex_pname,)* payload  }
             }
 
@@ -49,16 +74,24 @@ macro_rules! write_messages_struct {
             /// 
             /// # Returns
             /// [`Result`] which is:
-            /// - [`Ok`]: [`usize`] which represent size of bytes packed.
-            ///     1. [`Error::BufferSizeTooSmall`](`crate::net::Error::BufferSizeTooSmall`) if buffer is too small to pack message.
+            /// - [`Ok`]: [`usize`] which represent size of message packed, excluding the size header (same as [`size`](Self::size)).
+            /// - [`Err`]:
+            ///     1. [`ErrorKind::BufferSizeTooSmall`](`crate::net::ErrorKind::BufferSizeTooSmall`) if buffer is too small to pack message.
+            ///     2. [`ErrorKind::MessageSizeGreaterThanLimit`](`crate::net::ErrorKind::MessageSizeGreaterThanLimit`) if a field length exceed its limit or size can't fit the size header.
             pub fn pack_bytes(&self, buffer : &mut [u8]) -> Result<usize, $crate::net::Error> {
 
-                // Make sure buffer is big enough to pack
-                if buffer.len() >= self.payload.bytes_size() + $crate::net::MESSAGE_SIZE_TYPE_SIZE + (0 $(+ size_of::<$ex_ptype>())*) {
+                let content_size = self.payload.bytes_size() + (0 $(+ size_of::<$ex_ptype>())*);
+
+                // Make sure fields are within limits and size fits the header
+                if content_size > u16::MAX as usize {
+                    Err($crate::net::Error::size($crate::net::ErrorKind::MessageSizeGreaterThanLimit, u16::MAX as usize, content_size))
+                } else if !self.payload.within_limits() {
+                    Err($crate::net::Error::from($crate::net::ErrorKind::MessageSizeGreaterThanLimit))
+                } else if buffer.len() >= content_size + $crate::net::MESSAGE_SIZE_TYPE_SIZE {  // Make sure buffer is big enough to pack
                     tampon::serialize!(buffer, size, (self.size):u16, (self.payload):$payload_type $(,(self.$ex_pname):$ex_ptype)*);
-                    Ok(size) 
+                    Ok(size - $crate::net::MESSAGE_SIZE_TYPE_SIZE) 
                 } else {
-                    Err($crate::net::Error::BufferSizeTooSmall)
+                    Err($crate::net::Error::size($crate::net::ErrorKind::BufferSizeTooSmall, content_size + $crate::net::MESSAGE_SIZE_TYPE_SIZE, buffer.len()))
                 }
 
             }
@@ -81,11 +114,21 @@ macro_rules! write_messages_struct {
             /// [`Result`] which is:
             /// - [`Ok`]: [`Message`](Self) properly extracted from bytes.
             /// - [`Err`]:
-            ///     1. [`Error::InvalidMessage`](crate::net::Error::InvalidMessage) for malformed [`Message`](Self).
-            ///     2. [`Error::IncompleteMessage`](crate::net::Error::IncompleteMessage) for buffer too short to read [`Message`](Self) entirely.
-            ///     3. [`Error::MessageSizeInvalid`](crate::net::Error::MessageSizeInvalid) when given size doesn't match content size.
-            ///     4. [`Error::MessageSizeGreaterThanLimit`](crate::net::Error::MessageSizeGreaterThanLimit) when size exceed limit.
+            ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) for malformed [`Message`](Self).
+            ///     2. [`ErrorKind::IncompleteMessage`](crate::net::ErrorKind::IncompleteMessage) for buffer too short to read [`Message`](Self) entirely.
+            ///     3. [`ErrorKind::MessageSizeInvalid`](crate::net::ErrorKind::MessageSizeInvalid) when given size doesn't match content size.
+            ///     4. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when size exceed limit.
             pub fn from_bytes(bytes : &[u8]) -> Result<$struct_name, $crate::net::Error> {
+                Self::validate_bytes(bytes)?;
+
+                // Deserialize and return message
+                tampon::deserialize!(bytes, (payload):$payload_type $(,($ex_pname):$ex_ptype)*);
+                Ok($struct_name { size : bytes.len() as u16,  payload $(,$ex_pname)* })
+            }
+
+            /// Validate that bytes contain exactly one [`Message`](Self), with the same errors as [`from_bytes`](Self::from_bytes).
+            #[doc(hidden)]
+            pub fn validate_bytes(bytes : &[u8]) -> Result<(), $crate::net::Error> {
 
                 // Make sure we can read discriminant
                 if bytes.len() >= $crate::net::DISCRIMINANT_TYPE_SIZE {
@@ -96,33 +139,114 @@ macro_rules! write_messages_struct {
                     if <$payload_type>::is_valid(discriminant) {
                         // Get size of deserialization
                         match tampon::deserialize_size!(bytes, $max_size, (payload):$payload_type $(,($ex_pname):$ex_ptype)*) {
-                            Ok(size_from_ds) => {
-                                // Make sure size given matches size of bytes
-                                if bytes.len() == size_from_ds  {
-                                    // Deserialize and return massage
-                                    tampon::deserialize!(bytes, (payload):$payload_type $(,($ex_pname):$ex_ptype)*);
-                                    Ok($struct_name { size : size_from_ds as u16,  payload $(,$ex_pname)* })
-
-                                } else {
-                                    Err($crate::net::Error::MessageSizeInvalid)
-                                }
-                            },
-                            Err(err) => match err {
-                                tampon::TamponError::DeserializeSizeBufferIncomplete => Err($crate::net::Error::IncompleteMessage),
-                                tampon::TamponError::DeserializeSizeGreaterThanMax => Err($crate::net::Error::MessageSizeGreaterThanLimit),
-                            },
+                            // Make sure size given matches size of bytes
+                            Ok(size_from_ds) if bytes.len() == size_from_ds => Ok(()),
+                            Ok(size_from_ds) => Err($crate::net::Error::size($crate::net::ErrorKind::MessageSizeInvalid, size_from_ds, bytes.len())),
+                            Err(err) => Err(err.into()),
                         }
                     } else {
-                        Err($crate::net::Error::InvalidMessage)
+                        Err($crate::net::Error::discriminant(discriminant, 0))
                     }
                 } else {
-                    Err($crate::net::Error::IncompleteMessage)
+                    Err($crate::net::Error::size($crate::net::ErrorKind::IncompleteMessage, $crate::net::DISCRIMINANT_TYPE_SIZE, bytes.len()))
                 }
             }
         }
+
+        $crate::concat_idents::concat_idents!(message_ref = $struct_name, Ref {
+            #[doc = concat!("Borrowed view of [`", stringify!($struct_name), "`], validated once with payload fields read straight from the bytes.")]
+            /// 
+            /// Use it instead of the owned message to read strings and blobs without allocation.
+            #[derive(Debug, PartialEq, Clone)]
+            #[allow(dead_code)] // Generated for every message, even if only the owned one is used
+            pub struct message_ref<'a> {
+
+                /// Packed size of the message in bytes including payload and extra fields, excluding size itself.
+                pub size : u16,
+
+                /// View of the message content.
+                pub payload : <$payload_type as $crate::net::PayloadView<'a>>::Ref,
+
+                // Extra fields
+                $(
+                    $(
+                        #[$($ex_attr)*]
+                    )*
+                    $ex_vis $ex_pname : $ex_ptype,
+                )*
+
+            }
+
+            #[allow(dead_code)]
+            impl<'a> message_ref<'a> {
+                #[doc = concat!("View of a [`", stringify!($struct_name), "`] in an array of bytes, without the size header.")]
+                /// 
+                #[doc = concat!("Errors are the same as [`", stringify!($struct_name), "::from_bytes`].")]
+                pub fn from_bytes(bytes : &'a [u8]) -> Result<Self, $crate::net::Error> {
+                    $struct_name::validate_bytes(bytes)?;
+
+                    let (payload, mut _size) = <$payload_type as $crate::net::PayloadView<'a>>::read_ref(bytes);
+                    $(
+                        let ($ex_pname, field_size) = <$ex_ptype as $crate::net::PayloadField>::read_field(&bytes[_size..]);
+                        _size += field_size;
+                    )*
+                    Ok(message_ref { size : bytes.len() as u16, payload $(,$ex_pname)* })
+                }
+
+                #[doc = concat!("Owned [`", stringify!($struct_name), "`] of the view.")]
+                pub fn into_owned(self) -> $struct_name {
+                    $struct_name { size : self.size, payload : $crate::net::PayloadView::from_ref(self.payload) $(, $ex_pname : self.$ex_pname)* }
+                }
+            }
+        });
+
+        impl $crate::net::Message for $struct_name {
+            const MAX_SIZE : usize = $max_size;
+
+            fn size(&self) -> usize {
+                self.size as usize
+            }
+
+            fn pack_bytes(&self, buffer : &mut [u8]) -> Result<usize, $crate::net::Error> {
+                $struct_name::pack_bytes(self, buffer)
+            }
+
+            fn size_from_bytes(bytes : &[u8; $crate::net::MESSAGE_SIZE_TYPE_SIZE]) -> u16 {
+                $struct_name::size_from_bytes(bytes)
+            }
+
+            fn from_bytes(bytes : &[u8]) -> Result<Self, $crate::net::Error> {
+                $struct_name::from_bytes(bytes)
+            }
+        }
      }
 }
 
+/// Common interface of messages generated by [`write_messages_struct!`].
+/// 
+/// Used by generic components like [`MessageDecoder`](crate::net::MessageDecoder) that
+/// work the same way for [`ClientMessage`](crate::net::ClientMessage) and [`ServerMessage`](crate::net::ServerMessage).
+pub trait Message : Sized {
+    /// Maximum size in bytes of a message, excluding the size header.
+    const MAX_SIZE : usize;
+
+    /// Packed size of the message in bytes, excluding the size header.
+    fn size(&self) -> usize;
+
+    /// Pack the message in little-endian bytes in a given buffer.
+    /// 
+    /// See [`ServerMessage::pack_bytes`](crate::net::ServerMessage::pack_bytes).
+    fn pack_bytes(&self, buffer : &mut [u8]) -> Result<usize, Error>;
+
+    /// Get the size from the size header bytes.
+    fn size_from_bytes(bytes : &[u8; MESSAGE_SIZE_TYPE_SIZE]) -> u16;
+
+    /// Extract a message from an array of bytes, without the size header.
+    /// 
+    /// See [`ServerMessage::from_bytes`](crate::net::ServerMessage::from_bytes).
+    fn from_bytes(bytes : &[u8]) -> Result<Self, Error>;
+}
+
 
 
 /// This module test the write_messages_struct! macro.
@@ -130,18 +254,20 @@ macro_rules! write_messages_struct {
 /// # Verification(s)
 /// V1 : [Message::new] create a new [Message].
 /// V2 : [Message::pack_bytes] pack the [Message] correctly with a buffer of size [super::PACK_BUFFER_SIZE] and returns Ok(size).
-/// V3 : [Message::pack_bytes] returns [`Error::BufferSizeTooSmall`] when buffer is too small.
+/// V3 : [Message::pack_bytes] returns [`ErrorKind::BufferSizeTooSmall`](crate::net::ErrorKind::BufferSizeTooSmall) when buffer is too small.
 /// V4 : [Message::pack_bytes] then [Message::from_bytes] should contains the same message.
-/// V5 : [Message::from_bytes] must return [`Error::InvalidMessage`] for malformed message.
-/// V6 : [Message::from_bytes] must return [`Error::IncompleteMessage`] for buffer too short to read `Message` entirely.
-/// V7 : [Message::from_bytes] must return [`Error::MessageSizeInvalid`] when given size doesn't match content size.
-/// V8 : [Message::from_bytes] must return [`Error::MessageSizeGreaterThanLimit`] when size exceed limit.
+/// V5 : [Message::from_bytes] must return [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) for malformed message.
+/// V6 : [Message::from_bytes] must return [`ErrorKind::IncompleteMessage`](crate::net::ErrorKind::IncompleteMessage) for buffer too short to read `Message` entirely.
+/// V7 : [Message::from_bytes] must return [`ErrorKind::MessageSizeInvalid`](crate::net::ErrorKind::MessageSizeInvalid) when given size doesn't match content size.
+/// V8 : [Message::from_bytes] must return [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when size exceed limit.
 /// V9 : [Message::size_from_bytes] return correct size.
+/// V10 : [Message::pack_bytes] and [Message::from_bytes] return [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when a field exceed its maximum length.
+/// V11 : [MessageRef::from_bytes] gives back the same message and errors as [Message::from_bytes], borrowing variable-length fields.
 #[cfg(test)]
 mod tests_messages {
-    use std::{u8, u16, u32, u64, u128};
     use tampon::{Tampon, deserialize, deserialize_size, serialize};
-    use crate::net::{Error, MESSAGE_SIZE_TYPE_SIZE};
+    use crate::net::{ErrorKind, PayloadView, MESSAGE_SIZE_TYPE_SIZE};
+    use crate::net::client::test_payloads::{TestClientMessage, TestClientMessageRef, TestClientPayload, TestClientPayloadRef};
 
     const DISC_VAL : u16 = u16::MAX / 2 + 2;
     const P1_VAL : u8 = u8::MAX / 2;
@@ -219,24 +345,24 @@ mod tests_messages {
 
     #[test]
     fn v3_message_pack_bytes_buffer_too_small(){
-        // V3 : [Message::pack_bytes] returns [`Error::BufferSizeTooSmall`] when buffer is too small.
+        // V3 : [Message::pack_bytes] returns [`ErrorKind::BufferSizeTooSmall`] when buffer is too small.
         
         let mut buffer = [0u8; SIZE_VAL];
         let (msg1, msg2, msg3) = generate_test_msgs();
 
         match msg1.pack_bytes(&mut buffer) {
             Ok(_) => panic!("msg1 should return Err BufferSizeTooSmall"),
-            Err(err) => assert_eq!(err,Error::BufferSizeTooSmall),
+            Err(err) => assert_eq!(err.kind(), ErrorKind::BufferSizeTooSmall),
         }
 
         match msg2.pack_bytes(&mut buffer) {
             Ok(_) => panic!("msg2 should return Err BufferSizeTooSmall"),
-            Err(err) => assert_eq!(err,Error::BufferSizeTooSmall),
+            Err(err) => assert_eq!(err.kind(), ErrorKind::BufferSizeTooSmall),
         }
 
         match msg3.pack_bytes(&mut buffer) {
             Ok(_) => panic!("msg3 should return Err BufferSizeTooSmall"),
-            Err(err) => assert_eq!(err,Error::BufferSizeTooSmall),
+            Err(err) => assert_eq!(err.kind(), ErrorKind::BufferSizeTooSmall),
         }
     }
 
@@ -283,7 +409,7 @@ mod tests_messages {
         match msg_invalid.pack_bytes(&mut buffer) {
             Ok(_) => match MessageTestInvalid::from_bytes(&buffer) {
                     Ok(_) => panic!("msg_invalid.from_bytes() should not be Ok!"),
-                    Err(err) => assert_eq!(err, Error::InvalidMessage),
+                    Err(err) => assert_eq!(err.kind(), ErrorKind::InvalidMessage),
             },
             Err(err) => panic!("msg_invalid.pack_bytes() should not Err({:?})!", err),
         }
@@ -292,7 +418,7 @@ mod tests_messages {
 
     #[test]
     fn v6_message_from_bytes_incomplete_message(){
-        // V6 : [Message::from_bytes] must return [`Error::IncompleteMessage`] for buffer too short to read `Message` entirely.
+        // V6 : [Message::from_bytes] must return [`ErrorKind::IncompleteMessage`] for buffer too short to read `Message` entirely.
         let mut buffer = [0u8; PACK_BUFFER_SIZE];
 
         let (ctrl_msg1, ctrl_msg2, ctrl_msg3) = generate_test_msgs(); 
@@ -300,7 +426,7 @@ mod tests_messages {
         match ctrl_msg1.pack_bytes(&mut buffer) {
             Ok(_) => match MessageTestNoExtra::from_bytes(&buffer[..2]) {
                     Ok(_) => panic!("ctrl_msg1.from_bytes() should not be Ok!"),
-                    Err(err) => assert_eq!(err, Error::IncompleteMessage),
+                    Err(err) => assert_eq!(err.kind(), ErrorKind::IncompleteMessage),
             },
             Err(err) => panic!("ctrl_msg1.pack_bytes() should not Err({:?})!", err),
         }
@@ -308,7 +434,7 @@ mod tests_messages {
         match ctrl_msg2.pack_bytes(&mut buffer) {
             Ok(size) => match MessageTestOneExtra::from_bytes(&buffer[..(size/2)]) {
                     Ok(_) => panic!("ctrl_msg2.from_bytes() should not be Ok!"),
-                    Err(err) => assert_eq!(err, Error::IncompleteMessage),
+                    Err(err) => assert_eq!(err.kind(), ErrorKind::IncompleteMessage),
             },
             Err(err) => panic!("ctrl_msg2.from_bytes() should not Err({:?})!", err),
         }
@@ -316,7 +442,7 @@ mod tests_messages {
         match ctrl_msg3.pack_bytes(&mut buffer) {
             Ok(size) => match MessageTestMultiExtra::from_bytes(&buffer[..(size/2)]) {
                     Ok(_) => panic!("ctrl_msg3.from_bytes() should not be Ok!"),
-                    Err(err) => assert_eq!(err, Error::IncompleteMessage),
+                    Err(err) => assert_eq!(err.kind(), ErrorKind::IncompleteMessage),
             },
             Err(err) => panic!("ctrl_msg3.from_bytes() should not Err({:?})!", err),
         }
@@ -325,7 +451,7 @@ mod tests_messages {
 
     #[test]
     fn v7_message_from_bytes_size_invalid(){
-        // V7: [Message::from_bytes] must return [`Error::MessageSizeInvalid`] when given size doesn't match content size.
+        // V7: [Message::from_bytes] must return [`ErrorKind::MessageSizeInvalid`] when given size doesn't match content size.
         
         
         let mut buffer = [0u8; PACK_BUFFER_SIZE];
@@ -339,7 +465,7 @@ mod tests_messages {
                 serialize!(buffer, (size):u16);
                 match MessageTestNoExtra::from_bytes(&buffer) {
                     Ok(_) => panic!("ctrl_msg1.from_bytes() should not be Ok!"),
-                    Err(err) => assert_eq!(err, Error::MessageSizeInvalid),
+                    Err(err) => assert_eq!(err.kind(), ErrorKind::MessageSizeInvalid),
             }},
             Err(err) => panic!("ctrl_msg1.pack_bytes() should not Err({:?})!", err),
         }
@@ -350,7 +476,7 @@ mod tests_messages {
                 serialize!(buffer, (0):u16);
                 match MessageTestOneExtra::from_bytes(&buffer) {
                     Ok(_) => panic!("ctrl_msg2.from_bytes() should not be Ok!"),
-                    Err(err) => assert_eq!(err, Error::MessageSizeInvalid),
+                    Err(err) => assert_eq!(err.kind(), ErrorKind::MessageSizeInvalid),
             }},
             Err(err) => panic!("ctrl_msg1.pack_bytes() should not Err({:?})!", err),
         }
@@ -362,7 +488,7 @@ mod tests_messages {
                 serialize!(buffer, (size):u16);
                 match MessageTestMultiExtra::from_bytes(&buffer) {
                     Ok(_) => panic!("ctrl_msg3.from_bytes() should not be Ok!"),
-                    Err(err) => assert_eq!(err, Error::MessageSizeInvalid),
+                    Err(err) => assert_eq!(err.kind(), ErrorKind::MessageSizeInvalid),
             }},
             Err(err) => panic!("ctrl_msg1.pack_bytes() should not Err({:?})!", err),
         }
@@ -372,7 +498,7 @@ mod tests_messages {
 
     #[test]
     fn v8_message_from_bytes_greater_than_limit(){
-        // V8 : [Message::from_bytes] must return [`Error::MessageSizeGreaterThanLimit`] when size exceed limit.
+        // V8 : [Message::from_bytes] must return [`ErrorKind::MessageSizeGreaterThanLimit`] when size exceed limit.
         
         let msg_small = MessageTestSmallMax::new(DISC_VAL as u64, PayloadTest::new());
         let mut buffer = [0u8; PACK_BUFFER_SIZE];
@@ -380,7 +506,7 @@ mod tests_messages {
         match msg_small.pack_bytes(&mut buffer) {
             Ok(_) => match MessageTestSmallMax::from_bytes(&buffer) {
                     Ok(_) => panic!("msg_small.from_bytes() should not be Ok!"),
-                    Err(err) => assert_eq!(err, Error::MessageSizeGreaterThanLimit),
+                    Err(err) => assert_eq!(err.kind(), ErrorKind::MessageSizeGreaterThanLimit),
             },
             Err(err) => panic!("msg_small.pack_bytes() should not Err({:?})!", err),
         }
@@ -399,7 +525,76 @@ mod tests_messages {
 
     }
 
-    #[derive(Debug, PartialEq)]
+    #[test]
+    fn v10_message_field_greater_than_limit(){
+        // V10 : [Message::pack_bytes] and [Message::from_bytes] return [`ErrorKind::MessageSizeGreaterThanLimit`] when a field exceed its maximum length.
+        let variable = |text : &str| TestClientMessage::new(TestClientPayload::Variable { 
+            text: String::from(text), list: vec![1, 2], blob: Box::new([3, 4]), fixed: [5, 6, 7, 8] 
+        });
+        let mut buffer = [0u8; PACK_BUFFER_SIZE];
+
+        let valid = variable("Thirty-two characters long text!");
+        let size = valid.pack_bytes(&mut buffer).unwrap();
+        assert_eq!(TestClientMessage::from_bytes(&buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]), Ok(valid));
+
+        let oversized = variable("Thirty-three characters long text");
+        assert_eq!(oversized.pack_bytes(&mut buffer), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+
+        // Forge the oversized text length in a valid message
+        buffer[MESSAGE_SIZE_TYPE_SIZE + 2] = 33;
+        assert_eq!(TestClientMessage::from_bytes(&buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+    }
+
+    #[test]
+    fn v11_message_ref_from_bytes() {
+        // V11 : [MessageRef::from_bytes] gives back the same message and errors as [Message::from_bytes], borrowing variable-length fields.
+        macro_rules! assert_same {
+            ($message : ident, $bytes : expr) => {
+                let bytes : &[u8] = $bytes;
+                assert_eq!(concat_idents::concat_idents!(message_ref = $message, Ref { message_ref::from_bytes(bytes) }).map(|view| view.into_owned()), 
+                    $message::from_bytes(bytes));
+            };
+        }
+
+        let mut buffer = [0u8; PACK_BUFFER_SIZE];
+        let (msg1, msg2, msg3) = generate_test_msgs();
+
+        let size = msg1.pack_bytes(&mut buffer).unwrap();
+        assert_same!(MessageTestNoExtra, &buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]);
+        assert_same!(MessageTestNoExtra, &buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size - 1]);
+        assert_same!(MessageTestSmallMax, &buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]);
+        assert_same!(MessageTestInvalid, &buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]);
+
+        let size = msg2.pack_bytes(&mut buffer).unwrap();
+        assert_same!(MessageTestOneExtra, &buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]);
+        assert_same!(MessageTestOneExtra, &buffer[..MESSAGE_SIZE_TYPE_SIZE + size]);
+
+        let size = msg3.pack_bytes(&mut buffer).unwrap();
+        assert_same!(MessageTestMultiExtra, &buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]);
+        assert_eq!(MessageTestMultiExtraRef::from_bytes(&buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size]).unwrap().ex5, P5_VAL);
+
+        // Variable-length fields point into the bytes
+        let message = TestClientMessage::new(TestClientPayload::Variable { text: String::from("view"), list: vec![1, 2], blob: Box::new([3, 4, 5]), fixed: [6, 7, 8, 9] });
+        let mut buffer = [0u8; 64];
+        let size = message.pack_bytes(&mut buffer).unwrap();
+        let bytes = &buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size];
+        assert_same!(TestClientMessage, bytes);
+
+        let view = TestClientMessageRef::from_bytes(bytes).unwrap();
+        match &view.payload {
+            TestClientPayloadRef::Variable { text, list, blob, fixed } => {
+                assert!(matches!(text, std::borrow::Cow::Borrowed("view")));
+                assert_eq!(list.iter().collect::<Vec<_>>(), [1, 2]);
+                assert!(bytes.as_ptr_range().contains(&blob.as_bytes().as_ptr()));
+                assert_eq!(blob.as_bytes(), [3, 4, 5]);
+                assert_eq!(fixed.len(), 4);
+            },
+            payload => panic!("Unexpected payload {:?}", payload),
+        }
+        assert_eq!(view.into_owned(), message);
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
     pub struct  PayloadTest {
         discriminant : u16,
         p1 : u8, p2 : u16,  p3 : u32, p4 : u64, p5: u128
@@ -432,9 +627,12 @@ mod tests_messages {
         pub fn is_valid(_disc : u16) -> bool {
             true
         }
+        pub fn within_limits(&self) -> bool {
+            true
+        }
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, PartialEq, Clone)]
     pub struct  PayloadTestInvalid {
         discriminant : u16,
         p1 : u8, p2 : u16,  p3 : u32, p4 : u64, p5: u128
@@ -467,8 +665,32 @@ mod tests_messages {
         pub fn is_valid(_disc : u16) -> bool {
             false
         }
+        pub fn within_limits(&self) -> bool {
+            true
+        }
+    }
+
+    /// Test payloads are their own view.
+    macro_rules! impl_payload_view {
+        ($($payload : ident),+) => {
+            $(
+                impl<'a> PayloadView<'a> for $payload {
+                    type Ref = $payload;
+
+                    fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+                        $payload::deserialize(buffer)
+                    }
+
+                    fn from_ref(view : Self::Ref) -> Self {
+                        view
+                    }
+                }
+            )+
+        };
     }
 
+    impl_payload_view!(PayloadTest, PayloadTestInvalid);
+
     // No extra
     write_messages_struct!{ PACK_BUFFER_SIZE,
         MessageTestNoExtra < PayloadTest >
@@ -32,18 +32,216 @@ mod message;
 #[doc(hidden)]
 mod payload;
 
+#[doc(hidden)]
+mod nested;
+
 #[doc(hidden)]
 pub mod server;
 
 #[doc(hidden)]
 pub mod client;
 
+#[doc(hidden)]
+pub mod decoder;
+
+#[doc(hidden)]
+pub mod connection;
+
+#[cfg(feature = "async")]
+#[doc(hidden)]
+pub mod codec;
+
+#[doc(hidden)]
+pub mod udp;
+
+#[doc(hidden)]
+pub mod clock;
+
+#[doc(hidden)]
+pub mod reliable;
+
+#[doc(hidden)]
+pub mod handshake;
+
+#[doc(hidden)]
+pub mod field;
+
+#[doc(hidden)]
+pub mod fragment;
+
+#[doc(hidden)]
+pub mod batch;
+
+#[doc(hidden)]
+pub mod view;
+
+#[doc(hidden)]
+pub mod compression;
+
+#[doc(hidden)]
+pub mod error_code;
+
+#[doc(hidden)]
+pub mod heartbeat;
+
+#[doc(hidden)]
+pub mod clock_sync;
+
+#[doc(hidden)]
+pub mod rpc;
+
+#[doc(hidden)]
+pub mod rate_limit;
+
+#[doc(hidden)]
+pub mod schema;
+
+#[doc(hidden)]
+pub mod compat;
+
+#[doc(hidden)]
+pub mod dissector;
+
+#[cfg(feature = "secure")]
+#[doc(hidden)]
+pub mod secure;
+
+#[cfg(feature = "auth")]
+#[doc(hidden)]
+pub mod auth;
+
 // Re-export
 pub use error::Error as Error;
+pub use error::ErrorKind as ErrorKind;
+pub use error::ErrorContext as ErrorContext;
+pub use error_code::ServerErrorCode as ServerErrorCode;
+pub use error_code::ErrorCategory as ErrorCategory;
 pub use server::ServerMessage as ServerMessage;
 pub use server::ServerPayload as ServerPayload;
+pub use server::ServerMessageRef as ServerMessageRef;
+pub use server::ServerPayloadRef as ServerPayloadRef;
 pub use client::ClientMessage as ClientMessage;
 pub use client::ClientPayload as ClientPayload;
+pub use client::ClientMessageRef as ClientMessageRef;
+pub use client::ClientPayloadRef as ClientPayloadRef;
+pub use message::Message as Message;
+pub use field::PayloadField as PayloadField;
+pub use ethos_derive::EthosPayload as EthosPayload;
+pub use decoder::MessageDecoder as MessageDecoder;
+pub use connection::Connection as Connection;
+pub use connection::ClientConnection as ClientConnection;
+pub use connection::ServerConnection as ServerConnection;
+pub use udp::DatagramSocket as DatagramSocket;
+pub use udp::Datagram as Datagram;
+pub use udp::UdpChannel as UdpChannel;
+pub use udp::ClientUdpChannel as ClientUdpChannel;
+pub use udp::ServerUdpChannel as ServerUdpChannel;
+pub use udp::UDP_MAX_PEERS as UDP_MAX_PEERS;
+pub use clock::Clock as Clock;
+pub use clock::MonotonicClock as MonotonicClock;
+pub use clock::ManualClock as ManualClock;
+pub use reliable::Delivery as Delivery;
+pub use reliable::ReliableEndpoint as ReliableEndpoint;
+pub use reliable::ClientReliableEndpoint as ClientReliableEndpoint;
+pub use reliable::ServerReliableEndpoint as ServerReliableEndpoint;
+pub use reliable::RELIABLE_RECEIVE_WINDOW as RELIABLE_RECEIVE_WINDOW;
+pub use handshake::Handshake as Handshake;
+pub use handshake::PROTOCOL_VERSION as PROTOCOL_VERSION;
+pub use handshake::SCHEMA_HASH as SCHEMA_HASH;
+pub use fragment::Fragmenter as Fragmenter;
+pub use fragment::Reassembler as Reassembler;
+pub use fragment::FRAGMENT_MAX_DATA_SIZE as FRAGMENT_MAX_DATA_SIZE;
+pub use batch::MessageBatch as MessageBatch;
+pub use batch::BatchIter as BatchIter;
+pub use view::FieldRef as FieldRef;
+pub use view::PayloadView as PayloadView;
+pub use view::ListRef as ListRef;
+pub use view::ListIter as ListIter;
+pub use compression::Codec as Codec;
+pub use compression::Compressor as Compressor;
+pub use compression::COMPRESSION_THRESHOLD as COMPRESSION_THRESHOLD;
+pub use heartbeat::Heartbeat as Heartbeat;
+pub use heartbeat::ClientHeartbeat as ClientHeartbeat;
+pub use heartbeat::ServerHeartbeat as ServerHeartbeat;
+pub use heartbeat::Health as Health;
+pub use heartbeat::KeepAlive as KeepAlive;
+pub use heartbeat::HEARTBEAT_INTERVAL as HEARTBEAT_INTERVAL;
+pub use heartbeat::HEARTBEAT_TIMEOUT as HEARTBEAT_TIMEOUT;
+pub use clock_sync::ClockSync as ClockSync;
+pub use clock_sync::TimeSample as TimeSample;
+pub use clock_sync::time_response as time_response;
+pub use clock_sync::CLOCK_SYNC_WINDOW as CLOCK_SYNC_WINDOW;
+pub use rpc::RpcClient as RpcClient;
+pub use rpc::PendingReply as PendingReply;
+pub use rpc::open_request as open_request;
+pub use rpc::reply as reply;
+pub use rpc::RPC_TIMEOUT as RPC_TIMEOUT;
+pub use rate_limit::RateLimit as RateLimit;
+pub use rate_limit::RateLimitPolicy as RateLimitPolicy;
+pub use rate_limit::RateLimiter as RateLimiter;
+pub use rate_limit::TokenBucket as TokenBucket;
+pub use rate_limit::Verdict as Verdict;
+pub use schema::FieldSchema as FieldSchema;
+pub use schema::VariantSchema as VariantSchema;
+pub use schema::PayloadSchema as PayloadSchema;
+pub use schema::NestedSchema as NestedSchema;
+pub use schema::MessageSchema as MessageSchema;
+pub use schema::schema_json as schema_json;
+pub use schema::message_json as message_json;
+pub use schema::payload_json as payload_json;
+pub use compat::Snapshot as Snapshot;
+pub use compat::MessageSnapshot as MessageSnapshot;
+pub use compat::VariantSnapshot as VariantSnapshot;
+pub use compat::NestedSnapshot as NestedSnapshot;
+pub use compat::FieldSnapshot as FieldSnapshot;
+pub use compat::Change as Change;
+pub use compat::ChangeKind as ChangeKind;
+pub use compat::check_compatibility as check_compatibility;
+pub use dissector::wireshark_dissector as wireshark_dissector;
+#[cfg(feature = "secure")]
+pub use secure::KeyExchange as KeyExchange;
+#[cfg(feature = "secure")]
+pub use secure::Sealable as Sealable;
+#[cfg(feature = "secure")]
+pub use secure::SecureSession as SecureSession;
+#[cfg(feature = "secure")]
+pub use secure::ClientSession as ClientSession;
+#[cfg(feature = "secure")]
+pub use secure::ServerSession as ServerSession;
+#[cfg(feature = "secure")]
+pub use secure::KEY_SIZE as KEY_SIZE;
+#[cfg(feature = "secure")]
+pub use secure::TAG_SIZE as TAG_SIZE;
+#[cfg(feature = "secure")]
+pub use secure::REPLAY_WINDOW as REPLAY_WINDOW;
+#[cfg(feature = "auth")]
+pub use auth::Authenticator as Authenticator;
+#[cfg(feature = "auth")]
+pub use auth::InMemoryAuthenticator as InMemoryAuthenticator;
+#[cfg(feature = "auth")]
+pub use auth::LoginClient as LoginClient;
+#[cfg(feature = "auth")]
+pub use auth::LoginServer as LoginServer;
+#[cfg(feature = "auth")]
+pub use auth::Challenge as Challenge;
+#[cfg(feature = "auth")]
+pub use auth::Session as Session;
+#[cfg(feature = "auth")]
+pub use auth::SessionToken as SessionToken;
+#[cfg(feature = "auth")]
+pub use auth::login_proof as login_proof;
+#[cfg(feature = "auth")]
+pub use auth::TOKEN_LIFETIME as TOKEN_LIFETIME;
+#[cfg(feature = "async")]
+pub use codec::MessageCodec as MessageCodec;
+#[cfg(feature = "async")]
+pub use codec::ClientCodec as ClientCodec;
+#[cfg(feature = "async")]
+pub use codec::ServerCodec as ServerCodec;
+#[cfg(feature = "async")]
+pub use codec::MessageReader as MessageReader;
+#[cfg(feature = "async")]
+pub use codec::MessageWriter as MessageWriter;
 
 /// Size of type of size of payload
 pub const MESSAGE_SIZE_TYPE_SIZE : usize = size_of::<u16>();
@@ -57,6 +255,13 @@ pub const SERVER_MSG_BUFFER_SIZE : usize = 1024*1024;
 /// Maximum permitted client message in bytes (1ko).
 pub const CLIENT_MSG_MAX_SIZE : usize = 1024;
 
+/// Maximum length in bytes of an account name sent with [`ClientPayload::Login`].
+pub const ACCOUNT_MAX_LEN : usize = 64;
+
+/// Default maximum size in bytes of a datagram sent on [`UDP_PORT`].
+/// 
+/// Small enough to avoid IP fragmentation on most networks.
+pub const UDP_MTU : usize = 1200;
 
 /// Ethos TCP port 3847.
 /// 
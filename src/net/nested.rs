@@ -0,0 +1,478 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+/// This macro generate a struct usable as payload field type, like `pos : Vec3`.
+/// 
+/// The struct is serialized through [`Tampon`](tampon::Tampon) and implements [`PayloadField`](crate::net::PayloadField),
+/// so its fields take part in size limits and in the tests generated by [`write_messages_payloads!`](crate::write_messages_payloads).
+/// 
+/// # Note(s)
+/// * Fields can be of any [`PayloadField`](crate::net::PayloadField) type and declare a maximum length with `name : Type [max N]`.
+/// * Payload views read it by value, see [`FieldRef`](crate::net::FieldRef).
+/// * Each field must implement trait [std::default::Default].
+/// 
+/// # Example(s)
+/// ```
+/// ethos_core::write_payload_struct!{
+///     /// Position in world.
+///     pub Vec3 { pub x : f32, pub y : f32, pub z : f32 }
+/// }
+/// 
+/// let pos = Vec3 { x: 1.0, y: 2.0, z: 3.0 };
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! write_payload_struct {
+
+    ( $(#[$($comment:tt)*])* $vis:vis $struct_name : ident { $( $(#[$($attr_field:tt)*])* $fvis:vis $fname : ident : $ftype : ty $([max $max:expr])? ),* $(,)? } ) => {
+
+        $( #[$($comment)*] )*
+        #[derive(Debug, PartialEq, Clone, Default)]
+        $vis struct $struct_name {
+            $(
+                $(
+                    #[$($attr_field)*]
+                )*
+                $fvis $fname : $ftype
+            ),*
+        }
+
+        impl tampon::Tampon for $struct_name {
+            fn bytes_size(&self) -> usize {
+                0 $( + $crate::net::PayloadField::field_size(&self.$fname) )*
+            }
+
+            fn serialize(&self, buffer : &mut [u8]) -> usize {
+                let mut _size_written = 0;
+                $(
+                    _size_written += $crate::net::PayloadField::write_field(&self.$fname, &mut buffer[_size_written..]);
+                )*
+                _size_written
+            }
+
+            fn deserialize(buffer : &[u8]) -> (Self, usize) {
+                let mut _bytes_read = 0;
+                $(
+                    let ($fname, field_size) = <$ftype as $crate::net::PayloadField>::read_field(&buffer[_bytes_read..]);
+                    _bytes_read += field_size;
+                )*
+                ($struct_name { $( $fname ),* }, _bytes_read)
+            }
+
+            fn deserialize_size(buffer : &[u8], max_size : usize) -> Result<usize, tampon::TamponError> {
+                let mut _size = 0;
+                $(
+                    _size += <$ftype as $crate::net::PayloadField>::read_field_size(&buffer[_size..], usize::MAX $(.min($max))?)?;
+                    if max_size > 0 && _size > max_size {
+                        return Err(tampon::TamponError::DeserializeSizeGreaterThanMax);
+                    }
+                )*
+                Ok(_size)
+            }
+        }
+
+        impl $crate::net::PayloadField for $struct_name {
+            const SCHEMA_HASH : u64 = {
+                let mut _hash = $crate::net::handshake::schema_hash(stringify!($struct_name { $( $fname : $ftype $([max $max])? ),* }));
+                $(
+                    _hash = $crate::net::handshake::combine_hash(_hash, <$ftype as $crate::net::PayloadField>::SCHEMA_HASH);
+                )*
+                _hash
+            };
+
+            const NESTED : Option<&'static $crate::net::NestedSchema> = Some(&$crate::net::NestedSchema {
+                name : stringify!($struct_name),
+                fields : &[
+                    $(
+                        $crate::net::FieldSchema {
+                            name : stringify!($fname),
+                            ty : stringify!($ftype),
+                            size : <$ftype as $crate::net::PayloadField>::FIXED_SIZE,
+                            max : $crate::schema_max!($($max)?),
+                            optional : false,
+                            nested : <$ftype as $crate::net::PayloadField>::NESTED,
+                            doc : $crate::schema_doc!($( [$($attr_field)*] )*),
+                        }
+                    ),*
+                ],
+                variants : &[],
+                doc : $crate::schema_doc!($( [$($comment)*] )*),
+            });
+
+            fn field_size(&self) -> usize {
+                tampon::Tampon::bytes_size(self)
+            }
+
+            fn write_field(&self, buffer : &mut [u8]) -> usize {
+                tampon::Tampon::serialize(self, buffer)
+            }
+
+            fn read_field(buffer : &[u8]) -> (Self, usize) {
+                <Self as tampon::Tampon>::deserialize(buffer)
+            }
+
+            fn read_field_size(buffer : &[u8], _max_len : usize) -> Result<usize, tampon::TamponError> {
+                <Self as tampon::Tampon>::deserialize_size(buffer, 0)
+            }
+
+            fn within_limits(&self, _max_len : usize) -> bool {
+                true $( && $crate::net::PayloadField::within_limits(&self.$fname, usize::MAX $(.min($max))?) )*
+            }
+
+            fn sample(_len : usize) -> Self {
+                $struct_name { $( $fname : <$ftype as $crate::net::PayloadField>::sample(_len $(.min($max))?) ),* }
+            }
+        }
+
+        $crate::impl_nested_field_ref!($struct_name);
+    };
+}
+
+/// This macro generate a fieldless or data-carrying enum usable as payload field type, like `state : UnitState`.
+/// 
+/// Each variant needs a unique u8 value, packed before its fields. The enum is serialized through 
+/// [`Tampon`](tampon::Tampon) and implements [`PayloadField`](crate::net::PayloadField), so its fields take part in size limits 
+/// and in the tests generated by [`write_messages_payloads!`](crate::write_messages_payloads).
+/// 
+/// # Note(s)
+/// * Fields can be of any [`PayloadField`](crate::net::PayloadField) type and declare a maximum length with `name : Type [max N]`.
+/// * Payload views read it by value, see [`FieldRef`](crate::net::FieldRef).
+/// * Default value is the first variant with default fields.
+/// * Unknown values are reported as incomplete, like unknown payloads.
+/// 
+/// # Example(s)
+/// ```
+/// ethos_core::write_payload_enum!{
+///     /// State of a unit.
+///     pub UnitState,
+///     Idle = 0,
+///     Moving { speed : f32 } = 1
+/// }
+/// 
+/// let state = UnitState::Moving { speed: 1.5 };
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! write_payload_enum {
+
+    ( $(#[$($comment:tt)*])* $vis:vis $enum_name : ident, $( $(#[$($attr:tt)*])* $variant : ident $({ $( $(#[$($attr_field:tt)*])* $fname : ident : $ftype : ty $([max $max:expr])? ),* })? = $value:expr),+ $(,)? ) => {
+
+        $( #[$($comment)*] )*
+        #[repr(u8)]
+        #[derive(Debug, PartialEq, Clone)]
+        $vis enum $enum_name {
+            $(
+                $(
+                    #[$($attr)*]
+                )*
+                $variant $({
+                    $(
+                        $(
+                            #[$($attr_field)*]
+                        )*
+                        $fname : $ftype
+                    ),*
+                })? = $value,
+            )+
+        }
+
+        impl $enum_name {
+            /// Returns a value uniquely identifying the enum variant
+            pub fn discriminant(&self) -> u8 {
+                unsafe { *(self as *const Self as *const u8)}
+            }
+        }
+
+        impl Default for $enum_name {
+            fn default() -> Self {
+                <Self as $crate::net::PayloadField>::sample(0)
+            }
+        }
+
+        impl tampon::Tampon for $enum_name {
+            fn bytes_size(&self) -> usize {
+                match self {
+                    $(
+                        $enum_name::$variant $({ $( $fname ),* })? => 
+                            size_of::<u8>() $($( + $crate::net::PayloadField::field_size($fname) )*)?,
+                    )+
+                }
+            }
+
+            fn serialize(&self, buffer : &mut [u8]) -> usize {
+                let mut size_written = $crate::net::PayloadField::write_field(&self.discriminant(), buffer);
+
+                match self {
+                    $(
+                        $enum_name::$variant $({ $( $fname ),* })? => {
+                            $($(
+                                size_written += $crate::net::PayloadField::write_field($fname, &mut buffer[size_written..]);
+                            )*)?
+                        },
+                    )+
+                }
+
+                size_written
+            }
+
+            /// Buffer must have been validated with [`deserialize_size`](tampon::Tampon::deserialize_size).
+            fn deserialize(buffer : &[u8]) -> (Self, usize) {
+                let (discriminant, mut _bytes_read) = <u8 as $crate::net::PayloadField>::read_field(buffer);
+
+                match discriminant {
+                    $(
+                        $value => {
+                            $($(
+                                let ($fname, field_size) = <$ftype as $crate::net::PayloadField>::read_field(&buffer[_bytes_read..]);
+                                _bytes_read += field_size;
+                            )*)?
+                            ($enum_name::$variant $({ $( $fname ),* })?, _bytes_read)
+                        },
+                    )+
+                    _ => unreachable!("Unknown discriminant should have been refused by deserialize_size"),
+                }
+            }
+
+            fn deserialize_size(buffer : &[u8], max_size : usize) -> Result<usize, tampon::TamponError> {
+                let discriminant = buffer.first().ok_or(tampon::TamponError::DeserializeSizeBufferIncomplete)?;
+
+                match discriminant {
+                    $(
+                        $value => {
+                            let mut _size = size_of::<u8>();
+                            if max_size > 0 && _size > max_size {
+                                return Err(tampon::TamponError::DeserializeSizeGreaterThanMax);
+                            }
+                            $($(
+                                _size += <$ftype as $crate::net::PayloadField>::read_field_size(&buffer[_size..], usize::MAX $(.min($max))?)?;
+                                if max_size > 0 && _size > max_size {
+                                    return Err(tampon::TamponError::DeserializeSizeGreaterThanMax);
+                                }
+                            )*)?
+                            Ok(_size)
+                        },
+                    )+
+                    // Unknown discriminant
+                    _ => Err(tampon::TamponError::DeserializeSizeBufferIncomplete),
+                }
+            }
+        }
+
+        impl $crate::net::PayloadField for $enum_name {
+            const SCHEMA_HASH : u64 = {
+                let mut _hash = $crate::net::handshake::schema_hash(concat!(stringify!($enum_name) $(, ",", stringify!($variant $({ $( $fname : $ftype $([max $max])? ),* })? = $value))+));
+                $($($(
+                    _hash = $crate::net::handshake::combine_hash(_hash, <$ftype as $crate::net::PayloadField>::SCHEMA_HASH);
+                )*)?)+
+                _hash
+            };
+
+            const NESTED : Option<&'static $crate::net::NestedSchema> = Some(&$crate::net::NestedSchema {
+                name : stringify!($enum_name),
+                fields : &[],
+                variants : &[
+                    $(
+                        $crate::net::VariantSchema {
+                            name : stringify!($variant),
+                            discriminant : $value as u16,
+                            fields : &[
+                                $($(
+                                    $crate::net::FieldSchema {
+                                        name : stringify!($fname),
+                                        ty : stringify!($ftype),
+                                        size : <$ftype as $crate::net::PayloadField>::FIXED_SIZE,
+                                        max : $crate::schema_max!($($max)?),
+                                        optional : false,
+                                        nested : <$ftype as $crate::net::PayloadField>::NESTED,
+                                        doc : $crate::schema_doc!($( [$($attr_field)*] )*),
+                                    }
+                                ),*)?
+                            ],
+                            doc : $crate::schema_doc!($( [$($attr)*] )*),
+                        }
+                    ),+
+                ],
+                doc : $crate::schema_doc!($( [$($comment)*] )*),
+            });
+
+            fn field_size(&self) -> usize {
+                tampon::Tampon::bytes_size(self)
+            }
+
+            fn write_field(&self, buffer : &mut [u8]) -> usize {
+                tampon::Tampon::serialize(self, buffer)
+            }
+
+            fn read_field(buffer : &[u8]) -> (Self, usize) {
+                <Self as tampon::Tampon>::deserialize(buffer)
+            }
+
+            fn read_field_size(buffer : &[u8], _max_len : usize) -> Result<usize, tampon::TamponError> {
+                <Self as tampon::Tampon>::deserialize_size(buffer, 0)
+            }
+
+            fn within_limits(&self, _max_len : usize) -> bool {
+                match self {
+                    $(
+                        $enum_name::$variant $({ $( $fname ),* })? => 
+                            true $($( && $crate::net::PayloadField::within_limits($fname, usize::MAX $(.min($max))?) )*)?,
+                    )+
+                }
+            }
+
+            /// Variant is chosen by `len`, so that samples of different length cover different variants.
+            fn sample(len : usize) -> Self {
+                let variants : &[fn(usize) -> Self] = &[ $( |_len| $enum_name::$variant $({ $( $fname : <$ftype as $crate::net::PayloadField>::sample(_len $(.min($max))?) ),* })? ),+ ];
+                variants[len % variants.len()](len)
+            }
+        }
+
+        $crate::impl_nested_field_ref!($enum_name);
+    };
+}
+
+
+/// This module test [write_payload_struct!] and [write_payload_enum!] used as payload fields.
+/// 
+/// # Verification(s)
+/// V1 : Nested struct and enum fields give back the original payload for every enum variant.
+/// V2 : Nested fields exceeding their maximum length are refused by [`Message::pack_bytes`] and [`Message::from_bytes`].
+/// V3 : Unknown enum value is refused by [`Tampon::deserialize_size`].
+/// V4 : Default enum value is the first variant.
+/// V5 : Schema hash of payload includes definitions of nested types.
+/// V6 : Layout of payload includes layouts of nested types once, in order of first use.
+#[cfg(test)]
+mod tests {
+    use tampon::Tampon;
+
+    use crate::net::{handshake::schema_hash, Error, ErrorKind, Message, PayloadField, MESSAGE_SIZE_TYPE_SIZE};
+
+    crate::write_payload_struct!{
+        /// Position used for tests.
+        pub Vec3 { x : f32, y : f32, z : f32 }
+    }
+
+    crate::write_payload_struct!{
+        /// Struct with limited fields used for tests.
+        pub Tag { name : String [max 8], values : Vec<i16> [max 4] }
+    }
+
+    crate::write_payload_enum!{
+        /// State of unit used for tests.
+        pub UnitState,
+        Idle = 0,
+        Moving { speed : f32, target : Vec3 } = 1,
+        Tagged { tag : Tag } = 7
+    }
+
+    crate::write_messages_payloads!{
+        /// Payloads with nested fields used for tests.
+        NestedPayload,
+        Unit { pos : Vec3, state : UnitState, tags : Vec<Tag> [max 2] } = 1,
+        Invalid = 65535
+    }
+
+    crate::write_messages_struct!{ 256,
+        /// Message with nested fields used for tests.
+        NestedMessage < NestedPayload >
+    }
+
+    fn unit(state : UnitState, name : &str) -> NestedMessage {
+        NestedMessage::new(NestedPayload::Unit { 
+            pos: Vec3 { x: 1.0, y: -2.0, z: 3.5 }, state, 
+            tags: vec![Tag { name: String::from(name), values: vec![1, -1] }] 
+        })
+    }
+
+    fn round_trip(message : &NestedMessage) -> Result<NestedMessage, Error> {
+        let mut buffer = vec![0u8; MESSAGE_SIZE_TYPE_SIZE + message.size()];
+        message.pack_bytes(&mut buffer)?;
+        NestedMessage::from_bytes(&buffer[MESSAGE_SIZE_TYPE_SIZE..])
+    }
+
+    #[test]
+    fn v1_round_trip() {
+        // V1 : Nested struct and enum fields give back the original payload for every enum variant.
+        for state in [
+            UnitState::Idle, 
+            UnitState::Moving { speed: 4.0, target: Vec3 { x: 0.5, y: 0.0, z: -1.0 } }, 
+            UnitState::Tagged { tag: Tag { name: String::from("boss"), values: vec![3] } }
+        ] {
+            let message = unit(state, "ally");
+            assert_eq!(round_trip(&message).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn v2_nested_greater_than_limit() {
+        // V2 : Nested fields exceeding their maximum length are refused by [`Message::pack_bytes`] and [`Message::from_bytes`].
+        let message = unit(UnitState::Idle, "too long name");
+        assert_eq!(round_trip(&message), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+
+        let state = UnitState::Tagged { tag: Tag { name: String::from("abc"), values: vec![0; 5] } };
+        assert!(!state.within_limits(usize::MAX));
+        let mut buffer = vec![0u8; state.field_size()];
+        state.write_field(&mut buffer);
+        assert_eq!(UnitState::read_field_size(&buffer, usize::MAX), Err(tampon::TamponError::DeserializeSizeGreaterThanMax));
+    }
+
+    #[test]
+    fn v3_unknown_enum_value() {
+        // V3 : Unknown enum value is refused by [`Tampon::deserialize_size`].
+        assert_eq!(UnitState::deserialize_size(&[2], 0), Err(tampon::TamponError::DeserializeSizeBufferIncomplete));
+        assert_eq!(UnitState::deserialize_size(&[], 0), Err(tampon::TamponError::DeserializeSizeBufferIncomplete));
+        assert_eq!(UnitState::deserialize_size(&[0], 0), Ok(1));
+    }
+
+    #[test]
+    fn v4_default() {
+        // V4 : Default enum value is the first variant.
+        assert_eq!(UnitState::default(), UnitState::Idle);
+    }
+
+    #[test]
+    fn v5_schema_hash() {
+        // V5 : Schema hash of payload includes definitions of nested types.
+        assert_ne!(Vec3::SCHEMA_HASH, 0);
+        assert_ne!(Vec3::SCHEMA_HASH, Tag::SCHEMA_HASH);
+        assert_eq!(<Vec<Tag> as PayloadField>::SCHEMA_HASH, Tag::SCHEMA_HASH);
+        assert_ne!(NestedPayload::SCHEMA_HASH, schema_hash(NestedPayload::SCHEMA));
+        assert_eq!(<u32 as PayloadField>::SCHEMA_HASH, 0);
+    }
+
+    #[test]
+    fn v6_layout() {
+        // V6 : Layout of payload includes layouts of nested types once, in order of first use.
+        let nested = NestedPayload::LAYOUT.nested();
+        assert_eq!(nested.iter().map(|nested| nested.name).collect::<Vec<_>>(), vec!["Vec3", "UnitState", "Tag"]);
+        assert_eq!(nested[0].doc, " Position used for tests.\n");
+        assert!(nested[0].variants.is_empty());
+        assert_eq!(nested[1].variants.iter().map(|variant| variant.discriminant).collect::<Vec<_>>(), vec![0, 1, 7]);
+        assert_eq!(nested[1].variants[1].fields[1].nested, Some(nested[0]));
+        assert_eq!(nested[2].fields[0].max, Some(8));
+        assert_eq!(<Vec<Tag> as PayloadField>::NESTED, Tag::NESTED);
+        assert_eq!(<u32 as PayloadField>::NESTED, None);
+    }
+}
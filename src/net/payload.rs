@@ -26,27 +26,142 @@ SOFTWARE.
 
 /// This macro generate payloads code for bytes serialization. This help adding new payload quickly.
 ///
+/// Fields can be of any type implementing [`PayloadField`](crate::net::PayloadField), like numerics, [`String`], 
+/// [`Vec<T>`], [`Box<[u8]>`] and fixed arrays `[T; N]`. Variable-length fields can declare a maximum length 
+/// with `name : Type [max N]`, enforced when packing and unpacking.
+///
+/// [`Option<T>`] fields have their presence packed in a bitmap following the discriminant, one bit per
+/// optional field, so absent fields cost only a bit.
+///
+/// [`EthosPayload`](crate::net::EthosPayload) derive generates the same wire format with readable diagnostics.
+///
+/// A borrowed view `<Name>Ref<'a>` is generated alongside, reading fields straight from the packed bytes, 
+/// see [`PayloadView`](crate::net::PayloadView).
+///
+/// Its layout, with discriminants, field types and doc comments, is described by `LAYOUT`, see [`PayloadSchema`](crate::net::PayloadSchema).
+///
 /// # Note(s)
-/// * Array MUST be wrapped in a Tampon trait object!
 /// * Each payload parameter must implement trait [std::default::Default] and #[derive(PartialEq)] for tests purpose.
+/// * Maximum length must only be declared on variable-length fields.
 #[doc(hidden)]
 #[macro_export]
 macro_rules! write_messages_payloads {
 
-    ( $(#[$comment:meta])* $payload_name : ident, $( $(#[$attr:meta])* $payload : ident $({ $( $(#[$attr_field:meta])* $pname : ident : $ptype : ident ),* })? = $value:expr),+ ) => {
+    // View of payloads without fields doesn't borrow
+    (@view [$({})*] $($input:tt)*) => {
+        $crate::write_messages_payloads!{ @view_lifetime [] $($input)* }
+    };
+
+    (@view [$($fields:tt)*] $($input:tt)*) => {
+        $crate::write_messages_payloads!{ @view_lifetime ['a] $($input)* }
+    };
+
+    (@view_lifetime [$($lt:lifetime)?] $payload_name : ident, $( $(#[$($attr:tt)*])* $payload : ident $({ $( $(#[$($attr_field:tt)*])* $pname : ident : $ptype : ty $([max $max:expr])? ),* })? = $value:expr),+ ) => {
+        $crate::concat_idents::concat_idents!(payload_ref = $payload_name, Ref {
+            #[doc = concat!("Borrowed view of [`", stringify!($payload_name), "`], with fields read straight from the packed bytes.")]
+            /// 
+            /// See [`FieldRef`](crate::net::FieldRef) for the view of each field type.
+            #[derive(Debug, PartialEq, Clone)]
+            pub enum payload_ref<$($lt)?> {
+                $(
+                    $(
+                        #[$($attr)*]
+                    )*
+                    $payload $({
+                        $(
+                            $(
+                                #[$($attr_field)*]
+                            )*
+                            $pname : <$ptype as $crate::net::FieldRef<'a>>::Ref
+                        ),*
+                    })?,
+                )+
+            }
+
+            impl<$($lt)?> payload_ref<$($lt)?> {
+                /// View of payload packed at the beginning of buffer.
+                /// 
+                /// Buffer must have been validated with [`deserialize_size`](tampon::Tampon::deserialize_size).
+                /// 
+                /// # Returns
+                /// View and count of bytes read, which is 0 for unknown discriminant.
+                pub fn read(buffer : &$($lt)? [u8]) -> (Self, usize) {
+                    let (discriminant, mut _bytes_size) = <u16 as $crate::net::PayloadField>::read_field(buffer);
+
+                    match discriminant {
+                        $(
+                            $value => {
+                                $(
+                                    let bitmap = _bytes_size;
+                                    let mut _bit = 0;
+                                    _bytes_size += (0 $( + <$ptype as $crate::net::PayloadField>::OPTIONAL as usize )*).div_ceil(8);
+
+                                    $(
+                                        let present = !<$ptype as $crate::net::PayloadField>::OPTIONAL || {
+                                            let present = buffer[bitmap + _bit / 8] & (1 << (_bit % 8)) != 0;
+                                            _bit += 1;
+                                            present
+                                        };
+                                        let ($pname, field_size) = <$ptype as $crate::net::FieldRef<'a>>::read_bitmap_ref(&buffer[_bytes_size..], present);
+                                        _bytes_size += field_size;
+                                    )*
+                                )?
+                                (payload_ref::$payload $({ $( $pname ),* })?, _bytes_size)
+                            },
+                        )+
+                        _ =>  (payload_ref::Invalid, 0) // Invalid payload
+                    }
+                }
+
+                /// Returns a value uniquely identifying the enum variant, same as the owned payload.
+                pub fn discriminant(&self) -> u16 {
+                    match self {
+                        $(
+                            payload_ref::$payload $({ $( $pname : _ ),* })? => $value,
+                        )+
+                    }
+                }
+
+                #[doc = concat!("Owned [`", stringify!($payload_name), "`] of the view.")]
+                pub fn into_owned(self) -> $payload_name {
+                    match self {
+                        $(
+                            payload_ref::$payload $({ $( $pname ),* })? => $payload_name::$payload $({ 
+                                $( $pname : <$ptype as $crate::net::FieldRef<'a>>::from_ref($pname) ),* 
+                            })?,
+                        )+
+                    }
+                }
+            }
+
+            impl<'a> $crate::net::PayloadView<'a> for $payload_name {
+                type Ref = payload_ref<$($lt)?>;
 
-        $( #[$comment] )*
+                fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+                    payload_ref::read(buffer)
+                }
+
+                fn from_ref(view : Self::Ref) -> Self {
+                    view.into_owned()
+                }
+            }
+        });
+    };
+
+    ( $(#[$($comment:tt)*])* $payload_name : ident, $( $(#[$($attr:tt)*])* $payload : ident $({ $( $(#[$($attr_field:tt)*])* $pname : ident : $ptype : ty $([max $max:expr])? ),* })? = $value:expr),+ ) => {
+
+        $( #[$($comment)*] )*
         #[repr(u16)]
         #[derive(Debug, PartialEq, Clone)]
         pub enum $payload_name {
             $(
                 $(
-                    #[$attr]
+                    #[$($attr)*]
                 )*
                 $payload $({
                     $(
                         $(
-                            #[$attr_field]
+                            #[$($attr_field)*]
                         )*
                         $pname : $ptype
                     ),*
@@ -57,52 +172,73 @@ macro_rules! write_messages_payloads {
         impl Tampon for $payload_name {
             fn bytes_size(&self) -> usize {
                 
-                match self.discriminant() {
+                match self {
                     $(
-                        $value => tampon::bytes_size!( $( $(($pname):$ptype),*)? ) + $crate::net::DISCRIMINANT_TYPE_SIZE,
+                        $payload_name::$payload $({ $( $pname ),* })? => 
+                            $crate::net::DISCRIMINANT_TYPE_SIZE $( 
+                                + (0 $( + <$ptype as $crate::net::PayloadField>::OPTIONAL as usize )*).div_ceil(8)
+                                $( + $crate::net::PayloadField::bitmap_field_size($pname) )* 
+                            )?,
                     )+
-                    _ =>  0 // Invalid payload
                 }
 
             }
 
             fn serialize(&self, buffer : &mut [u8]) -> usize {
 
-                // Pack payload to bytes
+                // Pack discriminant, presence bitmap of optional fields then each field
+                let mut _size_written = $crate::net::PayloadField::write_field(&self.discriminant(), buffer);
+
                 match self {
                     $(
-                        $payload_name::$payload $({
+                        $payload_name::$payload $({ $( $pname ),* })? => {
                             $(
-                                $pname
-                            ),*
-                        })? => {
-                            tampon::serialize!(buffer, size_written, (self.discriminant()):u16
-                            $($(
-                                ,(*$pname):$ptype
-                            )*)?);
-                            size_written
+                                let bitmap = _size_written;
+                                let mut _bit = 0;
+                                _size_written += (0 $( + <$ptype as $crate::net::PayloadField>::OPTIONAL as usize )*).div_ceil(8);
+                                buffer[bitmap.._size_written].fill(0);
+
+                                $(
+                                    let (_present, field_size) = $crate::net::PayloadField::write_bitmap_field($pname, &mut buffer[_size_written..]);
+                                    _size_written += field_size;
+                                    if <$ptype as $crate::net::PayloadField>::OPTIONAL {
+                                        buffer[bitmap + _bit / 8] |= (_present as u8) << (_bit % 8);
+                                        _bit += 1;
+                                    }
+                                )*
+                            )?
                         },
                     )+
                 }
 
+                _size_written
+
             }
 
             fn deserialize(buffer : &[u8]) -> (Self, usize) {
                 
                 // Read discriminant
-                tampon::deserialize!(buffer, (discriminant):u16);
+                let (discriminant, mut _bytes_size) = <u16 as $crate::net::PayloadField>::read_field(buffer);
 
                 match discriminant {
                     $(
                         $value => {
+                            $(
+                                let bitmap = _bytes_size;
+                                let mut _bit = 0;
+                                _bytes_size += (0 $( + <$ptype as $crate::net::PayloadField>::OPTIONAL as usize )*).div_ceil(8);
 
-                            tampon::deserialize!(buffer[$crate::net::DISCRIMINANT_TYPE_SIZE..], _bytes_size $(, $(($pname):$ptype),*)?);
-                            ( $payload_name::$payload $({
                                 $(
-                                    $pname
-                                ),*
-                            })?, _bytes_size + $crate::net::DISCRIMINANT_TYPE_SIZE)
-
+                                    let present = !<$ptype as $crate::net::PayloadField>::OPTIONAL || {
+                                        let present = buffer[bitmap + _bit / 8] & (1 << (_bit % 8)) != 0;
+                                        _bit += 1;
+                                        present
+                                    };
+                                    let ($pname, field_size) = <$ptype as $crate::net::PayloadField>::read_bitmap_field(&buffer[_bytes_size..], present);
+                                    _bytes_size += field_size;
+                                )*
+                            )?
+                            ( $payload_name::$payload $({ $( $pname ),* })?, _bytes_size)
                         },
                     )+
                     _ =>  ($payload_name::Invalid, 0) // Invalid payload
@@ -119,24 +255,35 @@ macro_rules! write_messages_payloads {
                     Err(tampon::TamponError::DeserializeSizeGreaterThanMax)
                 } else {
                     // Read discriminant
-                    tampon::deserialize!(buffer, (discriminant):u16);
+                    let (discriminant, _) = <u16 as $crate::net::PayloadField>::read_field(buffer);
 
                     match discriminant {
                         $(
                             $value => {
-                                let _max_size = {
-                                    if max_size > 0 {
-                                        max_size - $crate::net::DISCRIMINANT_TYPE_SIZE
-                                    } else {
-                                        0
+                                let mut _size = $crate::net::DISCRIMINANT_TYPE_SIZE;
+                                $(
+                                    let bitmap = _size;
+                                    let mut _bit = 0;
+                                    _size += (0 $( + <$ptype as $crate::net::PayloadField>::OPTIONAL as usize )*).div_ceil(8);
+                                    if buffer.len() < _size {
+                                        return Err(tampon::TamponError::DeserializeSizeBufferIncomplete);
+                                    } else if max_size > 0 && _size > max_size {
+                                        return Err(tampon::TamponError::DeserializeSizeGreaterThanMax);
                                     }
-                                };
-
-                                match tampon::deserialize_size!(buffer[$crate::net::DISCRIMINANT_TYPE_SIZE..], _max_size $(, $(($pname):$ptype),*)?) {
-                                    Ok(size) => Ok(size + $crate::net::DISCRIMINANT_TYPE_SIZE),
-                                    Err(err) => Err(err),
-                                }
 
+                                    $(
+                                        let present = !<$ptype as $crate::net::PayloadField>::OPTIONAL || {
+                                            let present = buffer[bitmap + _bit / 8] & (1 << (_bit % 8)) != 0;
+                                            _bit += 1;
+                                            present
+                                        };
+                                        _size += <$ptype as $crate::net::PayloadField>::read_bitmap_field_size(&buffer[_size..], present, usize::MAX $(.min($max))?)?;
+                                        if max_size > 0 && _size > max_size {
+                                            return Err(tampon::TamponError::DeserializeSizeGreaterThanMax);
+                                        }
+                                    )*
+                                )?
+                                Ok(_size)
                             },
                         )+
                         // Unknown discriminant
@@ -147,6 +294,48 @@ macro_rules! write_messages_payloads {
         }
 
         impl $payload_name {
+            /// Definition of each payload, used to compute [`Self::SCHEMA_HASH`].
+            #[allow(dead_code)]
+            pub const SCHEMA : &'static str = concat!(stringify!($payload_name) $(, ",", stringify!($payload $({ $( $pname : $ptype $([max $max])? ),* })? = $value))+);
+
+            /// Hash of [`Self::SCHEMA`] and nested field types. Differs as soon as a payload, field or discriminant differs.
+            #[allow(dead_code)]
+            pub const SCHEMA_HASH : u64 = {
+                let mut _hash = $crate::net::handshake::schema_hash(Self::SCHEMA);
+                $($($(
+                    _hash = $crate::net::handshake::combine_hash(_hash, <$ptype as $crate::net::PayloadField>::SCHEMA_HASH);
+                )*)?)+
+                _hash
+            };
+
+            /// Layout of each payload with its discriminant, fields and doc comments, see [`schema_json`](crate::net::schema_json).
+            pub const LAYOUT : $crate::net::PayloadSchema = $crate::net::PayloadSchema {
+                name : stringify!($payload_name),
+                variants : &[
+                    $(
+                        $crate::net::VariantSchema {
+                            name : stringify!($payload),
+                            discriminant : $value,
+                            fields : &[
+                                $($(
+                                    $crate::net::FieldSchema {
+                                        name : stringify!($pname),
+                                        ty : stringify!($ptype),
+                                        size : <$ptype as $crate::net::PayloadField>::FIXED_SIZE,
+                                        max : $crate::schema_max!($($max)?),
+                                        optional : <$ptype as $crate::net::PayloadField>::OPTIONAL,
+                                        nested : <$ptype as $crate::net::PayloadField>::NESTED,
+                                        doc : $crate::schema_doc!($( [$($attr_field)*] )*),
+                                    }
+                                ),*)?
+                            ],
+                            doc : $crate::schema_doc!($( [$($attr)*] )*),
+                        },
+                    )+
+                ],
+                doc : $crate::schema_doc!($( [$($comment)*] )*),
+            };
+
             /// Returns a value uniquely identifying the enum variant
             /// 
             /// # See also
@@ -167,8 +356,21 @@ macro_rules! write_messages_payloads {
 
             }
 
+            /// Returns true if every field is within its maximum length.
+            pub fn within_limits(&self) -> bool {
+                match self {
+                    $(
+                        $payload_name::$payload $({ $( $pname ),* })? => 
+                            true $($( && $crate::net::PayloadField::within_limits($pname, usize::MAX $(.min($max))?) )*)?,
+                    )+
+                }
+            }
+
         }
 
+        // View borrows bytes only if a payload has fields
+        $crate::write_messages_payloads!{ @view [$( $({ $($pname)* })? )+] $payload_name, $( $(#[$($attr)*])* $payload $({ $( $(#[$($attr_field)*])* $pname : $ptype $([max $max])? ),* })? = $value),+ }
+
         /// This module include tests for each [Payload] enum.
         /// 
         /// # Verification(s)
@@ -182,10 +384,29 @@ macro_rules! write_messages_payloads {
         /// V8 : [Payload::deserialize_size] size given must equal [Payload::bytes_size]
         /// V9 : [Payload::deserialize_size] should returns Err(DeserializeSizeBufferIncomplete)  on small buffer.
         /// V10 : [Payload::deserialize_size] should returns Err(DeserializeSizeGreaterThanMax) on small max_size.
+        /// V11 : [Payload] with sample values is within limits and give back the original payload.
+        /// V12 : [Payload::deserialize_size] should returns Err(DeserializeSizeBufferIncomplete) for every truncation.
+        /// V13 : [Payload] with fields exceeding maximum length is not within limits and returns Err(DeserializeSizeGreaterThanMax).
+        /// V14 : [PayloadView::read_ref] view of sample values gives back the original payload and size.
+        /// V15 : [Payload::LAYOUT] discriminant, fields and sizes match the packed bytes of sample values.
         #[cfg(test)]
         mod tests {
             use tampon::Tampon;
-            use super::$payload_name;
+            // Trait of fields, unused by payloads without fields
+            #[allow(unused_imports)]
+            use $crate::net::PayloadField;
+            // Payload and types of its fields
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// Length of sample values of variable-length fields.
+            const SAMPLE_LEN : usize = 16;
+
+            /// Length exceeding maximum length, if any.
+            #[allow(dead_code)]
+            fn oversize_len(max_len : usize) -> usize {
+                if max_len == usize::MAX { SAMPLE_LEN } else { max_len + 1 }
+            }
 
             $(
                 concat_idents::concat_idents!(test_name = payload_, $payload {
@@ -195,12 +416,12 @@ macro_rules! write_messages_payloads {
                         // V1 : [Payload] can be created with default values
                         let payload = super::$payload_name::$payload $({
                             $(
-                                $pname : $ptype::default()
+                                $pname : <$ptype>::default()
                             ),*
                         })?;
 
                         // V2 : [Payload::serialize] buffer write.
-                        let mut buffer = [0u8; size_of::<$payload_name>()];    // Create big enough buffer
+                        let mut buffer = vec![0u8; payload.bytes_size()];
                         let size = payload.serialize(&mut buffer);
 
                         // V3 : [Payload::serialize] size given must equal [Payload::bytes_size]
@@ -232,6 +453,74 @@ macro_rules! write_messages_payloads {
                             Err(err) => assert_eq!(err, tampon::TamponError::DeserializeSizeGreaterThanMax),
                         }
 
+                        // V11 : [Payload] with sample values is within limits and give back the original payload.
+                        let payload = super::$payload_name::$payload $({
+                            $(
+                                $pname : <$ptype as PayloadField>::sample(SAMPLE_LEN $(.min($max))?)
+                            ),*
+                        })?;
+                        assert!(payload.within_limits());
+
+                        let mut buffer = vec![0u8; payload.bytes_size()];
+                        assert_eq!(payload.serialize(&mut buffer), buffer.len());
+                        assert_eq!($payload_name::deserialize_size(&buffer, 0), Ok(buffer.len()));
+                        assert_eq!($payload_name::deserialize(&buffer), (payload.clone(), buffer.len()));
+
+                        // V14 : [PayloadView::read_ref] view of sample values gives back the original payload and size.
+                        let (view, view_size) = <$payload_name as $crate::net::PayloadView>::read_ref(&buffer);
+                        assert_eq!(view_size, buffer.len());
+                        assert_eq!(view.discriminant(), payload.discriminant());
+                        assert_eq!(view.into_owned(), payload);
+
+                        // V15 : [Payload::LAYOUT] discriminant, fields and sizes match the packed bytes of sample values.
+                        let layout = $payload_name::LAYOUT.variant_named(stringify!($payload)).unwrap();
+                        assert_eq!(layout.discriminant, payload.discriminant());
+                        assert_eq!(buffer[..$crate::net::DISCRIMINANT_TYPE_SIZE], layout.discriminant.to_le_bytes());
+
+                        #[allow(unused_mut)]
+                        let mut offset = $crate::net::DISCRIMINANT_TYPE_SIZE + layout.bitmap_size();
+                        let mut fields = layout.fields.iter();
+                        $(
+                            if let super::$payload_name::$payload { $( $pname ),* } = &payload {
+                                $(
+                                    let field = fields.next().unwrap();
+                                    assert_eq!(field.name, stringify!($pname));
+                                    assert_eq!(field.optional, <$ptype as PayloadField>::OPTIONAL);
+
+                                    let size = PayloadField::bitmap_field_size($pname);
+                                    if let Some(fixed) = field.size {
+                                        assert_eq!(size, fixed);
+                                    }
+
+                                    let mut packed = vec![0u8; size];
+                                    PayloadField::write_bitmap_field($pname, &mut packed);
+                                    assert_eq!(buffer[offset..offset + size], packed[..]);
+                                    offset += size;
+                                )*
+                            }
+                        )?
+                        assert!(fields.next().is_none());
+                        assert_eq!(offset, buffer.len());
+
+                        // V12 : [Payload::deserialize_size] should returns Err(DeserializeSizeBufferIncomplete) for every truncation.
+                        for cut in 0..buffer.len() {
+                            assert_eq!($payload_name::deserialize_size(&buffer[..cut], 0), Err(tampon::TamponError::DeserializeSizeBufferIncomplete));
+                        }
+
+                        // V13 : [Payload] with fields exceeding maximum length is not within limits and returns Err(DeserializeSizeGreaterThanMax).
+                        let limits : &[usize] = &[ $($( usize::MAX $(.min($max))? ),*)? ];
+                        if limits.iter().any(|max_len| *max_len != usize::MAX) {
+                            let payload = super::$payload_name::$payload $({
+                                $(
+                                    $pname : <$ptype as PayloadField>::sample(oversize_len(usize::MAX $(.min($max))?))
+                                ),*
+                            })?;
+                            assert!(!payload.within_limits());
+
+                            let mut buffer = vec![0u8; payload.bytes_size()];
+                            payload.serialize(&mut buffer);
+                            assert_eq!($payload_name::deserialize_size(&buffer, 0), Err(tampon::TamponError::DeserializeSizeGreaterThanMax));
+                        }
 
                     }
                 });
@@ -251,4 +540,234 @@ macro_rules! write_messages_payloads {
 
         }
     };
-}
\ No newline at end of file
+}
+
+
+/// This module test that [`EthosPayload`](crate::net::EthosPayload) derive is wire-compatible with write_messages_payloads! macro.
+/// 
+/// # Verification(s)
+/// V1 : Derived payload serialize to the same bytes as macro payload for every variant.
+/// V2 : Derived payload deserialize bytes of macro payload and vice versa.
+/// V3 : Derived payload and macro payload have the same [`SCHEMA_HASH`](crate::net::SCHEMA_HASH).
+/// V4 : Derived payload returns the same errors as macro payload for truncated, oversized and unknown bytes.
+/// V5 : Derived payload supports generic field types.
+/// V6 : Derived payload view reads the same fields and size as macro payload view.
+/// V7 : Derived payload and macro payload without fields have the same bytes and views.
+/// V8 : Derived payload has the same `LAYOUT` as macro payload, exported by [`payload_json`](crate::net::payload_json).
+#[cfg(test)]
+mod tests_derive {
+    use tampon::{Tampon, TamponError};
+
+    mod by_macro {
+        use tampon::Tampon;
+
+        crate::write_messages_payloads!{
+            /// Payload generated by macro.
+            Payload,
+            Variable { text : String [max 8], list : Vec<u32> [max 4], opt : Option<u64>, names : Option<Vec<String>>, fixed : [i16; 3] } = 1,
+            Test { p16 : u16, p32 : u32 } = 65534,
+            Invalid = 65535
+        }
+
+        pub mod unit {
+            use tampon::Tampon;
+
+            crate::write_messages_payloads!{
+                /// Payload without fields generated by macro.
+                Unit,
+                Ping = 1,
+                Pong = 2,
+                Invalid = 65535
+            }
+        }
+    }
+
+    mod by_derive {
+        use crate::net::EthosPayload;
+
+        /// Payload generated by derive.
+        #[derive(Debug, PartialEq, Clone, EthosPayload)]
+        pub enum Payload {
+            #[ethos(id = 1)]
+            Variable { 
+                #[ethos(max = 8)]
+                text : String, 
+                #[ethos(max = 4)]
+                list : Vec<u32>, 
+                opt : Option<u64>, 
+                names : Option<Vec<String>>, 
+                fixed : [i16; 3] 
+            },
+
+            #[ethos(id = 65534)]
+            Test { p16 : u16, p32 : u32 },
+
+            #[ethos(id = 65535)]
+            Invalid,
+        }
+
+        /// Generic payload generated by derive.
+        #[derive(Debug, PartialEq, Clone, EthosPayload)]
+        pub enum Generic<T> {
+            #[ethos(id = 1)]
+            Value { value : T, values : Vec<T> },
+
+            #[ethos(id = 65535)]
+            Invalid,
+        }
+
+        /// Payload without fields generated by derive.
+        #[derive(Debug, PartialEq, Clone, EthosPayload)]
+        pub enum Unit {
+            #[ethos(id = 1)]
+            Ping,
+
+            #[ethos(id = 2)]
+            Pong,
+
+            #[ethos(id = 65535)]
+            Invalid,
+        }
+    }
+
+    /// Pairs of equivalent payloads.
+    fn pairs() -> Vec<(by_macro::Payload, by_derive::Payload)> {
+        let names = Some(vec![String::from("a"), String::from("bcd")]);
+        vec![
+            (by_macro::Payload::Variable { text: String::from("abc"), list: vec![1, 2], opt: Some(3), names: names.clone(), fixed: [4, -5, 6] }, 
+                by_derive::Payload::Variable { text: String::from("abc"), list: vec![1, 2], opt: Some(3), names, fixed: [4, -5, 6] }),
+            (by_macro::Payload::Variable { text: String::new(), list: vec![], opt: None, names: None, fixed: [0; 3] }, 
+                by_derive::Payload::Variable { text: String::new(), list: vec![], opt: None, names: None, fixed: [0; 3] }),
+            (by_macro::Payload::Test { p16: 1, p32: 2 }, by_derive::Payload::Test { p16: 1, p32: 2 }),
+            (by_macro::Payload::Invalid, by_derive::Payload::Invalid),
+        ]
+    }
+
+    fn serialize<T : Tampon>(payload : &T) -> Vec<u8> {
+        let mut buffer = vec![0u8; payload.bytes_size()];
+        assert_eq!(payload.serialize(&mut buffer), buffer.len());
+        buffer
+    }
+
+    #[test]
+    fn v1_same_bytes() {
+        // V1 : Derived payload serialize to the same bytes as macro payload for every variant.
+        for (by_macro, by_derive) in pairs() {
+            assert_eq!(serialize(&by_macro), serialize(&by_derive));
+            assert_eq!(by_macro.discriminant(), by_derive.discriminant());
+        }
+    }
+
+    #[test]
+    fn v2_cross_deserialize() {
+        // V2 : Derived payload deserialize bytes of macro payload and vice versa.
+        for (by_macro, by_derive) in pairs() {
+            let bytes = serialize(&by_macro);
+            assert_eq!(by_derive::Payload::deserialize_size(&bytes, 0), Ok(bytes.len()));
+            assert_eq!(by_derive::Payload::deserialize(&bytes), (by_derive.clone(), bytes.len()));
+
+            let bytes = serialize(&by_derive);
+            assert_eq!(by_macro::Payload::deserialize_size(&bytes, 0), Ok(bytes.len()));
+            assert_eq!(by_macro::Payload::deserialize(&bytes), (by_macro, bytes.len()));
+        }
+    }
+
+    #[test]
+    fn v3_same_schema_hash() {
+        // V3 : Derived payload and macro payload have the same [`SCHEMA_HASH`](crate::net::SCHEMA_HASH).
+        assert_eq!(by_macro::Payload::SCHEMA_HASH, by_derive::Payload::SCHEMA_HASH);
+    }
+
+    #[test]
+    fn v4_same_errors() {
+        // V4 : Derived payload returns the same errors as macro payload for truncated, oversized and unknown bytes.
+        for (by_macro, _) in pairs() {
+            let bytes = serialize(&by_macro);
+            for cut in 0..bytes.len() {
+                assert_eq!(by_derive::Payload::deserialize_size(&bytes[..cut], 0), by_macro::Payload::deserialize_size(&bytes[..cut], 0));
+            }
+            for max_size in 1..bytes.len() {
+                assert_eq!(by_derive::Payload::deserialize_size(&bytes, max_size), by_macro::Payload::deserialize_size(&bytes, max_size));
+            }
+        }
+
+        let oversized = by_macro::Payload::Variable { text: String::from("123456789"), list: vec![], opt: None, names: None, fixed: [0; 3] };
+        assert!(!oversized.within_limits());
+        assert_eq!(by_derive::Payload::deserialize_size(&serialize(&oversized), 0), Err(TamponError::DeserializeSizeGreaterThanMax));
+
+        let unknown = [2u8, 0, 0, 0];
+        assert_eq!(by_derive::Payload::deserialize_size(&unknown, 0), Err(TamponError::DeserializeSizeBufferIncomplete));
+        assert_eq!(by_derive::Payload::deserialize(&unknown), (by_derive::Payload::Invalid, 0));
+        assert!(!by_derive::Payload::is_valid(2));
+        assert!(!by_macro::Payload::is_valid(2));
+    }
+
+    #[test]
+    fn v5_generic() {
+        // V5 : Derived payload supports generic field types.
+        let number = by_derive::Generic::Value { value: 1u32, values: vec![2, 3] };
+        let bytes = serialize(&number);
+        assert_eq!(by_derive::Generic::<u32>::deserialize(&bytes), (number, bytes.len()));
+
+        let text = by_derive::Generic::Value { value: String::from("a"), values: vec![String::from("b")] };
+        let bytes = serialize(&text);
+        assert_eq!(by_derive::Generic::<String>::deserialize(&bytes), (text, bytes.len()));
+    }
+
+    #[test]
+    fn v6_same_view() {
+        // V6 : Derived payload view reads the same fields and size as macro payload view.
+        for (by_macro, by_derive) in pairs() {
+            let bytes = serialize(&by_macro);
+            let (macro_view, macro_size) = by_macro::PayloadRef::read(&bytes);
+            let (derive_view, derive_size) = by_derive::PayloadRef::read(&bytes);
+
+            assert_eq!(macro_size, derive_size);
+            assert_eq!(macro_view.discriminant(), derive_view.discriminant());
+            assert_eq!(macro_view.into_owned(), by_macro);
+            assert_eq!(derive_view.into_owned(), by_derive);
+        }
+
+        let text = by_derive::Generic::Value { value: String::from("a"), values: vec![String::from("b")] };
+        let bytes = serialize(&text);
+        let (view, size) = by_derive::GenericRef::<String>::read(&bytes);
+        assert_eq!(size, bytes.len());
+        assert!(matches!(&view, by_derive::GenericRef::Value { value, .. } if value == "a"));
+        assert_eq!(view.into_owned(), text);
+    }
+
+    #[test]
+    fn v7_unit_only() {
+        // V7 : Derived payload and macro payload without fields have the same bytes and views.
+        use crate::net::PayloadView;
+
+        let pairs = [(by_macro::unit::Unit::Ping, by_derive::Unit::Ping), (by_macro::unit::Unit::Pong, by_derive::Unit::Pong), 
+            (by_macro::unit::Unit::Invalid, by_derive::Unit::Invalid)];
+        assert!(by_macro::unit::Unit::is_valid(2) && by_derive::Unit::is_valid(2));
+        for (by_macro, by_derive) in pairs {
+            let bytes = serialize(&by_macro);
+            assert_eq!(bytes, serialize(&by_derive));
+
+            let (macro_view, macro_size) = by_macro::unit::UnitRef::read(&bytes);
+            let (derive_view, derive_size) = <by_derive::Unit as PayloadView>::read_ref(&bytes);
+            assert_eq!(macro_size, derive_size);
+            assert_eq!(macro_view.into_owned(), by_macro);
+            assert_eq!(by_derive::Unit::from_ref(derive_view), by_derive);
+        }
+        assert_eq!(by_macro::unit::Unit::SCHEMA_HASH, by_derive::Unit::SCHEMA_HASH);
+    }
+
+    #[test]
+    fn v8_same_layout() {
+        // V8 : Derived payload has the same `LAYOUT` as macro payload, exported by [`payload_json`](crate::net::payload_json).
+        assert_eq!(by_derive::Payload::LAYOUT.name, by_macro::Payload::LAYOUT.name);
+        assert_eq!(by_derive::Payload::LAYOUT.variants, by_macro::Payload::LAYOUT.variants);
+        assert_eq!(by_derive::Payload::LAYOUT.doc, " Payload generated by derive.\n");
+        assert_eq!(by_derive::Unit::LAYOUT.variants, by_macro::unit::Unit::LAYOUT.variants);
+        assert_eq!(by_derive::Generic::<String>::LAYOUT.variants[0].fields[0].size, None);
+        assert_eq!(by_derive::Generic::<u32>::LAYOUT.variants[0].fields[0].size, Some(4));
+
+        let json = crate::net::payload_json(&by_derive::Payload::LAYOUT, by_derive::Payload::SCHEMA_HASH);
+        assert_eq!(json.replace("derive", "macro"), crate::net::payload_json(&by_macro::Payload::LAYOUT, by_macro::Payload::SCHEMA_HASH));
+    }
+}
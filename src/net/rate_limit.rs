@@ -0,0 +1,507 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+
+use std::collections::HashMap;
+
+use crate::net::{ClientMessage, ClientPayload, Clock};
+
+/// Tokens are counted in thousandths so that refill is exact at millisecond granularity.
+const MILLI : u64 = 1000;
+
+/// Rate of a [`TokenBucket`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Count of messages that can be sent at once.
+    pub burst : u32,
+
+    /// Count of messages refilled each second.
+    pub per_second : u32,
+}
+
+impl RateLimit {
+    /// Default limit of all messages of a connection.
+    pub const CONNECTION : RateLimit = RateLimit::new(64, 32);
+
+    /// Create a new [`RateLimit`].
+    pub const fn new(burst : u32, per_second : u32) -> RateLimit {
+        RateLimit { burst, per_second }
+    }
+}
+
+/// Token bucket allowing a burst of messages then a steady rate.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ RateLimit, TokenBucket };
+/// 
+/// let mut bucket = TokenBucket::new(RateLimit::new(2, 10), 0);
+/// assert!(bucket.take(0));
+/// assert!(bucket.take(0));
+/// assert!(!bucket.take(0));
+/// 
+/// // One token each 100 ms
+/// assert_eq!(bucket.retry_after(0), 100);
+/// assert!(bucket.take(100));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    limit : RateLimit,
+
+    /// Thousandths of tokens available.
+    tokens : u64,
+
+    /// Time in milliseconds of the last refill.
+    last : u64,
+}
+
+impl TokenBucket {
+    /// Create a new full [`TokenBucket`] at a given time in milliseconds.
+    pub fn new(limit : RateLimit, now : u64) -> TokenBucket {
+        TokenBucket { limit, tokens: limit.burst as u64 * MILLI, last: now }
+    }
+
+    /// Limit of the bucket.
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Count of whole tokens available at a given time.
+    pub fn tokens(&mut self, now : u64) -> u32 {
+        self.refill(now);
+        (self.tokens / MILLI) as u32
+    }
+
+    /// Take a token at a given time.
+    /// 
+    /// # Returns
+    /// True if a token was available.
+    pub fn take(&mut self, now : u64) -> bool {
+        self.refill(now);
+        match self.tokens.checked_sub(MILLI) {
+            Some(tokens) => {
+                self.tokens = tokens;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Time in milliseconds before a token is available, [`u64::MAX`] if bucket never refills.
+    pub fn retry_after(&mut self, now : u64) -> u64 {
+        self.refill(now);
+        match (MILLI.saturating_sub(self.tokens), self.limit.per_second as u64) {
+            (0, _) => 0,
+            (_, 0) => u64::MAX,
+            (missing, rate) => missing.div_ceil(rate),
+        }
+    }
+
+    /// Add tokens refilled since last refill, up to burst.
+    fn refill(&mut self, now : u64) {
+        // One thousandth of token per millisecond is one token per second
+        let elapsed = now.saturating_sub(self.last);
+        self.tokens = self.tokens.saturating_add(elapsed.saturating_mul(self.limit.per_second as u64)).min(self.limit.burst as u64 * MILLI);
+        self.last = self.last.max(now);
+    }
+}
+
+/// Decision of a [`RateLimiter`] about a message received, that server acts on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Handle the message.
+    Accept,
+
+    /// Discard the message, either over its payload limit or invalid.
+    Drop,
+
+    /// Discard the message and stop reading from the connection for a time in milliseconds, the connection is over its limit.
+    Throttle {
+        /// Time in milliseconds before the connection can send again.
+        retry_after : u64
+    },
+
+    /// Close the connection, too many invalid messages were received.
+    Disconnect,
+}
+
+/// Limits shared by the [`RateLimiter`] of every connection.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ ClientPayload, RateLimit, RateLimitPolicy };
+/// 
+/// // Only 1 login each 5 seconds, and disconnect on 3rd invalid message in a minute
+/// let login = ClientPayload::Login { account: String::new() }.discriminant();
+/// let policy = RateLimitPolicy::new(RateLimit::CONNECTION)
+///     .with_payload_limit(login, RateLimit::new(1, 0))
+///     .with_strikes(3, 60_000);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+    /// Limit of all messages of a connection.
+    connection : RateLimit,
+
+    /// Limits of specific payloads, by discriminant.
+    payloads : HashMap<u16, RateLimit>,
+
+    /// Count of strikes disconnecting a connection.
+    max_strikes : u32,
+
+    /// Time in milliseconds without strike to forgive one.
+    strike_decay : u64,
+}
+
+impl RateLimitPolicy {
+    /// Default count of strikes disconnecting a connection.
+    pub const MAX_STRIKES : u32 = 5;
+
+    /// Default time in milliseconds without strike to forgive one.
+    pub const STRIKE_DECAY : u64 = 60_000;
+
+    /// Create a new [`RateLimitPolicy`] with a limit for all messages of a connection and default strikes.
+    pub fn new(connection : RateLimit) -> RateLimitPolicy {
+        RateLimitPolicy { connection, payloads: HashMap::new(), max_strikes: Self::MAX_STRIKES, strike_decay: Self::STRIKE_DECAY }
+    }
+
+    /// Limit a payload in addition to the connection limit.
+    pub fn with_payload_limit(mut self, discriminant : u16, limit : RateLimit) -> RateLimitPolicy {
+        self.payloads.insert(discriminant, limit);
+        self
+    }
+
+    /// Disconnect when strikes reach a count, forgiving one each decay time in milliseconds without strike, never if 0.
+    /// 
+    /// # Panic(s)
+    /// Will panic! if count is 0.
+    pub fn with_strikes(mut self, max_strikes : u32, strike_decay : u64) -> RateLimitPolicy {
+        assert!(max_strikes > 0, "RateLimitPolicy max strikes can't be 0!");
+        self.max_strikes = max_strikes;
+        self.strike_decay = strike_decay;
+        self
+    }
+
+    /// Limit of all messages of a connection.
+    pub fn connection(&self) -> RateLimit {
+        self.connection
+    }
+
+    /// Limit of a payload, [`None`] if only limited by the connection limit.
+    pub fn payload_limit(&self, discriminant : u16) -> Option<RateLimit> {
+        self.payloads.get(&discriminant).copied()
+    }
+}
+
+impl Default for RateLimitPolicy {
+    fn default() -> Self {
+        Self::new(RateLimit::CONNECTION)
+    }
+}
+
+/// Flood protection of the messages received from a single client connection.
+/// 
+/// Every message goes through a token bucket for the connection and one for its payload if limited by the [`RateLimitPolicy`].
+/// [`ClientPayload::Invalid`] payloads and messages that fail to unpack count as strikes, disconnecting the client when 
+/// too many happen before being forgiven. Time is given by a [`Clock`] so verdicts are deterministic.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ ClientMessage, ClientPayload, ManualClock, RateLimit, RateLimitPolicy, RateLimiter, Verdict };
+/// 
+/// let clock = ManualClock::new(0);
+/// let mut limiter = RateLimiter::new(&clock, RateLimitPolicy::new(RateLimit::new(1, 4)).with_strikes(2, 1000));
+/// let message = ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 });
+/// 
+/// assert_eq!(limiter.check(&message), Verdict::Accept);
+/// assert_eq!(limiter.check(&message), Verdict::Throttle { retry_after: 250 });
+/// 
+/// // Messages that fail to unpack
+/// assert_eq!(limiter.strike(), Verdict::Drop);
+/// assert_eq!(limiter.strike(), Verdict::Disconnect);
+/// ```
+pub struct RateLimiter<C : Clock> {
+    clock : C,
+
+    policy : RateLimitPolicy,
+
+    /// Bucket of all messages.
+    connection : TokenBucket,
+
+    /// Buckets of limited payloads, created on first message.
+    payloads : HashMap<u16, TokenBucket>,
+
+    /// Strikes not forgiven yet.
+    strikes : u32,
+
+    /// Time in milliseconds of the last strike or forgiveness.
+    last_strike : u64,
+}
+
+impl<C : Clock> RateLimiter<C> {
+    /// Create a new [`RateLimiter`] with full buckets.
+    pub fn new(clock : C, policy : RateLimitPolicy) -> RateLimiter<C> {
+        let now = clock.now();
+        RateLimiter { connection: TokenBucket::new(policy.connection, now), clock, policy, payloads: HashMap::new(), strikes: 0, last_strike: now }
+    }
+
+    /// Policy of the limiter.
+    pub fn policy(&self) -> &RateLimitPolicy {
+        &self.policy
+    }
+
+    /// Count of strikes not forgiven yet.
+    pub fn strikes(&mut self) -> u32 {
+        self.forgive();
+        self.strikes
+    }
+
+    /// Decide what to do with a message received.
+    /// 
+    /// # Returns
+    /// - [`Verdict::Accept`] if message is within limits.
+    /// - [`Verdict::Throttle`] if connection is over its limit.
+    /// - [`Verdict::Drop`] if payload is over its limit, or [`ClientPayload::Invalid`].
+    /// - [`Verdict::Disconnect`] if [`ClientPayload::Invalid`] reached the maximum strikes.
+    pub fn check(&mut self, message : &ClientMessage) -> Verdict {
+        if let ClientPayload::Invalid = message.payload {
+            return self.strike();
+        }
+
+        let now = self.clock.now();
+        if !self.connection.take(now) {
+            return Verdict::Throttle { retry_after: self.connection.retry_after(now) };
+        }
+
+        let discriminant = message.payload.discriminant();
+        match self.policy.payload_limit(discriminant) {
+            Some(limit) => match self.payloads.entry(discriminant).or_insert_with(|| TokenBucket::new(limit, now)).take(now) {
+                true => Verdict::Accept,
+                false => Verdict::Drop,
+            },
+            None => Verdict::Accept,
+        }
+    }
+
+    /// Count a strike for an invalid or malformed message, like one that failed to unpack.
+    /// 
+    /// # Returns
+    /// [`Verdict::Disconnect`] if strikes reached the maximum, [`Verdict::Drop`] otherwise.
+    pub fn strike(&mut self) -> Verdict {
+        self.forgive();
+        self.strikes = self.strikes.saturating_add(1);
+        self.last_strike = self.clock.now();
+
+        match self.strikes >= self.policy.max_strikes {
+            true => Verdict::Disconnect,
+            false => Verdict::Drop,
+        }
+    }
+
+    /// Forgive a strike for each decay time elapsed since last strike or forgiveness.
+    fn forgive(&mut self) {
+        let now = self.clock.now();
+        if self.strikes == 0 || self.policy.strike_decay == 0 {
+            self.last_strike = now;
+            return;
+        }
+
+        let forgiven = now.saturating_sub(self.last_strike) / self.policy.strike_decay;
+        self.strikes = self.strikes.saturating_sub(forgiven.min(u32::MAX as u64) as u32);
+        self.last_strike += forgiven * self.policy.strike_decay;
+    }
+}
+
+
+/// This module test [TokenBucket], [RateLimitPolicy] and [RateLimiter] with a [ManualClock](crate::net::ManualClock).
+/// 
+/// # Verification(s)
+/// V1 : [TokenBucket::take] allows a burst then refills at rate, up to burst.
+/// V2 : [TokenBucket::retry_after] gives the exact time before a token is available.
+/// V3 : [RateLimiter::check] throttles a connection over its limit until refilled.
+/// V4 : [RateLimiter::check] drops payloads over their limit without affecting other payloads.
+/// V5 : [RateLimiter::check] of invalid payloads and [RateLimiter::strike] drop then disconnect.
+/// V6 : [RateLimiter] forgives strikes over time.
+/// V7 : [RateLimiter] verdicts of a flood are deterministic and independent between connections.
+#[cfg(test)]
+mod tests {
+    use crate::net::{ClientMessage, ClientPayload, ManualClock, RateLimit, RateLimitPolicy, RateLimiter, TokenBucket, Verdict};
+
+    fn test_message() -> ClientMessage {
+        ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 })
+    }
+
+    fn login_message() -> ClientMessage {
+        ClientMessage::new(ClientPayload::Login { account: String::from("account") })
+    }
+
+    #[test]
+    fn v1_bucket_burst_and_refill() {
+        // V1 : [TokenBucket::take] allows a burst then refills at rate, up to burst.
+        let mut bucket = TokenBucket::new(RateLimit::new(5, 20), 1000);
+        for _ in 0..5 {
+            assert!(bucket.take(1000));
+        }
+        assert!(!bucket.take(1000));
+
+        // 20 per second is one each 50 ms
+        assert!(!bucket.take(1049));
+        assert!(bucket.take(1050));
+        assert!(!bucket.take(1050));
+        assert_eq!(bucket.tokens(1200), 3);
+
+        // Never more than burst
+        assert_eq!(bucket.tokens(100_000), 5);
+
+        // Time going backward doesn't refill
+        assert!(bucket.take(100_000));
+        assert_eq!(bucket.tokens(0), 4);
+    }
+
+    #[test]
+    fn v2_bucket_retry_after() {
+        // V2 : [TokenBucket::retry_after] gives the exact time before a token is available.
+        let mut bucket = TokenBucket::new(RateLimit::new(1, 3), 0);
+        assert_eq!(bucket.retry_after(0), 0);
+        assert!(bucket.take(0));
+
+        // 1000 / 3 rounded up
+        assert_eq!(bucket.retry_after(0), 334);
+        assert_eq!(bucket.retry_after(100), 234);
+        assert!(!bucket.take(333));
+        assert!(bucket.take(334));
+
+        let mut bucket = TokenBucket::new(RateLimit::new(1, 0), 0);
+        assert!(bucket.take(0));
+        assert_eq!(bucket.retry_after(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn v3_connection_throttle() {
+        // V3 : [RateLimiter::check] throttles a connection over its limit until refilled.
+        let clock = ManualClock::new(0);
+        let mut limiter = RateLimiter::new(&clock, RateLimitPolicy::new(RateLimit::new(10, 100)));
+
+        for _ in 0..10 {
+            assert_eq!(limiter.check(&test_message()), Verdict::Accept);
+        }
+        assert_eq!(limiter.check(&test_message()), Verdict::Throttle { retry_after: 10 });
+
+        clock.advance(5);
+        assert_eq!(limiter.check(&test_message()), Verdict::Throttle { retry_after: 5 });
+
+        clock.advance(5);
+        assert_eq!(limiter.check(&test_message()), Verdict::Accept);
+        assert_eq!(limiter.strikes(), 0);
+    }
+
+    #[test]
+    fn v4_payload_limit() {
+        // V4 : [RateLimiter::check] drops payloads over their limit without affecting other payloads.
+        let clock = ManualClock::new(0);
+        let policy = RateLimitPolicy::new(RateLimit::new(100, 100)).with_payload_limit(login_message().payload.discriminant(), RateLimit::new(2, 1));
+        assert_eq!(policy.payload_limit(login_message().payload.discriminant()), Some(RateLimit::new(2, 1)));
+        assert_eq!(policy.payload_limit(test_message().payload.discriminant()), None);
+        let mut limiter = RateLimiter::new(&clock, policy);
+
+        assert_eq!(limiter.check(&login_message()), Verdict::Accept);
+        assert_eq!(limiter.check(&login_message()), Verdict::Accept);
+        assert_eq!(limiter.check(&login_message()), Verdict::Drop);
+        assert_eq!(limiter.check(&test_message()), Verdict::Accept);
+
+        clock.advance(1000);
+        assert_eq!(limiter.check(&login_message()), Verdict::Accept);
+        assert_eq!(limiter.check(&login_message()), Verdict::Drop);
+        assert_eq!(limiter.strikes(), 0);
+    }
+
+    #[test]
+    fn v5_strikes() {
+        // V5 : [RateLimiter::check] of invalid payloads and [RateLimiter::strike] drop then disconnect.
+        let clock = ManualClock::new(0);
+        let mut limiter = RateLimiter::new(&clock, RateLimitPolicy::default().with_strikes(3, 0));
+
+        assert_eq!(limiter.check(&ClientMessage::new(ClientPayload::Invalid)), Verdict::Drop);
+        assert_eq!(limiter.strike(), Verdict::Drop);
+        assert_eq!(limiter.check(&test_message()), Verdict::Accept);
+        assert_eq!(limiter.strikes(), 2);
+
+        // Decay of 0 never forgives
+        clock.advance(u32::MAX as u64);
+        assert_eq!(limiter.check(&ClientMessage::new(ClientPayload::Invalid)), Verdict::Disconnect);
+        assert_eq!(limiter.strike(), Verdict::Disconnect);
+    }
+
+    #[test]
+    fn v6_strike_decay() {
+        // V6 : [RateLimiter] forgives strikes over time.
+        let clock = ManualClock::new(0);
+        let mut limiter = RateLimiter::new(&clock, RateLimitPolicy::default().with_strikes(3, 1000));
+
+        assert_eq!(limiter.strike(), Verdict::Drop);
+        assert_eq!(limiter.strike(), Verdict::Drop);
+
+        clock.advance(999);
+        assert_eq!(limiter.strikes(), 2);
+        clock.advance(1);
+        assert_eq!(limiter.strikes(), 1);
+
+        // Forgiveness doesn't reset time of next one
+        clock.advance(500);
+        assert_eq!(limiter.strike(), Verdict::Drop);
+        clock.advance(500);
+        assert_eq!(limiter.strikes(), 2);
+        assert_eq!(limiter.strike(), Verdict::Disconnect);
+
+        clock.advance(10_000);
+        assert_eq!(limiter.strikes(), 0);
+    }
+
+    #[test]
+    fn v7_flood_deterministic() {
+        // V7 : [RateLimiter] verdicts of a flood are deterministic and independent between connections.
+        let clock = ManualClock::new(0);
+        let policy = RateLimitPolicy::new(RateLimit::new(8, 40));
+        let mut first = RateLimiter::new(&clock, policy.clone());
+        let mut second = RateLimiter::new(&clock, policy);
+        let mut quiet = RateLimiter::new(&clock, RateLimitPolicy::new(RateLimit::new(8, 40)));
+
+        // Flood of 1 message per millisecond during 1 second
+        let mut accepted = 0;
+        for _ in 0..1000 {
+            let verdict = first.check(&test_message());
+            assert_eq!(second.check(&test_message()), verdict);
+            if verdict == Verdict::Accept {
+                accepted += 1;
+            } else {
+                assert!(matches!(verdict, Verdict::Throttle { retry_after } if retry_after <= 25));
+            }
+            clock.advance(1);
+        }
+
+        // Burst and 1 second of refill
+        assert_eq!(accepted, 8 + 39);
+        assert_eq!(quiet.check(&test_message()), Verdict::Accept);
+    }
+}
@@ -0,0 +1,751 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::{collections::{HashMap, HashSet, VecDeque}, marker::PhantomData};
+
+use crate::net::{decoder::{decode_frame, Frame}, udp::ReceiveWindow, ClientMessage, Clock, Error, ErrorKind, Message, ServerMessage, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Size of packet header : sequence, acknowledged sequence and bits of previous acknowledged sequences.
+const HEADER_SIZE : usize = 3 * size_of::<u32>();
+
+/// Size of entry header : [`Delivery`] and message id.
+const ENTRY_HEADER_SIZE : usize = size_of::<u8>() + size_of::<u16>();
+
+/// Count of sent packets remembered. Older packets can't be acknowledged.
+const SENT_HISTORY : usize = u32::BITS as usize + 1;
+
+/// Maximum distance between two message ids still considered newer.
+const ID_WINDOW : u16 = u16::MAX / 2;
+
+/// Default count of reliable message ids of the same [`Delivery`] accepted ahead of the first one missing, see [`ReliableEndpoint::with_receive_window`].
+pub const RELIABLE_RECEIVE_WINDOW : u16 = 256;
+
+/// Endpoint used by client to send [`ClientMessage`] and receive [`ServerMessage`] reliably.
+pub type ClientReliableEndpoint<C> = ReliableEndpoint<ClientMessage, ServerMessage, C>;
+
+/// Endpoint used by server to send [`ServerMessage`] and receive [`ClientMessage`] reliably.
+pub type ServerReliableEndpoint<C> = ReliableEndpoint<ServerMessage, ClientMessage, C>;
+
+/// Delivery guarantee of a message sent with [`ReliableEndpoint::send`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Delivery {
+    /// Sent once. May be lost or delivered out of order.
+    Unreliable = 0,
+
+    /// Sent once. May be lost, and is dropped if a more recent sequenced message was already delivered.
+    UnreliableSequenced = 1,
+
+    /// Resent until acknowledged. Delivered once, as soon as received.
+    ReliableUnordered = 2,
+
+    /// Resent until acknowledged. Delivered once, in the order sent.
+    ReliableOrdered = 3,
+}
+
+impl Delivery {
+    /// Returns true if message is resent until acknowledged.
+    pub const fn is_reliable(&self) -> bool {
+        matches!(self, Delivery::ReliableUnordered | Delivery::ReliableOrdered)
+    }
+
+    /// Get [`Delivery`] from its u8 value.
+    const fn from_u8(value : u8) -> Option<Delivery> {
+        match value {
+            0 => Some(Delivery::Unreliable),
+            1 => Some(Delivery::UnreliableSequenced),
+            2 => Some(Delivery::ReliableUnordered),
+            3 => Some(Delivery::ReliableOrdered),
+            _ => None,
+        }
+    }
+}
+
+/// Message waiting to be sent or acknowledged.
+struct Entry {
+    delivery : Delivery,
+
+    /// Id of message, unique per [`Delivery`].
+    id : u16,
+
+    /// Packed message, size header included.
+    frame : Vec<u8>,
+
+    /// Time the message was last sent.
+    sent_at : Option<u64>,
+}
+
+/// Packet sent and waiting for acknowledgement.
+struct SentPacket {
+    sequence : u32,
+    sent_at : u64,
+
+    /// Reliable messages of the packet.
+    entries : Vec<(Delivery, u16)>,
+}
+
+/// Reliability layer of a single connection over unreliable datagrams.
+/// 
+/// Messages `S` are given to [`send`](Self::send) with a [`Delivery`] guarantee, then packed in 
+/// packets returned by [`transmit`](Self::transmit) that must be sent to the remote endpoint. Packets
+/// received are given to [`receive`](Self::receive) and messages `R` are obtained by [`poll`](Self::poll).
+/// 
+/// Every packet carries a sequence number and acknowledges the last 33 packets received. Reliable 
+/// messages are resent in a new packet when not acknowledged after the resend timeout.
+/// 
+/// # Note(s)
+/// * Less than [`RELIABLE_RECEIVE_WINDOW`] reliable messages of the same [`Delivery`] can be waiting for acknowledgement, [`send`](Self::send) refuses more.
+/// * Reliable messages received ahead of the first one missing are buffered up to the same window, so a peer can't grow memory 
+///   by leaving a gap. Both endpoints must use the same window, see [`with_receive_window`](Self::with_receive_window).
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ ClientReliableEndpoint, ServerReliableEndpoint, ClientMessage, ClientPayload, Delivery, MonotonicClock, UDP_MTU };
+/// 
+/// let clock = MonotonicClock::new();
+/// let mut client = ClientReliableEndpoint::new(&clock, UDP_MTU, 100);
+/// let mut server = ServerReliableEndpoint::new(&clock, UDP_MTU, 100);
+/// 
+/// client.send(&ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 }), Delivery::ReliableOrdered).unwrap();
+/// for packet in client.transmit() {
+///     server.receive(&packet).unwrap();
+/// }
+/// 
+/// assert_eq!(server.poll(), Some(ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 })));
+/// ```
+pub struct ReliableEndpoint<S : Message, R : Message, C : Clock> {
+    clock : C,
+
+    /// Maximum size of a packet.
+    mtu : usize,
+
+    /// Milliseconds before a reliable message not acknowledged is resent.
+    resend_timeout : u64,
+
+    /// Sequence of the next packet sent. Never 0 which means nothing acknowledged.
+    sequence : u32,
+
+    /// Id of the next message sent for each [`Delivery`].
+    next_id : [u16; 4],
+
+    /// Count of reliable message ids of the same [`Delivery`] sent or accepted ahead of the first one not acknowledged or missing.
+    receive_window : u16,
+
+    /// Messages waiting to be sent or acknowledged, in the order given.
+    pending : Vec<Entry>,
+
+    /// Packets waiting for acknowledgement.
+    sent : VecDeque<SentPacket>,
+
+    /// Smoothed round-trip time in milliseconds.
+    rtt : Option<u64>,
+
+    /// Sequences of packets received.
+    received : ReceiveWindow,
+
+    /// True if a packet was received since the last packet sent.
+    ack_pending : bool,
+
+    /// Id of the next [`Delivery::ReliableOrdered`] message to deliver.
+    ordered_next : u16,
+
+    /// [`Delivery::ReliableOrdered`] messages received ahead of order.
+    ordered_buffer : HashMap<u16, R>,
+
+    /// Every [`Delivery::ReliableUnordered`] message before this id was received.
+    unordered_floor : u16,
+
+    /// [`Delivery::ReliableUnordered`] messages received after floor.
+    unordered_received : HashSet<u16>,
+
+    /// Id of the last [`Delivery::UnreliableSequenced`] message delivered.
+    sequenced_last : Option<u16>,
+
+    /// Messages ready to be polled.
+    delivered : VecDeque<R>,
+
+    message : PhantomData<S>,
+}
+
+impl<S : Message, R : Message, C : Clock> ReliableEndpoint<S, R, C> {
+    /// Create a new [`ReliableEndpoint`].
+    /// 
+    /// # Argument(s)
+    /// * `clock` - [`Clock`] used for resend timeouts and round-trip time.
+    /// * `mtu` - Maximum size of a packet, usually [`UDP_MTU`](crate::net::UDP_MTU).
+    /// * `resend_timeout` - Milliseconds before a reliable message not acknowledged is resent.
+    pub fn new(clock : C, mtu : usize, resend_timeout : u64) -> ReliableEndpoint<S, R, C> {
+        ReliableEndpoint { 
+            clock, mtu, resend_timeout, receive_window: RELIABLE_RECEIVE_WINDOW,
+            sequence: 1, next_id: [0; 4], pending: Vec::new(), sent: VecDeque::new(), rtt: None, 
+            received: ReceiveWindow::default(), ack_pending: false,
+            ordered_next: 0, ordered_buffer: HashMap::new(), unordered_floor: 0, unordered_received: HashSet::new(), 
+            sequenced_last: None, delivered: VecDeque::new(), message: PhantomData 
+        }
+    }
+
+    /// Set the count of reliable message ids of the same [`Delivery`] accepted ahead of the first one missing. 
+    /// 
+    /// Bounds the messages buffered until the missing one is received, and the messages waiting for acknowledgement 
+    /// since the remote endpoint refuses further ones. Must be the same on both endpoints.
+    /// 
+    /// # Panic(s)
+    /// Will panic! if window is 0 or greater than 32767.
+    pub fn with_receive_window(mut self, window : u16) -> ReliableEndpoint<S, R, C> {
+        assert!(window > 0 && window <= ID_WINDOW, "window must be between 1 and {}", ID_WINDOW);
+        self.receive_window = window;
+        self
+    }
+
+    /// Smoothed round-trip time in milliseconds, if any packet was acknowledged yet.
+    pub fn rtt(&self) -> Option<u64> {
+        self.rtt
+    }
+
+    /// Count of reliable messages waiting for acknowledgement.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Queue a message to be sent by the next [`transmit`](Self::transmit).
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Message was queued.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if message can't fit in a packet.
+    ///     2. [`ErrorKind::TooManyPending`](crate::net::ErrorKind::TooManyPending) if the oldest reliable message of the same [`Delivery`] 
+    ///        waiting for acknowledgement is a receive window behind, since remote endpoint would refuse it.
+    pub fn send(&mut self, message : &S, delivery : Delivery) -> Result<(), Error> {
+        if message.size() > S::MAX_SIZE || HEADER_SIZE + ENTRY_HEADER_SIZE + MESSAGE_SIZE_TYPE_SIZE + message.size() > self.mtu {
+            return Err(ErrorKind::MessageSizeGreaterThanLimit.into());
+        }
+
+        let id = self.next_id[delivery as usize];
+        if delivery.is_reliable() && self.pending.iter().find(|entry| entry.delivery == delivery).is_some_and(|oldest| id.wrapping_sub(oldest.id) >= self.receive_window) {
+            return Err(ErrorKind::TooManyPending.into());
+        }
+
+        let mut frame = vec![0u8; MESSAGE_SIZE_TYPE_SIZE + message.size()];
+        message.pack_bytes(&mut frame)?;
+
+        self.next_id[delivery as usize] = id.wrapping_add(1);
+        self.pending.push(Entry { delivery, id, frame, sent_at: None });
+        Ok(())
+    }
+
+    /// Pack messages queued and reliable messages to resend into packets.
+    /// 
+    /// An acknowledgement only packet is produced if packets were received since last transmit and no message is due.
+    /// 
+    /// # Returns
+    /// Packets to send to the remote endpoint.
+    pub fn transmit(&mut self) -> Vec<Vec<u8>> {
+        let now = self.clock.now();
+        let mut packets = Vec::new();
+        let mut packet = vec![0u8; HEADER_SIZE];
+        let mut entries = Vec::new();
+
+        for index in 0..self.pending.len() {
+            let entry = &self.pending[index];
+            if entry.sent_at.is_some_and(|sent_at| now.saturating_sub(sent_at) < self.resend_timeout) {
+                continue;
+            }
+
+            if packet.len() + ENTRY_HEADER_SIZE + entry.frame.len() > self.mtu {
+                packets.push(self.finish(packet, entries, now));
+                packet = vec![0u8; HEADER_SIZE];
+                entries = Vec::new();
+            }
+
+            let entry = &mut self.pending[index];
+            packet.push(entry.delivery as u8);
+            packet.extend_from_slice(&entry.id.to_le_bytes());
+            packet.extend_from_slice(&entry.frame);
+            entry.sent_at = Some(now);
+            if entry.delivery.is_reliable() {
+                entries.push((entry.delivery, entry.id));
+            }
+        }
+
+        // Unreliable messages are sent only once
+        self.pending.retain(|entry| entry.delivery.is_reliable());
+
+        if packet.len() > HEADER_SIZE || self.ack_pending {
+            packets.push(self.finish(packet, entries, now));
+        }
+
+        packets
+    }
+
+    /// Write header of packet and remember it for acknowledgement.
+    fn finish(&mut self, mut packet : Vec<u8>, entries : Vec<(Delivery, u16)>, now : u64) -> Vec<u8> {
+        // Latest is 0 until a packet is received since sequences start at 1
+        packet[0..4].copy_from_slice(&self.sequence.to_le_bytes());
+        packet[4..8].copy_from_slice(&self.received.latest().to_le_bytes());
+        packet[8..12].copy_from_slice(&self.received.previous().to_le_bytes());
+
+        self.sent.push_back(SentPacket { sequence: self.sequence, sent_at: now, entries });
+        if self.sent.len() > SENT_HISTORY {
+            self.sent.pop_front();
+        }
+
+        self.sequence = self.sequence.wrapping_add(1).max(1);
+        self.ack_pending = false;
+        packet
+    }
+
+    /// Process a packet received from the remote endpoint.
+    /// 
+    /// Duplicated packets are silently ignored.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Packet was processed. Messages delivered can be obtained with [`poll`](Self::poll).
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if packet is malformed. Packet is ignored entirely.
+    ///     2. Any error of [`Message::from_bytes`]. Packet is ignored entirely.
+    ///     3. [`ErrorKind::TooManyPending`](crate::net::ErrorKind::TooManyPending) if a reliable message is ahead of the receive window. 
+    ///        Packet is ignored entirely and not acknowledged, so it is resent.
+    pub fn receive(&mut self, packet : &[u8]) -> Result<(), Error> {
+        if packet.len() < HEADER_SIZE {
+            return Err(ErrorKind::InvalidMessage.into());
+        }
+
+        let sequence = u32::from_le_bytes(packet[0..4].try_into().unwrap());
+        let ack = u32::from_le_bytes(packet[4..8].try_into().unwrap());
+        let ack_bits = u32::from_le_bytes(packet[8..12].try_into().unwrap());
+
+        // Validate the whole packet before processing
+        let mut messages = Vec::new();
+        let mut bytes = &packet[HEADER_SIZE..];
+        while !bytes.is_empty() {
+            if bytes.len() < ENTRY_HEADER_SIZE {
+                return Err(ErrorKind::InvalidMessage.into());
+            }
+            let delivery = Delivery::from_u8(bytes[0]).ok_or(ErrorKind::InvalidMessage)?;
+            let id = u16::from_le_bytes(bytes[1..3].try_into().unwrap());
+
+            match decode_frame::<R>(&bytes[ENTRY_HEADER_SIZE..]) {
+                Some(Frame::Message(result, size)) => {
+                    let message = result?;
+                    if self.beyond_window(delivery, id) {
+                        return Err(ErrorKind::TooManyPending.into());
+                    }
+                    messages.push((delivery, id, message));
+                    bytes = &bytes[ENTRY_HEADER_SIZE + size..];
+                },
+                Some(Frame::Corrupted(err)) => return Err(err),
+                None => return Err(ErrorKind::InvalidMessage.into()),
+            }
+        }
+
+        if sequence == 0 {
+            return Err(ErrorKind::InvalidMessage.into());
+        }
+
+        self.acknowledge(ack, ack_bits);
+
+        if self.received.register(sequence).is_some() {
+            self.ack_pending = true;
+            for (delivery, id, message) in messages {
+                self.deliver(delivery, id, message);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Release messages of packets acknowledged by remote endpoint.
+    fn acknowledge(&mut self, ack : u32, ack_bits : u32) {
+        if ack == 0 {
+            return;
+        }
+
+        let now = self.clock.now();
+        let mut acked = HashSet::new();
+
+        self.sent.retain(|packet| {
+            let distance = ack.wrapping_sub(packet.sequence);
+            let is_acked = distance == 0 || (distance <= u32::BITS && ack_bits & (1 << (distance - 1)) != 0);
+            if is_acked {
+                acked.extend(packet.entries.iter().copied());
+                let sample = now.saturating_sub(packet.sent_at);
+                self.rtt = Some(match self.rtt {
+                    Some(rtt) => (rtt * 7 + sample) / 8,
+                    None => sample,
+                });
+            }
+            !is_acked
+        });
+
+        if !acked.is_empty() {
+            self.pending.retain(|entry| !acked.contains(&(entry.delivery, entry.id)));
+        }
+    }
+
+    /// Returns true if a reliable message id is ahead of the receive window.
+    fn beyond_window(&self, delivery : Delivery, id : u16) -> bool {
+        let floor = match delivery {
+            Delivery::ReliableUnordered => self.unordered_floor,
+            Delivery::ReliableOrdered => self.ordered_next,
+            _ => return false,
+        };
+
+        // Ids further than ID_WINDOW are older ones already delivered
+        (self.receive_window..ID_WINDOW).contains(&id.wrapping_sub(floor))
+    }
+
+    /// Deliver a message received according to its [`Delivery`].
+    fn deliver(&mut self, delivery : Delivery, id : u16, message : R) {
+        match delivery {
+            Delivery::Unreliable => self.delivered.push_back(message),
+            Delivery::UnreliableSequenced => {
+                if self.sequenced_last.is_none_or(|last| (1..ID_WINDOW).contains(&id.wrapping_sub(last))) {
+                    self.sequenced_last = Some(id);
+                    self.delivered.push_back(message);
+                }
+            },
+            Delivery::ReliableUnordered => {
+                if id.wrapping_sub(self.unordered_floor) < self.receive_window && self.unordered_received.insert(id) {
+                    self.delivered.push_back(message);
+                    while self.unordered_received.remove(&self.unordered_floor) {
+                        self.unordered_floor = self.unordered_floor.wrapping_add(1);
+                    }
+                }
+            },
+            Delivery::ReliableOrdered => {
+                if id.wrapping_sub(self.ordered_next) < self.receive_window {
+                    self.ordered_buffer.entry(id).or_insert(message);
+                    while let Some(message) = self.ordered_buffer.remove(&self.ordered_next) {
+                        self.delivered.push_back(message);
+                        self.ordered_next = self.ordered_next.wrapping_add(1);
+                    }
+                }
+            },
+        }
+    }
+
+    /// Get the next message delivered.
+    pub fn poll(&mut self) -> Option<R> {
+        self.delivered.pop_front()
+    }
+}
+
+
+/// This module test [ReliableEndpoint] with a [ManualClock] and a simulated network.
+/// 
+/// # Verification(s)
+/// V1 : [ReliableEndpoint] delivers messages of every [Delivery] over a perfect network.
+/// V2 : [Delivery::ReliableOrdered] messages are delivered once and in order over a lossy network.
+/// V3 : [Delivery::ReliableUnordered] messages are delivered once over a lossy network.
+/// V4 : [Delivery::UnreliableSequenced] messages are delivered in increasing order over a lossy network.
+/// V5 : [Delivery::Unreliable] messages are delivered at most once over a lossy network.
+/// V6 : [ReliableEndpoint::transmit] resends reliable messages only after resend timeout.
+/// V7 : [ReliableEndpoint::receive] returns [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) for malformed packets.
+/// V8 : [ReliableEndpoint::send] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) for message bigger than MTU.
+/// V9 : [ReliableEndpoint::send] returns [`ErrorKind::TooManyPending`](crate::net::ErrorKind::TooManyPending) once [RELIABLE_RECEIVE_WINDOW] reliable messages of a [Delivery] wait for acknowledgement.
+/// V10 : [ReliableEndpoint::receive] refuses reliable messages ahead of the receive window without acknowledging them, so they are resent.
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use crate::net::{ClientMessage, ClientPayload, ClientReliableEndpoint, Clock, Delivery, ErrorKind, ManualClock, ServerReliableEndpoint, RELIABLE_RECEIVE_WINDOW, UDP_MTU};
+
+    const RESEND_TIMEOUT : u64 = 100;
+    const TICK : u64 = 10;
+
+    /// Deterministic network with loss, latency and jitter in milliseconds.
+    struct SimulatedNetwork {
+        clock : ManualClock,
+        loss_percent : u64,
+        latency : u64,
+        jitter : u64,
+        seed : u64,
+        /// Time of arrival and packet, true if going to server.
+        in_flight : Vec<(u64, bool, Vec<u8>)>,
+    }
+
+    impl SimulatedNetwork {
+        fn new(loss_percent : u64, latency : u64, jitter : u64) -> SimulatedNetwork {
+            SimulatedNetwork { clock: ManualClock::new(0), loss_percent, latency, jitter, seed: 0x2545F4914F6CDD1D, in_flight: Vec::new() }
+        }
+
+        /// Xorshift pseudo random number.
+        fn random(&mut self) -> u64 {
+            self.seed ^= self.seed << 13;
+            self.seed ^= self.seed >> 7;
+            self.seed ^= self.seed << 17;
+            self.seed
+        }
+
+        fn send(&mut self, packets : Vec<Vec<u8>>, to_server : bool) {
+            for packet in packets {
+                if self.random() % 100 >= self.loss_percent {
+                    let arrival = self.clock.now() + self.latency + self.random() % (self.jitter + 1);
+                    self.in_flight.push((arrival, to_server, packet));
+                }
+            }
+        }
+
+        /// Advance time by one tick, exchanging packets between endpoints.
+        fn tick(&mut self, client : &mut ClientReliableEndpoint<ManualClock>, server : &mut ServerReliableEndpoint<ManualClock>) {
+            self.clock.advance(TICK);
+            let packets = client.transmit();
+            self.send(packets, true);
+            let packets = server.transmit();
+            self.send(packets, false);
+
+            let now = self.clock.now();
+            let (arrived, in_flight) = self.in_flight.drain(..).partition(|(arrival, _, _)| *arrival <= now);
+            self.in_flight = in_flight;
+            for (_, to_server, packet) in arrived {
+                if to_server {
+                    server.receive(&packet).unwrap();
+                } else {
+                    client.receive(&packet).unwrap();
+                }
+            }
+        }
+    }
+
+    fn endpoints(network : &SimulatedNetwork) -> (ClientReliableEndpoint<ManualClock>, ServerReliableEndpoint<ManualClock>) {
+        (ClientReliableEndpoint::new(network.clock.clone(), UDP_MTU, RESEND_TIMEOUT), 
+            ServerReliableEndpoint::new(network.clock.clone(), UDP_MTU, RESEND_TIMEOUT))
+    }
+
+    fn message(i : u16) -> ClientMessage {
+        ClientMessage::new(ClientPayload::Test { p16: i, p32: i as u32 })
+    }
+
+    fn index(message : ClientMessage) -> u16 {
+        match message.payload {
+            ClientPayload::Test { p16, .. } => p16,
+            _ => panic!("Unexpected payload"),
+        }
+    }
+
+    /// Send 500 messages over 50 ticks, then let network settle and collect messages received by server.
+    /// 
+    /// Messages refused by a full receive window are sent on later ticks.
+    fn exchange(network : &mut SimulatedNetwork, delivery : Delivery) -> Vec<u16> {
+        let (mut client, mut server) = endpoints(network);
+        let mut received = Vec::new();
+        let mut queued = VecDeque::new();
+
+        for tick in 0..300u16 {
+            if tick < 50 {
+                queued.extend((0..10).map(|i| tick * 10 + i));
+            }
+            while let Some(i) = queued.front() {
+                match client.send(&message(*i), delivery) {
+                    Ok(()) => { queued.pop_front(); },
+                    Err(err) if err.kind() == ErrorKind::TooManyPending => break,
+                    Err(err) => panic!("{}", err),
+                }
+            }
+            network.tick(&mut client, &mut server);
+            while let Some(message) = server.poll() {
+                received.push(index(message));
+            }
+        }
+
+        if delivery.is_reliable() {
+            assert!(queued.is_empty());
+            assert_eq!(client.pending(), 0);
+        }
+        received
+    }
+
+    #[test]
+    fn v1_perfect_network() {
+        // V1 : [ReliableEndpoint] delivers messages of every [Delivery] over a perfect network.
+        for delivery in [Delivery::Unreliable, Delivery::UnreliableSequenced, Delivery::ReliableUnordered, Delivery::ReliableOrdered] {
+            let mut network = SimulatedNetwork::new(0, 20, 0);
+            assert_eq!(exchange(&mut network, delivery), (0..500).collect::<Vec<_>>(), "{:?}", delivery);
+        }
+    }
+
+    #[test]
+    fn v2_reliable_ordered() {
+        // V2 : [Delivery::ReliableOrdered] messages are delivered once and in order over a lossy network.
+        let mut network = SimulatedNetwork::new(30, 20, 40);
+        assert_eq!(exchange(&mut network, Delivery::ReliableOrdered), (0..500).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn v3_reliable_unordered() {
+        // V3 : [Delivery::ReliableUnordered] messages are delivered once over a lossy network.
+        let mut network = SimulatedNetwork::new(30, 20, 40);
+        let mut received = exchange(&mut network, Delivery::ReliableUnordered);
+        assert_ne!(received, (0..500).collect::<Vec<_>>(), "Jitter should have reordered messages");
+        received.sort();
+        assert_eq!(received, (0..500).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn v4_unreliable_sequenced() {
+        // V4 : [Delivery::UnreliableSequenced] messages are delivered in increasing order over a lossy network.
+        let mut network = SimulatedNetwork::new(30, 20, 40);
+        let received = exchange(&mut network, Delivery::UnreliableSequenced);
+        assert!(!received.is_empty() && received.len() < 500);
+        assert!(received.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn v5_unreliable() {
+        // V5 : [Delivery::Unreliable] messages are delivered at most once over a lossy network.
+        let mut network = SimulatedNetwork::new(30, 20, 40);
+        let mut received = exchange(&mut network, Delivery::Unreliable);
+        assert!(!received.is_empty() && received.len() < 500);
+        received.sort();
+        received.dedup();
+        assert!(received.len() < 500);
+    }
+
+    #[test]
+    fn v6_resend_timeout() {
+        // V6 : [ReliableEndpoint::transmit] resends reliable messages only after resend timeout.
+        let network = SimulatedNetwork::new(0, 0, 0);
+        let (mut client, _) = endpoints(&network);
+
+        client.send(&message(1), Delivery::ReliableOrdered).unwrap();
+        client.send(&message(2), Delivery::Unreliable).unwrap();
+        let first = client.transmit();
+        assert_eq!(first.len(), 1);
+
+        network.clock.advance(RESEND_TIMEOUT - 1);
+        assert!(client.transmit().is_empty());
+
+        network.clock.advance(1);
+        let resent = client.transmit();
+        assert_eq!(resent.len(), 1);
+        
+        // Same entries without the unreliable message, in a new packet
+        assert_ne!(resent[0][0..4], first[0][0..4]);
+        assert!(resent[0].len() < first[0].len());
+        assert_eq!(resent[0][12..], first[0][12..resent[0].len()]);
+        assert_eq!(client.pending(), 1);
+    }
+
+    #[test]
+    fn v7_receive_malformed() {
+        // V7 : [ReliableEndpoint::receive] returns [`ErrorKind::InvalidMessage`] for malformed packets.
+        let network = SimulatedNetwork::new(0, 0, 0);
+        let (mut client, mut server) = endpoints(&network);
+
+        client.send(&message(1), Delivery::ReliableOrdered).unwrap();
+        let packet = client.transmit().remove(0);
+
+        // Too short for header
+        assert_eq!(server.receive(&packet[..5]), Err(ErrorKind::InvalidMessage.into()));
+
+        // Truncated entry
+        assert_eq!(server.receive(&packet[..packet.len() - 1]), Err(ErrorKind::InvalidMessage.into()));
+
+        // Unknown delivery
+        let mut unknown = packet.clone();
+        unknown[12] = 9;
+        assert_eq!(server.receive(&unknown), Err(ErrorKind::InvalidMessage.into()));
+
+        // Nothing was delivered nor registered
+        assert_eq!(server.poll(), None);
+        assert_eq!(server.receive(&packet), Ok(()));
+        assert_eq!(server.poll(), Some(message(1)));
+    }
+
+    #[test]
+    fn v8_send_greater_than_mtu() {
+        // V8 : [ReliableEndpoint::send] returns [`ErrorKind::MessageSizeGreaterThanLimit`] for message bigger than MTU.
+        let network = SimulatedNetwork::new(0, 0, 0);
+        let mut client = ClientReliableEndpoint::new(network.clock.clone(), 20, RESEND_TIMEOUT);
+
+        assert_eq!(client.send(&message(1), Delivery::ReliableOrdered), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+    }
+
+    #[test]
+    fn v9_send_too_many_pending() {
+        // V9 : [ReliableEndpoint::send] returns [`ErrorKind::TooManyPending`] once [RELIABLE_RECEIVE_WINDOW] reliable messages of a [Delivery] wait for acknowledgement.
+        // Large packets so that every message is acknowledged at once
+        let network = SimulatedNetwork::new(0, 0, 0);
+        let mut client = ClientReliableEndpoint::new(network.clock.clone(), 1 << 16, RESEND_TIMEOUT);
+        let mut server = ServerReliableEndpoint::new(network.clock.clone(), 1 << 16, RESEND_TIMEOUT);
+
+        for i in 0..RELIABLE_RECEIVE_WINDOW {
+            client.send(&message(i), Delivery::ReliableOrdered).unwrap();
+        }
+        assert_eq!(client.send(&message(0), Delivery::ReliableOrdered), Err(ErrorKind::TooManyPending.into()));
+
+        // Other deliveries aren't limited by it
+        client.send(&message(0), Delivery::ReliableUnordered).unwrap();
+        client.send(&message(0), Delivery::Unreliable).unwrap();
+
+        // Acknowledged messages free room
+        for packet in client.transmit() {
+            server.receive(&packet).unwrap();
+        }
+        for packet in server.transmit() {
+            client.receive(&packet).unwrap();
+        }
+        assert_eq!(client.pending(), 0);
+        client.send(&message(0), Delivery::ReliableOrdered).unwrap();
+    }
+
+    #[test]
+    fn v10_receive_window() {
+        // V10 : [ReliableEndpoint::receive] refuses reliable messages ahead of the receive window without acknowledging them, so they are resent.
+        // Client with a larger window sends messages beyond the window of server, with the first packet lost
+        let network = SimulatedNetwork::new(0, 0, 0);
+        let mut client = ClientReliableEndpoint::new(network.clock.clone(), UDP_MTU, RESEND_TIMEOUT).with_receive_window(1024);
+        let (_, mut server) = endpoints(&network);
+
+        for i in 0..300 {
+            client.send(&message(i), Delivery::ReliableOrdered).unwrap();
+        }
+        let mut packets = client.transmit();
+        let first = packets.remove(0);
+        let refused = packets.iter().filter(|packet| server.receive(packet) == Err(ErrorKind::TooManyPending.into())).count();
+        assert!(refused > 0);
+        assert_eq!(server.poll(), None);
+
+        // Messages buffered are delivered once the gap is filled, without those refused
+        server.receive(&first).unwrap();
+        let received : Vec<u16> = std::iter::from_fn(|| server.poll()).map(index).collect();
+        assert!(received.len() <= RELIABLE_RECEIVE_WINDOW as usize);
+        assert_eq!(received, (0..received.len() as u16).collect::<Vec<_>>());
+
+        // Refused messages weren't acknowledged and are resent
+        for packet in server.transmit() {
+            client.receive(&packet).unwrap();
+        }
+        assert_eq!(client.pending(), 300 - received.len());
+        network.clock.advance(RESEND_TIMEOUT);
+        for packet in client.transmit() {
+            server.receive(&packet).unwrap();
+        }
+        let resent : Vec<u16> = std::iter::from_fn(|| server.poll()).map(index).collect();
+        assert_eq!(resent, (received.len() as u16..300).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,501 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+
+use std::{collections::HashMap, future::Future, pin::Pin, sync::{Arc, Mutex}, task::{Context, Poll, Waker}};
+
+use crate::net::{ClientMessage, ClientPayload, Clock, Error, ErrorKind, Message, ServerMessage, ServerPayload, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Default time in milliseconds before an unanswered request times out.
+pub const RPC_TIMEOUT : u64 = 10_000;
+
+/// Callback handling the reply of a request.
+type Callback = Box<dyn FnOnce(Result<ServerMessage, Error>) + Send>;
+
+/// Reply shared between [`RpcClient`] and [`PendingReply`].
+#[derive(Default)]
+struct Slot {
+    result : Option<Result<ServerMessage, Error>>,
+    waker : Option<Waker>,
+}
+
+/// Handler of the reply of a pending request.
+enum Handler {
+    Callback(Callback),
+    Future(Arc<Mutex<Slot>>),
+}
+
+impl Handler {
+    fn complete(self, result : Result<ServerMessage, Error>) {
+        match self {
+            Handler::Callback(callback) => callback(result),
+            Handler::Future(slot) => {
+                let mut slot = slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                slot.result = Some(result);
+                if let Some(waker) = slot.waker.take() {
+                    waker.wake();
+                }
+            },
+        }
+    }
+}
+
+/// Request waiting for its reply.
+struct Pending {
+    /// Time in milliseconds when request times out.
+    deadline : u64,
+
+    handler : Handler,
+}
+
+/// Pack a message without its size header.
+fn pack<M : Message>(message : &M) -> Result<Box<[u8]>, Error> {
+    let mut buffer = vec![0u8; MESSAGE_SIZE_TYPE_SIZE + message.size()];
+    let size = message.pack_bytes(&mut buffer)?;
+    Ok(buffer[MESSAGE_SIZE_TYPE_SIZE..MESSAGE_SIZE_TYPE_SIZE + size].into())
+}
+
+/// Check that a wrapping message fits its limit.
+fn within_limit<M : Message>(message : M) -> Result<M, Error> {
+    // Size saturates at the size header limit, so let pack_bytes check it before needing a buffer
+    match message.pack_bytes(&mut []) {
+        Err(err) if err.kind() == ErrorKind::MessageSizeGreaterThanLimit => Err(err),
+        _ if message.size() > M::MAX_SIZE => Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, M::MAX_SIZE, message.size())),
+        _ => Ok(message),
+    }
+}
+
+/// Unwrap a message received by server.
+/// 
+/// # Returns
+/// [`Result`] which is:
+/// - [`Ok`]: Id of the request with the client message it carries if message is a [`ClientPayload::Request`], else [`None`] with the message itself.
+/// - [`Err`]: [`ErrorKind::InvalidMessage`] if request doesn't carry a single valid client message.
+pub fn open_request(message : ClientMessage) -> Result<(Option<u32>, ClientMessage), Error> {
+    match &message.payload {
+        ClientPayload::Request { id, data } => match ClientMessage::from_bytes(data)? {
+            inner if matches!(inner.payload, ClientPayload::Request { .. }) => Err(ErrorKind::InvalidMessage.into()),
+            inner => Ok((Some(*id), inner)),
+        },
+        _ => Ok((None, message)),
+    }
+}
+
+/// Wrap a server message answering a request.
+/// 
+/// The reply keeps the timestamp of the message.
+/// 
+/// # Returns
+/// [`Result`] which is:
+/// - [`Ok`]: [`ServerPayload::Reply`] to send.
+/// - [`Err`]: [`ErrorKind::MessageSizeGreaterThanLimit`] if the reply can't fit a message.
+pub fn reply(id : u32, message : &ServerMessage) -> Result<ServerMessage, Error> {
+    within_limit(ServerMessage::new(message.timestamp, ServerPayload::Reply { id, data: pack(message)? }))
+}
+
+/// Future resolved with the reply of a request sent by a [`RpcClient`].
+/// 
+/// Resolves with [`ErrorKind::TimedOut`] once expired by [`RpcClient::expire`], or [`ErrorKind::ConnectionClosed`] if 
+/// the request was cancelled or the [`RpcClient`] dropped.
+pub struct PendingReply {
+    id : u32,
+    slot : Arc<Mutex<Slot>>,
+}
+
+impl PendingReply {
+    /// Id of the request.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl std::fmt::Debug for PendingReply {
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingReply").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+impl Future for PendingReply {
+    type Output = Result<ServerMessage, Error>;
+
+    fn poll(self : Pin<&mut Self>, cx : &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+/// Client side table of requests waiting for their reply.
+/// 
+/// Requests are wrapped in a [`ClientPayload::Request`] with an unique id, and server answers them with a [`reply`].
+/// Replies are handled by a callback or a [`PendingReply`] future, so a [`ServerPayload::Error`] can be matched to 
+/// the request that caused it. Every other message received is an unsolicited push given back to the caller.
+/// 
+/// Time is given by a [`Clock`] and requests time out when calling [`expire`](Self::expire).
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ ClientMessage, ClientPayload, ManualClock, RpcClient, ServerMessage, ServerPayload, open_request, reply };
+/// 
+/// let mut client = RpcClient::new(ManualClock::new(0), 1000);
+/// let (request, mut pending) = client.call(&ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 })).unwrap();
+/// 
+/// // Server answers the request with an error
+/// let (id, message) = open_request(request).unwrap();
+/// assert!(matches!(message.payload, ClientPayload::Test { p16: 1, p32: 2 }));
+/// let answer = reply(id.unwrap(), &ServerMessage::new(10, ServerPayload::Error { err: 4001 })).unwrap();
+/// 
+/// // Reply resolves the future instead of being given back
+/// assert!(client.receive(answer).is_none());
+/// assert!(client.is_empty());
+/// ```
+pub struct RpcClient<C : Clock> {
+    clock : C,
+
+    /// Time in milliseconds before an unanswered request times out.
+    timeout : u64,
+
+    /// Id of the next request.
+    next_id : u32,
+
+    pending : HashMap<u32, Pending>,
+}
+
+impl<C : Clock> RpcClient<C> {
+    /// Create a new [`RpcClient`].
+    /// 
+    /// # Argument(s)
+    /// * `clock` - [`Clock`] of timeouts.
+    /// * `timeout` - Time in milliseconds before an unanswered request times out, usually [`RPC_TIMEOUT`].
+    pub fn new(clock : C, timeout : u64) -> RpcClient<C> {
+        RpcClient { clock, timeout, next_id: 0, pending: HashMap::new() }
+    }
+
+    /// Count of requests waiting for their reply.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns true if no request is waiting for its reply.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns true if request is waiting for its reply.
+    pub fn is_pending(&self, id : u32) -> bool {
+        self.pending.contains_key(&id)
+    }
+
+    /// Wrap a request whose reply resolves the returned [`PendingReply`].
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`ClientPayload::Request`] to send with the [`PendingReply`] of the request.
+    /// - [`Err`]: [`ErrorKind::MessageSizeGreaterThanLimit`] if the request can't fit a message.
+    pub fn call(&mut self, message : &ClientMessage) -> Result<(ClientMessage, PendingReply), Error> {
+        let slot = Arc::new(Mutex::new(Slot::default()));
+        let request = self.wrap(message, Handler::Future(slot.clone()))?;
+        let ClientPayload::Request { id, .. } = request.payload else {
+            unreachable!("wrapped message is always a request");
+        };
+
+        Ok((request, PendingReply { id, slot }))
+    }
+
+    /// Wrap a request whose reply is handled by a callback.
+    /// 
+    /// Callback is called by [`receive`](Self::receive), [`expire`](Self::expire) or [`cancel`](Self::cancel).
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`ClientPayload::Request`] to send.
+    /// - [`Err`]: [`ErrorKind::MessageSizeGreaterThanLimit`] if the request can't fit a message.
+    pub fn call_with<F : FnOnce(Result<ServerMessage, Error>) + Send + 'static>(&mut self, message : &ClientMessage, callback : F) -> Result<ClientMessage, Error> {
+        self.wrap(message, Handler::Callback(Box::new(callback)))
+    }
+
+    /// Handle a message received from server.
+    /// 
+    /// Replies of pending requests are given to their handler, with an error if malformed. Replies 
+    /// of unknown requests, like those already timed out, are dropped.
+    /// 
+    /// # Returns
+    /// Message if it isn't a reply, [`None`] otherwise.
+    pub fn receive(&mut self, message : ServerMessage) -> Option<ServerMessage> {
+        let ServerPayload::Reply { id, data } = &message.payload else {
+            return Some(message);
+        };
+
+        if let Some(pending) = self.pending.remove(id) {
+            pending.handler.complete(match ServerMessage::from_bytes(data) {
+                Ok(inner) if matches!(inner.payload, ServerPayload::Reply { .. }) => Err(ErrorKind::InvalidMessage.into()),
+                result => result,
+            });
+        }
+        None
+    }
+
+    /// Time out requests unanswered since timeout, resolving them with [`ErrorKind::TimedOut`].
+    /// 
+    /// # Returns
+    /// Count of requests timed out.
+    pub fn expire(&mut self) -> usize {
+        let now = self.clock.now();
+        let expired : Vec<u32> = self.pending.iter().filter(|(_, pending)| pending.deadline <= now).map(|(id, _)| *id).collect();
+
+        for id in &expired {
+            if let Some(pending) = self.pending.remove(id) {
+                pending.handler.complete(Err(ErrorKind::TimedOut.into()));
+            }
+        }
+        expired.len()
+    }
+
+    /// Stop waiting for the reply of a request, resolving it with [`ErrorKind::ConnectionClosed`].
+    /// 
+    /// # Returns
+    /// True if request was pending.
+    pub fn cancel(&mut self, id : u32) -> bool {
+        match self.pending.remove(&id) {
+            Some(pending) => {
+                pending.handler.complete(Err(ErrorKind::ConnectionClosed.into()));
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Wrap message in a request with an unused id and add it to pending.
+    fn wrap(&mut self, message : &ClientMessage, handler : Handler) -> Result<ClientMessage, Error> {
+        while self.pending.contains_key(&self.next_id) {
+            self.next_id = self.next_id.wrapping_add(1);
+        }
+
+        let id = self.next_id;
+        let request = within_limit(ClientMessage::new(ClientPayload::Request { id, data: pack(message)? }))?;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.pending.insert(id, Pending { deadline: self.clock.now().saturating_add(self.timeout), handler });
+
+        Ok(request)
+    }
+}
+
+impl<C : Clock> Drop for RpcClient<C> {
+    fn drop(&mut self) {
+        for (_, pending) in self.pending.drain() {
+            pending.handler.complete(Err(ErrorKind::ConnectionClosed.into()));
+        }
+    }
+}
+
+
+/// This module test [RpcClient], [open_request] and [reply].
+/// 
+/// # Verification(s)
+/// V1 : [open_request] unwraps requests and passes other messages through.
+/// V2 : [RpcClient::call] future resolves with the reply of its request.
+/// V3 : [RpcClient::call_with] callback is called with the reply of its request.
+/// V4 : [RpcClient::receive] gives back unsolicited messages and matches replies received out of order.
+/// V5 : [RpcClient::expire] resolves unanswered requests with [`ErrorKind::TimedOut`](crate::net::ErrorKind::TimedOut) and drops late replies.
+/// V6 : [RpcClient::cancel] and dropping [RpcClient] resolve pending requests with [`ErrorKind::ConnectionClosed`](crate::net::ErrorKind::ConnectionClosed).
+/// V7 : Malformed requests and replies give an error, nested ones give [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage).
+/// V8 : [RpcClient::call] and [reply] return [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when wrapped message can't fit.
+#[cfg(test)]
+mod tests {
+    use std::sync::{mpsc, Arc, Mutex};
+
+    use futures::{executor::block_on, FutureExt};
+
+    use crate::net::{ClientMessage, ClientPayload, Clock, ErrorKind, ManualClock, RpcClient, ServerMessage, ServerPayload, open_request, reply};
+
+    const TIMEOUT : u64 = 1000;
+
+    fn test_request(p16 : u16) -> ClientMessage {
+        ClientMessage::new(ClientPayload::Test { p16, p32: 0 })
+    }
+
+    /// Answer a request with a test payload echoing it.
+    fn answer(request : ClientMessage, timestamp : u64) -> ServerMessage {
+        let (id, message) = open_request(request).unwrap();
+        let ClientPayload::Test { p16, p32 } = message.payload else { panic!("not a test request") };
+        reply(id.unwrap(), &ServerMessage::new(timestamp, ServerPayload::Test { p16, p32 })).unwrap()
+    }
+
+    #[test]
+    fn v1_open_request() {
+        // V1 : [open_request] unwraps requests and passes other messages through.
+        let (id, message) = open_request(test_request(5)).unwrap();
+        assert_eq!(id, None);
+        assert!(matches!(message.payload, ClientPayload::Test { p16: 5, p32: 0 }));
+
+        let mut client = RpcClient::new(ManualClock::new(0), TIMEOUT);
+        let (first, _) = client.call(&test_request(6)).unwrap();
+        let (second, _) = client.call(&test_request(7)).unwrap();
+
+        let (first_id, message) = open_request(first).unwrap();
+        assert!(matches!(message.payload, ClientPayload::Test { p16: 6, p32: 0 }));
+        let (second_id, message) = open_request(second).unwrap();
+        assert!(matches!(message.payload, ClientPayload::Test { p16: 7, p32: 0 }));
+        assert_ne!(first_id.unwrap(), second_id.unwrap());
+    }
+
+    #[test]
+    fn v2_future() {
+        // V2 : [RpcClient::call] future resolves with the reply of its request.
+        let mut client = RpcClient::new(ManualClock::new(0), TIMEOUT);
+        let (request, mut pending) = client.call(&test_request(9)).unwrap();
+        assert!(client.is_pending(pending.id()));
+        assert!((&mut pending).now_or_never().is_none());
+
+        assert!(client.receive(answer(request, 77)).is_none());
+        assert!(client.is_empty());
+
+        let replied = block_on(pending).unwrap();
+        assert_eq!(replied.timestamp, 77);
+        assert!(matches!(replied.payload, ServerPayload::Test { p16: 9, p32: 0 }));
+    }
+
+    #[test]
+    fn v3_callback() {
+        // V3 : [RpcClient::call_with] callback is called with the reply of its request.
+        let mut client = RpcClient::new(ManualClock::new(0), TIMEOUT);
+        let (sender, receiver) = mpsc::channel();
+        let request = client.call_with(&test_request(3), move |result| sender.send(result).unwrap()).unwrap();
+        assert!(receiver.try_recv().is_err());
+
+        // Server refuses with an error matched to the request
+        let (id, _) = open_request(request).unwrap();
+        client.receive(reply(id.unwrap(), &ServerMessage::new(5, ServerPayload::Error { err: 4001 })).unwrap());
+
+        assert!(matches!(receiver.try_recv().unwrap().unwrap().payload, ServerPayload::Error { err: 4001 }));
+        assert!(client.is_empty());
+    }
+
+    #[test]
+    fn v4_unsolicited_and_out_of_order() {
+        // V4 : [RpcClient::receive] gives back unsolicited messages and matches replies received out of order.
+        let mut client = RpcClient::new(ManualClock::new(0), TIMEOUT);
+        let replies = Arc::new(Mutex::new(Vec::new()));
+
+        let mut requests = Vec::new();
+        for p16 in 0..4 {
+            let replies = replies.clone();
+            requests.push(client.call_with(&test_request(p16), move |result| {
+                if let ServerPayload::Test { p16: replied, .. } = result.unwrap().payload {
+                    replies.lock().unwrap().push((p16, replied));
+                }
+            }).unwrap());
+        }
+
+        for (index, request) in requests.into_iter().enumerate().rev() {
+            let pushed = client.receive(ServerMessage::new(index as u64, ServerPayload::Test { p16: 100, p32: 0 })).unwrap();
+            assert!(matches!(pushed.payload, ServerPayload::Test { p16: 100, p32: 0 }));
+            assert!(client.receive(answer(request, 0)).is_none());
+        }
+
+        let replies = replies.lock().unwrap();
+        assert_eq!(replies.len(), 4);
+        assert!(replies.iter().all(|(p16, replied)| p16 == replied));
+    }
+
+    #[test]
+    fn v5_expire() {
+        // V5 : [RpcClient::expire] resolves unanswered requests with [`ErrorKind::TimedOut`](crate::net::ErrorKind::TimedOut) and drops late replies.
+        let clock = ManualClock::new(0);
+        let mut client = RpcClient::new(clock.clone(), TIMEOUT);
+        let (late, pending) = client.call(&test_request(1)).unwrap();
+
+        clock.advance(TIMEOUT / 2);
+        let (on_time, answered) = client.call(&test_request(2)).unwrap();
+        assert_eq!(client.expire(), 0);
+
+        clock.advance(TIMEOUT / 2);
+        assert_eq!(client.expire(), 1);
+        assert_eq!(block_on(pending).unwrap_err().kind(), ErrorKind::TimedOut);
+        assert_eq!(client.len(), 1);
+
+        assert!(client.receive(answer(late, clock.now())).is_none());
+        assert_eq!(client.len(), 1);
+
+        client.receive(answer(on_time, clock.now()));
+        assert!(block_on(answered).is_ok());
+    }
+
+    #[test]
+    fn v6_cancel_and_drop() {
+        // V6 : [RpcClient::cancel] and dropping [RpcClient] resolve pending requests with [`ErrorKind::ConnectionClosed`](crate::net::ErrorKind::ConnectionClosed).
+        let mut client = RpcClient::new(ManualClock::new(0), TIMEOUT);
+        let (_, cancelled) = client.call(&test_request(1)).unwrap();
+        let (sender, receiver) = mpsc::channel();
+        client.call_with(&test_request(2), move |result| sender.send(result).unwrap()).unwrap();
+
+        assert!(client.cancel(cancelled.id()));
+        assert!(!client.cancel(cancelled.id()));
+        assert_eq!(block_on(cancelled).unwrap_err().kind(), ErrorKind::ConnectionClosed);
+
+        drop(client);
+        assert_eq!(receiver.try_recv().unwrap().unwrap_err().kind(), ErrorKind::ConnectionClosed);
+    }
+
+    #[test]
+    fn v7_invalid() {
+        // V7 : Malformed requests and replies give an error, nested ones give [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage).
+        let malformed = ClientMessage::new(ClientPayload::Request { id: 0, data: vec![1, 2, 3].into() });
+        assert!(open_request(malformed).is_err());
+
+        let mut client = RpcClient::new(ManualClock::new(0), TIMEOUT);
+        let (request, _) = client.call(&test_request(1)).unwrap();
+        let (nested, _) = client.call(&request).unwrap();
+        assert_eq!(open_request(nested).unwrap_err().kind(), ErrorKind::InvalidMessage);
+
+        let (_, malformed) = client.call(&test_request(2)).unwrap();
+        client.receive(ServerMessage::new(0, ServerPayload::Reply { id: malformed.id(), data: vec![0xFF; 4].into() }));
+        assert!(block_on(malformed).is_err());
+
+        let (_, nested) = client.call(&test_request(3)).unwrap();
+        let inner = reply(99, &ServerMessage::new(0, ServerPayload::Test { p16: 0, p32: 0 })).unwrap();
+        client.receive(reply(nested.id(), &inner).unwrap());
+        assert_eq!(block_on(nested).unwrap_err().kind(), ErrorKind::InvalidMessage);
+    }
+
+    #[test]
+    fn v8_too_large() {
+        // V8 : [RpcClient::call] and [reply] return [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when wrapped message can't fit.
+        let mut client = RpcClient::new(ManualClock::new(0), TIMEOUT);
+        let mut request = client.call(&test_request(0)).unwrap().0;
+        while let Ok((wrapped, _)) = client.call(&request) {
+            request = wrapped;
+        }
+        assert_eq!(client.call(&request).unwrap_err().kind(), ErrorKind::MessageSizeGreaterThanLimit);
+
+        let large = ServerMessage::new(0, ServerPayload::Reply { id: 0, data: vec![0u8; 65_510].into() });
+        assert_eq!(reply(0, &large).unwrap_err().kind(), ErrorKind::MessageSizeGreaterThanLimit);
+    }
+}
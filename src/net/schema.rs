@@ -0,0 +1,598 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+
+use std::fmt::Write;
+
+use crate::net::{ClientMessage, ClientPayload, ServerMessage, ServerPayload, PROTOCOL_VERSION, SCHEMA_HASH};
+
+/// Doc comment of attributes, ignoring other attributes. Used by generated layouts.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! schema_doc {
+    (@line doc = $doc:literal) => { concat!($doc, "\n") };
+    (@line $($other:tt)*) => { "" };
+    ( $( [ $($attr:tt)* ] )* ) => { concat!("" $(, $crate::schema_doc!(@line $($attr)*))*) };
+}
+
+/// Maximum length of a field, if declared. Used by generated layouts.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! schema_max {
+    () => { None };
+    ($max:expr) => { Some($max) };
+}
+
+/// Layout of a payload field or message extra field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// Name of the field.
+    pub name : &'static str,
+
+    /// Rust type of the field, which gives its wire format, see [`PayloadField`](crate::net::PayloadField).
+    pub ty : &'static str,
+
+    /// Packed size in bytes, [`None`] if variable-length or optional.
+    pub size : Option<usize>,
+
+    /// Maximum length declared with `[max N]`, if any.
+    pub max : Option<usize>,
+
+    /// True if presence is packed in the bitmap following the discriminant.
+    pub optional : bool,
+
+    /// Layout of the nested type of the field or of its elements, if any.
+    pub nested : Option<&'static NestedSchema>,
+
+    /// Doc comment of the field.
+    pub doc : &'static str,
+}
+
+/// Layout of a single payload of a payload enumeration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariantSchema {
+    /// Name of the payload.
+    pub name : &'static str,
+
+    /// Discriminant packed first.
+    pub discriminant : u16,
+
+    /// Fields in packing order.
+    pub fields : &'static [FieldSchema],
+
+    /// Doc comment of the payload.
+    pub doc : &'static str,
+}
+
+impl VariantSchema {
+    /// Size in bytes of the presence bitmap of optional fields following the discriminant.
+    pub fn bitmap_size(&self) -> usize {
+        self.fields.iter().filter(|field| field.optional).count().div_ceil(8)
+    }
+}
+
+/// Layout of a payload enumeration generated by [`write_messages_payloads!`](crate::write_messages_payloads) or derived with
+/// [`EthosPayload`](crate::net::EthosPayload), see `LAYOUT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadSchema {
+    /// Name of the enumeration.
+    pub name : &'static str,
+
+    /// Payloads in declaration order.
+    pub variants : &'static [VariantSchema],
+
+    /// Doc comment of the enumeration.
+    pub doc : &'static str,
+}
+
+impl PayloadSchema {
+    /// Layout of a payload by discriminant.
+    pub fn variant(&self, discriminant : u16) -> Option<&'static VariantSchema> {
+        self.variants.iter().find(|variant| variant.discriminant == discriminant)
+    }
+
+    /// Layout of a payload by name.
+    pub fn variant_named(&self, name : &str) -> Option<&'static VariantSchema> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+
+    /// Layouts of nested types of fields, including those of nested types, in order of first use.
+    pub fn nested(&self) -> Vec<&'static NestedSchema> {
+        let mut nested = Vec::new();
+        for variant in self.variants {
+            collect_nested(variant.fields, &mut nested);
+        }
+        nested
+    }
+}
+
+/// Add layouts of nested types of fields not collected yet.
+fn collect_nested(fields : &'static [FieldSchema], nested : &mut Vec<&'static NestedSchema>) {
+    for layout in fields.iter().filter_map(|field| field.nested) {
+        if !nested.iter().any(|collected| collected.name == layout.name) {
+            nested.push(layout);
+            collect_nested(layout.fields, nested);
+            for variant in layout.variants {
+                collect_nested(variant.fields, nested);
+            }
+        }
+    }
+}
+
+/// Layout of a nested type generated by [`write_payload_struct!`](crate::write_payload_struct) or 
+/// [`write_payload_enum!`](crate::write_payload_enum), see [`PayloadField::NESTED`](crate::net::PayloadField::NESTED).
+/// 
+/// Optional fields of nested types have their presence packed as a u8 before the value, so none is in a bitmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NestedSchema {
+    /// Name of the type.
+    pub name : &'static str,
+
+    /// Fields of a struct in packing order, empty for an enumeration.
+    pub fields : &'static [FieldSchema],
+
+    /// Variants of an enumeration, with their u8 discriminant packed first. Empty for a struct.
+    pub variants : &'static [VariantSchema],
+
+    /// Doc comment of the type.
+    pub doc : &'static str,
+}
+
+/// Layout of a message generated by [`write_messages_struct!`](crate::write_messages_struct), see `LAYOUT`.
+/// 
+/// A message is packed as its size header, its payload then its extra fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageSchema {
+    /// Name of the message.
+    pub name : &'static str,
+
+    /// Name of the payload enumeration.
+    pub payload : &'static str,
+
+    /// Maximum size in bytes of the message, excluding size header.
+    pub max_size : usize,
+
+    /// Extra fields packed after the payload.
+    pub extras : &'static [FieldSchema],
+
+    /// Doc comment of the message.
+    pub doc : &'static str,
+}
+
+/// Protocol schema of [`ClientMessage`] and [`ServerMessage`] as pretty-printed JSON.
+/// 
+/// Contains [`PROTOCOL_VERSION`], [`SCHEMA_HASH`], then the layout of each message with its payloads. Output is 
+/// deterministic, so it can be committed and diffed to review protocol changes. Exported schemas are compared with 
+/// [`check_compatibility`](crate::net::check_compatibility).
+/// 
+/// # Example(s)
+/// ```
+/// let json = ethos_core::net::schema_json();
+/// assert!(json.contains("\"name\": \"ClientMessage\""));
+/// ```
+pub fn schema_json() -> String {
+    let mut json = JsonWriter::default();
+    json.open('{');
+    json.key("version");
+    json.number(PROTOCOL_VERSION as u64);
+    json.key("schema_hash");
+    json.string(&format!("{:#018x}", SCHEMA_HASH));
+    json.key("size_header");
+    json.string("u16");
+    json.key("discriminant");
+    json.string("u16");
+    json.key("length_prefix");
+    json.string("u16");
+    json.key("messages");
+    json.open('[');
+    write_message(&mut json, &ClientMessage::LAYOUT, &ClientPayload::LAYOUT, ClientPayload::SCHEMA_HASH);
+    write_message(&mut json, &ServerMessage::LAYOUT, &ServerPayload::LAYOUT, ServerPayload::SCHEMA_HASH);
+    json.close(']');
+    json.close('}');
+    json.finish()
+}
+
+/// Layout of a message with its payloads as pretty-printed JSON, same as each message of [`schema_json`].
+/// 
+/// Exports protocols other than [`ClientMessage`] and [`ServerMessage`], with the `SCHEMA_HASH` of the payload enumeration.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{message_json, ServerMessage, ServerPayload};
+/// 
+/// let json = message_json(&ServerMessage::LAYOUT, &ServerPayload::LAYOUT, ServerPayload::SCHEMA_HASH);
+/// assert!(json.contains("\"name\": \"ServerMessage\""));
+/// ```
+pub fn message_json(message : &MessageSchema, payload : &PayloadSchema, schema_hash : u64) -> String {
+    let mut json = JsonWriter::default();
+    write_message(&mut json, message, payload, schema_hash);
+    json.finish()
+}
+
+/// Layout of a payload enumeration as pretty-printed JSON, same as the payload of each message of [`schema_json`].
+/// 
+/// Exports payloads derived with [`EthosPayload`](crate::net::EthosPayload), with their `LAYOUT` and `SCHEMA_HASH`.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{payload_json, ClientPayload};
+/// 
+/// let json = payload_json(&ClientPayload::LAYOUT, ClientPayload::SCHEMA_HASH);
+/// assert!(json.contains("\"name\": \"Hello\""));
+/// ```
+pub fn payload_json(payload : &PayloadSchema, schema_hash : u64) -> String {
+    let mut json = JsonWriter::default();
+    write_payload(&mut json, payload, schema_hash);
+    json.finish()
+}
+
+/// Write a message with its payloads.
+fn write_message(json : &mut JsonWriter, message : &MessageSchema, payload : &PayloadSchema, schema_hash : u64) {
+    json.open('{');
+    json.key("name");
+    json.string(message.name);
+    json.key("doc");
+    json.string(&clean_doc(message.doc));
+    json.key("max_size");
+    json.number(message.max_size as u64);
+    json.key("extras");
+    write_fields(json, message.extras);
+    json.key("payload");
+    write_payload(json, payload, schema_hash);
+    json.close('}');
+}
+
+/// Write a payload enumeration with its payloads.
+fn write_payload(json : &mut JsonWriter, payload : &PayloadSchema, schema_hash : u64) {
+    json.open('{');
+    json.key("name");
+    json.string(payload.name);
+    json.key("doc");
+    json.string(&clean_doc(payload.doc));
+    json.key("schema_hash");
+    json.string(&format!("{:#018x}", schema_hash));
+    json.key("variants");
+    write_variants(json, payload.variants);
+    json.key("nested");
+    json.open('[');
+    for nested in payload.nested() {
+        json.open('{');
+        json.key("name");
+        json.string(nested.name);
+        json.key("doc");
+        json.string(&clean_doc(nested.doc));
+        json.key("fields");
+        write_fields(json, nested.fields);
+        json.key("variants");
+        write_variants(json, nested.variants);
+        json.close('}');
+    }
+    json.close(']');
+    json.close('}');
+}
+
+/// Write a list of payloads or variants of a nested enumeration.
+fn write_variants(json : &mut JsonWriter, variants : &[VariantSchema]) {
+    json.open('[');
+    for variant in variants {
+        json.open('{');
+        json.key("name");
+        json.string(variant.name);
+        json.key("discriminant");
+        json.number(variant.discriminant as u64);
+        json.key("doc");
+        json.string(&clean_doc(variant.doc));
+        json.key("bitmap_size");
+        json.number(variant.bitmap_size() as u64);
+        json.key("fields");
+        write_fields(json, variant.fields);
+        json.close('}');
+    }
+    json.close(']');
+}
+
+/// Write a list of fields.
+fn write_fields(json : &mut JsonWriter, fields : &[FieldSchema]) {
+    json.open('[');
+    for field in fields {
+        json.open('{');
+        json.key("name");
+        json.string(field.name);
+        json.key("type");
+        json.string(field.ty);
+        json.key("size");
+        json.option(field.size);
+        json.key("max");
+        json.option(field.max);
+        json.key("optional");
+        json.raw(if field.optional { "true" } else { "false" });
+        json.key("doc");
+        json.string(&clean_doc(field.doc));
+        json.close('}');
+    }
+    json.close(']');
+}
+
+/// Doc comment without the leading space of each line and trailing new line.
+fn clean_doc(doc : &str) -> String {
+    doc.lines().map(|line| line.strip_prefix(' ').unwrap_or(line)).collect::<Vec<_>>().join("\n").trim().to_string()
+}
+
+/// Minimal pretty-printing JSON writer, with 2 spaces indentation.
+#[derive(Default)]
+struct JsonWriter {
+    out : String,
+
+    /// For each open object or array, true if it has an element yet.
+    stack : Vec<bool>,
+
+    /// True if a key was just written, so the value follows on the same line.
+    after_key : bool,
+}
+
+impl JsonWriter {
+    /// Start a value, separating it from the previous one.
+    fn value(&mut self) {
+        if self.after_key {
+            self.after_key = false;
+            return;
+        }
+
+        if let Some(has_element) = self.stack.last_mut() {
+            if *has_element {
+                self.out.push(',');
+            }
+            *has_element = true;
+            self.out.push('\n');
+            self.indent();
+        }
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.stack.len() {
+            self.out.push_str("  ");
+        }
+    }
+
+    fn open(&mut self, bracket : char) {
+        self.value();
+        self.out.push(bracket);
+        self.stack.push(false);
+    }
+
+    fn close(&mut self, bracket : char) {
+        if self.stack.pop() == Some(true) {
+            self.out.push('\n');
+            self.indent();
+        }
+        self.out.push(bracket);
+    }
+
+    fn key(&mut self, key : &str) {
+        self.value();
+        self.write_string(key);
+        self.out.push_str(": ");
+        self.after_key = true;
+    }
+
+    fn raw(&mut self, raw : &str) {
+        self.value();
+        self.out.push_str(raw);
+    }
+
+    fn number(&mut self, number : u64) {
+        self.raw(&number.to_string());
+    }
+
+    fn option(&mut self, number : Option<usize>) {
+        match number {
+            Some(number) => self.number(number as u64),
+            None => self.raw("null"),
+        }
+    }
+
+    fn string(&mut self, string : &str) {
+        self.value();
+        self.write_string(string);
+    }
+
+    fn write_string(&mut self, string : &str) {
+        self.out.push('"');
+        for c in string.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                c if (c as u32) < 0x20 => { let _ = write!(self.out, "\\u{:04x}", c as u32); },
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push('\n');
+        self.out
+    }
+}
+
+
+/// This module test [PayloadSchema], [MessageSchema], [schema_json], [message_json] and [payload_json].
+/// 
+/// # Verification(s)
+/// V1 : `LAYOUT` of payloads lists every payload in declaration order with unique and valid discriminants.
+/// V2 : `LAYOUT` of fields gives types, sizes, maximum lengths and optional fields.
+/// V3 : `LAYOUT` of messages gives maximum size and extra fields packed after payload.
+/// V4 : `LAYOUT` keeps doc comments only, and [schema_json] cleans them.
+/// V5 : [schema_json] is deterministic and describes every payload of both messages.
+/// V6 : [schema_json] escapes strings.
+/// V7 : [message_json] and [payload_json] give the same objects as [schema_json].
+#[cfg(test)]
+mod tests {
+    use crate::net::{ClientMessage, ClientPayload, Message, ServerMessage, ServerPayload, CLIENT_MSG_MAX_SIZE, MESSAGE_SIZE_TYPE_SIZE, PROTOCOL_VERSION, SCHEMA_HASH};
+
+    use crate::net::client::test_payloads::TestClientPayload;
+
+    use super::{clean_doc, message_json, payload_json, schema_json, JsonWriter, PayloadSchema};
+
+    /// Assert discriminants are unique and valid.
+    fn assert_discriminants(layout : &PayloadSchema, is_valid : fn(u16) -> bool) {
+        for (index, variant) in layout.variants.iter().enumerate() {
+            assert!(is_valid(variant.discriminant), "{} discriminant is invalid", variant.name);
+            assert!(layout.variants[index + 1..].iter().all(|other| other.discriminant != variant.discriminant));
+            assert_eq!(layout.variant(variant.discriminant), Some(variant));
+        }
+    }
+
+    #[test]
+    fn v1_payloads() {
+        // V1 : `LAYOUT` of payloads lists every payload in declaration order with unique and valid discriminants.
+        assert_eq!(ClientPayload::LAYOUT.name, "ClientPayload");
+        assert_eq!(ClientPayload::LAYOUT.variants.first().unwrap().name, "Hello");
+        assert_eq!(ClientPayload::LAYOUT.variants.last().unwrap().name, "Invalid");
+        assert_discriminants(&ClientPayload::LAYOUT, ClientPayload::is_valid);
+
+        assert_eq!(ServerPayload::LAYOUT.name, "ServerPayload");
+        assert_eq!(ServerPayload::LAYOUT.variant_named("Accept").unwrap().discriminant, ServerPayload::Accept.discriminant());
+        assert_eq!(ServerPayload::LAYOUT.variant(65535).unwrap().name, "Invalid");
+        assert!(ServerPayload::LAYOUT.variant(0).is_none());
+        assert_discriminants(&ServerPayload::LAYOUT, ServerPayload::is_valid);
+    }
+
+    #[test]
+    fn v2_fields() {
+        // V2 : `LAYOUT` of fields gives types, sizes, maximum lengths and optional fields.
+        let hello = ClientPayload::LAYOUT.variant_named("Hello").unwrap();
+        assert_eq!(hello.fields.iter().map(|field| (field.name, field.ty, field.size)).collect::<Vec<_>>(), 
+            vec![("version", "u16", Some(2)), ("schema", "u64", Some(8))]);
+
+        let variable = TestClientPayload::LAYOUT.variant_named("Variable").unwrap();
+        assert_eq!(variable.fields.iter().map(|field| (field.size, field.max)).collect::<Vec<_>>(), 
+            vec![(None, Some(32)), (None, Some(8)), (None, Some(64)), (Some(8), None)]);
+        assert_eq!(variable.bitmap_size(), 0);
+
+        let optional = TestClientPayload::LAYOUT.variant_named("Optional").unwrap();
+        assert_eq!(optional.fields.iter().filter(|field| field.optional).count(), 9);
+        assert_eq!(optional.bitmap_size(), 2);
+        assert_eq!(optional.fields[6].max, Some(8));
+
+        let login = ClientPayload::LAYOUT.variant_named("Login").unwrap();
+        assert_eq!(login.fields[0].max, Some(crate::net::ACCOUNT_MAX_LEN));
+    }
+
+    #[test]
+    fn v3_messages() {
+        // V3 : `LAYOUT` of messages gives maximum size and extra fields packed after payload.
+        assert_eq!(ClientMessage::LAYOUT.name, "ClientMessage");
+        assert_eq!(ClientMessage::LAYOUT.payload, "ClientPayload");
+        assert_eq!(ClientMessage::LAYOUT.max_size, CLIENT_MSG_MAX_SIZE);
+        assert!(ClientMessage::LAYOUT.extras.is_empty());
+
+        assert_eq!(ServerMessage::LAYOUT.max_size, ServerMessage::MAX_SIZE);
+        assert_eq!(ServerMessage::LAYOUT.extras.len(), 1);
+        let timestamp = ServerMessage::LAYOUT.extras[0];
+        assert_eq!((timestamp.name, timestamp.ty, timestamp.size), ("timestamp", "u64", Some(8)));
+
+        // Size header, payload then timestamp
+        let message = ServerMessage::new(0x0102030405060708, ServerPayload::Test { p16: 1, p32: 2 });
+        let mut buffer = [0u8; 64];
+        let size = message.pack_bytes(&mut buffer).unwrap();
+        let end = MESSAGE_SIZE_TYPE_SIZE + size;
+        assert_eq!(buffer[end - timestamp.size.unwrap()..end], 0x0102030405060708u64.to_le_bytes());
+    }
+
+    #[test]
+    fn v4_docs() {
+        // V4 : `LAYOUT` keeps doc comments only, and [schema_json] cleans them.
+        let hello = ClientPayload::LAYOUT.variant_named("Hello").unwrap();
+        assert!(hello.doc.starts_with(" First message sent after connecting"));
+        assert_eq!(clean_doc(hello.doc), "First message sent after connecting, checked by [`Handshake`](crate::net::Handshake).\n\n\
+            Discriminant MUST never change so that any version can read it.");
+        assert_eq!(clean_doc(hello.fields[0].doc), "Protocol version of client.");
+
+        // Fields without doc comment
+        let test = ClientPayload::LAYOUT.variant_named("Test").unwrap();
+        assert_eq!(test.fields[0].doc, "");
+        assert_eq!(clean_doc(ClientPayload::LAYOUT.doc), "Payload sent from client to server.\n\nPayload are packed for smaller transfer size.");
+    }
+
+    #[test]
+    fn v5_json() {
+        // V5 : [schema_json] is deterministic and describes every payload of both messages.
+        let json = schema_json();
+        assert_eq!(json, schema_json());
+
+        assert!(json.starts_with(&format!("{{\n  \"version\": {},\n  \"schema_hash\": \"{:#018x}\",", PROTOCOL_VERSION, SCHEMA_HASH)));
+        assert!(json.ends_with("}\n"));
+        assert_eq!(json.matches("\"discriminant\": ").count(), 1 + ClientPayload::LAYOUT.variants.len() + ServerPayload::LAYOUT.variants.len());
+
+        for variant in ClientPayload::LAYOUT.variants.iter().chain(ServerPayload::LAYOUT.variants) {
+            assert!(json.contains(&format!("\"name\": \"{}\",\n", variant.name)));
+            assert!(json.contains(&format!("\"discriminant\": {},\n", variant.discriminant)));
+        }
+        assert!(json.contains("\"name\": \"timestamp\",\n"));
+        assert!(json.contains("\"extras\": [],\n"));
+        assert!(json.contains("\"max\": 64,\n"));
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+        assert_eq!(json.matches('[').count(), json.matches(']').count());
+    }
+
+    #[test]
+    fn v6_escape() {
+        // V6 : [schema_json] escapes strings.
+        let mut json = JsonWriter::default();
+        json.open('[');
+        json.string("quote \" backslash \\ new line \n tab \t bell \u{7} é");
+        json.open('{');
+        json.close('}');
+        json.close(']');
+        assert_eq!(json.finish(), "[\n  \"quote \\\" backslash \\\\ new line \\n tab \\t bell \\u0007 é\",\n  {}\n]\n");
+    }
+
+    /// Object of json indented by given spaces after its first line, as nested in another object.
+    fn nested(json : &str, spaces : usize) -> String {
+        json.trim_end().lines().collect::<Vec<_>>().join(&format!("\n{}", " ".repeat(spaces)))
+    }
+
+    #[test]
+    fn v7_message_and_payload_json() {
+        // V7 : [message_json] and [payload_json] give the same objects as [schema_json].
+        let json = schema_json();
+
+        let client = message_json(&ClientMessage::LAYOUT, &ClientPayload::LAYOUT, ClientPayload::SCHEMA_HASH);
+        assert!(client.starts_with("{\n  \"name\": \"ClientMessage\",") && client.ends_with("}\n"));
+        assert!(json.contains(&format!("    {},\n", nested(&client, 4))));
+
+        let server = message_json(&ServerMessage::LAYOUT, &ServerPayload::LAYOUT, ServerPayload::SCHEMA_HASH);
+        assert!(json.contains(&format!("    {}\n", nested(&server, 4))));
+
+        let payload = payload_json(&ServerPayload::LAYOUT, ServerPayload::SCHEMA_HASH);
+        assert!(payload.starts_with("{\n  \"name\": \"ServerPayload\","));
+        assert!(json.contains(&format!("\"payload\": {}\n", nested(&payload, 6))));
+        assert!(server.contains(&format!("\"payload\": {}\n", nested(&payload, 2))));
+    }
+}
@@ -0,0 +1,618 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::{fmt::Debug, io::{Read, Write}, marker::PhantomData};
+
+use chacha20poly1305::{aead::AeadInPlace, ChaCha20Poly1305, KeyInit, Nonce, Tag};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::net::{handshake::{read_message, write_message}, ClientMessage, ClientPayload, Error, ErrorKind, Message, ServerMessage, ServerPayload, MESSAGE_SIZE_TYPE_SIZE};
+
+/// Size in bytes of exchanged public keys, secrets and session keys.
+pub const KEY_SIZE : usize = 32;
+
+/// Size in bytes of the authentication tag appended to sealed messages.
+pub const TAG_SIZE : usize = 16;
+
+/// Count of counters below the highest received still accepted once, for messages received out of order.
+pub const REPLAY_WINDOW : u64 = u64::BITS as u64;
+
+/// Key derivation label of the key sealing client messages.
+const CLIENT_LABEL : &[u8] = b"ethos client";
+
+/// Key derivation label of the key sealing server messages.
+const SERVER_LABEL : &[u8] = b"ethos server";
+
+/// Secure session used by client to seal [`ClientMessage`] and open [`ServerMessage`].
+pub type ClientSession = SecureSession<ClientMessage, ServerMessage>;
+
+/// Secure session used by server to seal [`ServerMessage`] and open [`ClientMessage`].
+pub type ServerSession = SecureSession<ServerMessage, ClientMessage>;
+
+/// Message able to carry another sealed message of the same type.
+/// 
+/// Implemented by [`ClientMessage`] with [`ClientPayload::Sealed`] and [`ServerMessage`] with [`ServerPayload::Sealed`].
+pub trait Sealable : Message {
+    /// Message carrying the sealed bytes of self.
+    fn seal(&self, counter : u64, data : Box<[u8]>) -> Self;
+
+    /// Counter and sealed bytes carried, [`None`] if message isn't sealed.
+    fn sealed(&self) -> Option<(u64, &[u8])>;
+}
+
+impl Sealable for ClientMessage {
+    fn seal(&self, counter : u64, data : Box<[u8]>) -> Self {
+        ClientMessage::new(ClientPayload::Sealed { counter, data })
+    }
+
+    fn sealed(&self) -> Option<(u64, &[u8])> {
+        match &self.payload {
+            ClientPayload::Sealed { counter, data } => Some((*counter, data)),
+            _ => None,
+        }
+    }
+}
+
+impl Sealable for ServerMessage {
+    fn seal(&self, counter : u64, data : Box<[u8]>) -> Self {
+        ServerMessage::new(self.timestamp, ServerPayload::Sealed { counter, data })
+    }
+
+    fn sealed(&self) -> Option<(u64, &[u8])> {
+        match &self.payload {
+            ServerPayload::Sealed { counter, data } => Some((*counter, data)),
+            _ => None,
+        }
+    }
+}
+
+/// X25519 key exchange establishing a [`SecureSession`] between client and server.
+/// 
+/// Client sends [`ClientPayload::KeyExchange`] and server answers [`ServerPayload::KeyExchange`]. Each side 
+/// then derives, with HKDF-SHA256, one ChaCha20-Poly1305 key per direction from the shared secret and both public keys.
+/// 
+/// # Note(s)
+/// The exchange is anonymous: it stops eavesdropping and forgery by anyone on the path, but an active 
+/// man-in-the-middle can only be detected if client pins the public key of server with [`KeyExchange::with_server_key`].
+/// 
+/// # Example(s)
+/// ```no_run
+/// use std::net::TcpStream;
+/// use ethos_core::net::{ ClientConnection, ClientMessage, ClientPayload, Handshake, KeyExchange, TCP_PORT };
+/// 
+/// let mut stream = TcpStream::connect(("127.0.0.1", TCP_PORT)).unwrap();
+/// Handshake::default().client(&mut stream).unwrap();
+/// let mut session = KeyExchange::new().client(&mut stream).unwrap();
+/// 
+/// let mut connection = ClientConnection::from_stream(stream);
+/// connection.send(&session.seal(&ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 })).unwrap()).unwrap();
+/// let _message = session.open(&connection.receive().unwrap()).unwrap();
+/// ```
+pub struct KeyExchange {
+    /// Secret of this side.
+    secret : StaticSecret,
+
+    /// Public key of this side.
+    public : PublicKey,
+
+    /// Public key server must present, if pinned by client.
+    server_key : Option<[u8; KEY_SIZE]>,
+}
+
+impl KeyExchange {
+    /// Create a new [`KeyExchange`] with a random secret from the operating system.
+    pub fn new() -> KeyExchange {
+        Self::from_secret(StaticSecret::random().to_bytes())
+    }
+
+    /// Create a new [`KeyExchange`] from a given secret.
+    /// 
+    /// Used by server with a persisted secret so its public key can be pinned, or by tests with fixed keys.
+    pub fn from_secret(secret : [u8; KEY_SIZE]) -> KeyExchange {
+        let secret = StaticSecret::from(secret);
+        let public = PublicKey::from(&secret);
+        KeyExchange { secret, public, server_key: None }
+    }
+
+    /// Require server to present the given public key, refusing any other.
+    pub fn with_server_key(mut self, key : [u8; KEY_SIZE]) -> KeyExchange {
+        self.server_key = Some(key);
+        self
+    }
+
+    /// Public key of this side.
+    pub fn public_key(&self) -> [u8; KEY_SIZE] {
+        self.public.to_bytes()
+    }
+
+    /// Message sent by client to start the key exchange.
+    pub fn offer(&self) -> ClientMessage {
+        ClientMessage::new(ClientPayload::KeyExchange { key: self.public_key() })
+    }
+
+    /// Server answer to the key of client.
+    /// 
+    /// # Returns
+    /// Answer to send to client, with the public key of server, and [`Result`] which is:
+    /// - [`Ok`]: [`ServerSession`] established.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ClientPayload::KeyExchange`].
+    ///     2. [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) if key of client is a low order point.
+    pub fn respond(&self, message : &ClientMessage, timestamp : u64) -> (ServerMessage, Result<ServerSession, Error>) {
+        let answer = ServerMessage::new(timestamp, ServerPayload::KeyExchange { key: self.public_key() });
+
+        match message.payload {
+            ClientPayload::KeyExchange { key } => {
+                let keys = self.derive(key, key, self.public_key());
+                (answer, keys.map(|(client, server)| SecureSession::new(server, client)))
+            },
+            _ => (answer, Err(ErrorKind::InvalidMessage.into())),
+        }
+    }
+
+    /// Client check of the key of server.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`ClientSession`] established.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't [`ServerPayload::KeyExchange`].
+    ///     2. [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) if key of server isn't the pinned one or is a low order point.
+    pub fn conclude(&self, message : &ServerMessage) -> Result<ClientSession, Error> {
+        match message.payload {
+            ServerPayload::KeyExchange { key } if self.server_key.is_some_and(|pinned| pinned != key) => Err(ErrorKind::AuthenticationFailed.into()),
+            ServerPayload::KeyExchange { key } => {
+                let (client, server) = self.derive(key, self.public_key(), key)?;
+                Ok(SecureSession::new(client, server))
+            },
+            _ => Err(ErrorKind::InvalidMessage.into()),
+        }
+    }
+
+    /// Perform the client side of the key exchange on a blocking stream.
+    /// 
+    /// Only the answer of server is read from stream, so it can be wrapped in a 
+    /// [`ClientConnection`](crate::net::ClientConnection) afterward.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`ClientSession`] established.
+    /// - [`Err`]: Any error of [`KeyExchange::conclude`], or reading and writing errors.
+    pub fn client<T : Read + Write>(&self, stream : &mut T) -> Result<ClientSession, Error> {
+        write_message(stream, &self.offer())?;
+        self.conclude(&read_message(stream)?)
+    }
+
+    /// Perform the server side of the key exchange on a blocking stream.
+    /// 
+    /// Only the key of client is read from stream, so it can be wrapped in a 
+    /// [`ServerConnection`](crate::net::ServerConnection) afterward. The answer is sent even if the exchange failed.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`ServerSession`] established.
+    /// - [`Err`]: Any error of [`KeyExchange::respond`], or reading and writing errors.
+    pub fn server<T : Read + Write>(&self, stream : &mut T, timestamp : u64) -> Result<ServerSession, Error> {
+        let (answer, result) = self.respond(&read_message(stream)?, timestamp);
+        write_message(stream, &answer)?;
+        result
+    }
+
+    /// Derive the keys sealing client and server messages from the key of the remote peer.
+    fn derive(&self, remote : [u8; KEY_SIZE], client : [u8; KEY_SIZE], server : [u8; KEY_SIZE]) -> Result<([u8; KEY_SIZE], [u8; KEY_SIZE]), Error> {
+        let shared = self.secret.diffie_hellman(&PublicKey::from(remote));
+        if !shared.was_contributory() {
+            return Err(ErrorKind::AuthenticationFailed.into());
+        }
+
+        let mut salt = [0u8; KEY_SIZE * 2];
+        salt[..KEY_SIZE].copy_from_slice(&client);
+        salt[KEY_SIZE..].copy_from_slice(&server);
+        let hkdf = Hkdf::<Sha256>::new(Some(&salt), shared.as_bytes());
+
+        let (mut client, mut server) = ([0u8; KEY_SIZE], [0u8; KEY_SIZE]);
+        hkdf.expand(CLIENT_LABEL, &mut client).expect("Key size is a valid HKDF-SHA256 output length");
+        hkdf.expand(SERVER_LABEL, &mut server).expect("Key size is a valid HKDF-SHA256 output length");
+        Ok((client, server))
+    }
+}
+
+impl Default for KeyExchange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for KeyExchange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyExchange").field("public", &self.public_key()).field("server_key", &self.server_key).finish_non_exhaustive()
+    }
+}
+
+/// Seal messages sent and open messages received with ChaCha20-Poly1305, established by a [`KeyExchange`].
+/// 
+/// Use [`ClientSession`] or [`ServerSession`] according to the side of the connection.
+/// 
+/// The whole packed message, size and extra fields included, is encrypted in a sealed payload carrying its counter. 
+/// Each direction has its own key and counter, starting at 1, used as nonce so it is never reused. 
+/// Counters received are remembered within [`REPLAY_WINDOW`] so any replayed message is refused.
+/// 
+/// # Note(s)
+/// Sealing adds a counter, a length and a [`TAG_SIZE`] tag, so messages close to [`Message::MAX_SIZE`] can't be sealed.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::net::{ ClientMessage, ClientPayload, ClientSession, ErrorKind, ServerSession };
+/// 
+/// // Fixed test keys, normally established by a KeyExchange
+/// let mut client = ClientSession::new([1u8; 32], [2u8; 32]);
+/// let mut server = ServerSession::new([2u8; 32], [1u8; 32]);
+/// 
+/// let message = ClientMessage::new(ClientPayload::Test { p16: 1, p32: 2 });
+/// let sealed = client.seal(&message).unwrap();
+/// 
+/// assert_eq!(server.open(&sealed), Ok(message));
+/// assert_eq!(server.open(&sealed), Err(ErrorKind::AuthenticationFailed.into()));
+/// ```
+pub struct SecureSession<S : Sealable, R : Sealable> {
+    /// Cipher sealing messages sent.
+    sealer : ChaCha20Poly1305,
+
+    /// Cipher opening messages received.
+    opener : ChaCha20Poly1305,
+
+    /// Counter of the last message sealed.
+    sent : u64,
+
+    /// Highest counter of messages opened, 0 if none.
+    highest : u64,
+
+    /// Bit n is set if counter `highest - n - 1` was opened.
+    window : u64,
+
+    messages : PhantomData<(S, R)>,
+}
+
+impl<S : Sealable, R : Sealable> SecureSession<S, R> {
+    /// Create a new [`SecureSession`] from keys sealing messages sent and opening messages received.
+    /// 
+    /// Keys are usually derived by a [`KeyExchange`]. Remote peer uses the same keys swapped.
+    pub fn new(send_key : [u8; KEY_SIZE], receive_key : [u8; KEY_SIZE]) -> SecureSession<S, R> {
+        SecureSession { 
+            sealer: ChaCha20Poly1305::new(&send_key.into()), 
+            opener: ChaCha20Poly1305::new(&receive_key.into()), 
+            sent: 0, 
+            highest: 0, 
+            window: 0, 
+            messages: PhantomData 
+        }
+    }
+
+    /// Count of messages sealed.
+    pub fn sent(&self) -> u64 {
+        self.sent
+    }
+
+    /// Seal a message to send.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Sealed message to send instead.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if message, or the sealed message, exceed [`Message::MAX_SIZE`].
+    ///     2. [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) if counters are exhausted.
+    pub fn seal(&mut self, message : &S) -> Result<S, Error> {
+        if message.size() > S::MAX_SIZE {
+            return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, S::MAX_SIZE, message.size()));
+        }
+
+        let counter = self.sent.checked_add(1).ok_or(ErrorKind::AuthenticationFailed)?;
+
+        let mut data = vec![0u8; MESSAGE_SIZE_TYPE_SIZE + message.size() + TAG_SIZE];
+        let size = MESSAGE_SIZE_TYPE_SIZE + message.pack_bytes(&mut data)?;
+        let tag = self.sealer.encrypt_in_place_detached(&nonce(counter), &[], &mut data[..size])
+            .map_err(|_| ErrorKind::MessageSizeGreaterThanLimit)?;
+        data[size..].copy_from_slice(&tag);
+
+        let sealed = message.seal(counter, data.into());
+        if sealed.size() > S::MAX_SIZE {
+            return Err(Error::size(ErrorKind::MessageSizeGreaterThanLimit, S::MAX_SIZE, sealed.size()));
+        }
+
+        self.sent = counter;
+        Ok(sealed)
+    }
+
+    /// Open a sealed message received.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Message opened.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if message isn't sealed, or opened message is malformed or sealed again.
+    ///     2. [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) if message was forged, altered, sealed with another key or already opened.
+    pub fn open(&mut self, message : &R) -> Result<R, Error> {
+        let (counter, data) = message.sealed().ok_or(ErrorKind::InvalidMessage)?;
+        if data.len() < MESSAGE_SIZE_TYPE_SIZE + TAG_SIZE || !self.is_fresh(counter) {
+            return Err(ErrorKind::AuthenticationFailed.into());
+        }
+
+        let (content, tag) = data.split_at(data.len() - TAG_SIZE);
+        let mut bytes = content.to_vec();
+        self.opener.decrypt_in_place_detached(&nonce(counter), &[], &mut bytes, Tag::from_slice(tag))
+            .map_err(|_| ErrorKind::AuthenticationFailed)?;
+
+        // Authentic from now, so counter can't be opened again
+        self.accept(counter);
+
+        let size = R::size_from_bytes(&[bytes[0], bytes[1]]) as usize;
+        if size != bytes.len() - MESSAGE_SIZE_TYPE_SIZE {
+            return Err(ErrorKind::InvalidMessage.into());
+        }
+
+        match R::from_bytes(&bytes[MESSAGE_SIZE_TYPE_SIZE..])? {
+            opened if opened.sealed().is_some() => Err(ErrorKind::InvalidMessage.into()),
+            opened => Ok(opened),
+        }
+    }
+
+    /// Returns true if counter was never opened and is within [`REPLAY_WINDOW`].
+    fn is_fresh(&self, counter : u64) -> bool {
+        if counter > self.highest {
+            return true;
+        }
+
+        match self.highest - counter {
+            age @ 1..=REPLAY_WINDOW => self.window & 1 << (age - 1) == 0,
+            _ => false,
+        }
+    }
+
+    /// Remember an opened counter.
+    fn accept(&mut self, counter : u64) {
+        if counter > self.highest {
+            let shift = counter - self.highest;
+            self.window = if shift < REPLAY_WINDOW { self.window << shift } else { 0 };
+            if shift <= REPLAY_WINDOW {
+                self.window |= 1 << (shift - 1);
+            }
+            self.highest = counter;
+        } else {
+            self.window |= 1 << (self.highest - counter - 1);
+        }
+    }
+}
+
+impl<S : Sealable, R : Sealable> Debug for SecureSession<S, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecureSession").field("sent", &self.sent).field("highest", &self.highest).finish_non_exhaustive()
+    }
+}
+
+/// Nonce of a counter.
+fn nonce(counter : u64) -> Nonce {
+    let mut nonce = Nonce::default();
+    nonce[..size_of::<u64>()].copy_from_slice(&counter.to_le_bytes());
+    nonce
+}
+
+
+/// This module test [KeyExchange] and [SecureSession] in-process with fixed test keys.
+/// 
+/// # Verification(s)
+/// V1 : [KeyExchange::respond] and [KeyExchange::conclude] establish sessions opening each other sealed messages.
+/// V2 : [KeyExchange::client] and [KeyExchange::server] establish sessions over a loopback connection.
+/// V3 : [SecureSession::open] returns [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) for altered, forged or wrongly keyed messages.
+/// V4 : [SecureSession::open] refuses replayed messages and those older than [REPLAY_WINDOW], accepting reordered ones once.
+/// V5 : [KeyExchange] returns [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) for unexpected messages and [`ErrorKind::AuthenticationFailed`](crate::net::ErrorKind::AuthenticationFailed) for refused keys.
+/// V6 : [SecureSession::seal] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) when sealed message exceed limit.
+/// V7 : [SecureSession::open] returns [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) for unsealed messages and messages sealed twice.
+#[cfg(test)]
+mod tests {
+    use std::{net::{TcpListener, TcpStream}, thread};
+
+    use crate::net::{ClientConnection, ClientMessage, ClientPayload, ClientSession, ErrorKind, KeyExchange, Sealable, ServerConnection, ServerMessage, ServerPayload, 
+        ServerSession, CLIENT_MSG_MAX_SIZE, REPLAY_WINDOW};
+
+    /// Fixed secret of client.
+    const CLIENT_SECRET : [u8; 32] = [0x11; 32];
+
+    /// Fixed secret of server.
+    const SERVER_SECRET : [u8; 32] = [0x22; 32];
+
+    /// Sessions established with fixed test keys.
+    fn sessions() -> (ClientSession, ServerSession) {
+        let (client, server) = (KeyExchange::from_secret(CLIENT_SECRET), KeyExchange::from_secret(SERVER_SECRET));
+        let (answer, server) = server.respond(&client.offer(), 0);
+        (client.conclude(&answer).unwrap(), server.unwrap())
+    }
+
+    fn test_message(i : u16) -> ClientMessage {
+        ClientMessage::new(ClientPayload::Test { p16: i, p32: i as u32 * 2 })
+    }
+
+    #[test]
+    fn v1_exchange() {
+        // V1 : [KeyExchange::respond] and [KeyExchange::conclude] establish sessions opening each other sealed messages.
+        let (mut client, mut server) = sessions();
+
+        for i in 0..10u16 {
+            let sealed = client.seal(&test_message(i)).unwrap();
+            assert!(matches!(sealed.payload, ClientPayload::Sealed { counter, .. } if counter == i as u64 + 1));
+            assert_eq!(server.open(&sealed), Ok(test_message(i)));
+
+            let message = ServerMessage::new(i as u64, ServerPayload::Error { err: i as u32 });
+            let sealed = server.seal(&message).unwrap();
+            assert_eq!(sealed.timestamp, i as u64);
+            assert_eq!(client.open(&sealed), Ok(message));
+        }
+        assert_eq!(client.sent(), 10);
+
+        // Same keys seal the same bytes and plaintext doesn't leak
+        let (mut other, _) = sessions();
+        let sealed = other.seal(&test_message(0)).unwrap();
+        assert_eq!(sealed, sessions().0.seal(&test_message(0)).unwrap());
+        let (_, data) = sealed.sealed().unwrap();
+        let mut plain = vec![0u8; 2 + test_message(0).size as usize];
+        crate::net::Message::pack_bytes(&test_message(0), &mut plain).unwrap();
+        assert!(!data.windows(plain.len()).any(|window| window == plain));
+    }
+
+    #[test]
+    fn v2_loopback() {
+        // V2 : [KeyExchange::client] and [KeyExchange::server] establish sessions over a loopback connection.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let pinned = KeyExchange::from_secret(SERVER_SECRET).public_key();
+
+        let handle = thread::spawn(move || {
+            let mut stream = listener.accept().unwrap().0;
+            let mut session = KeyExchange::from_secret(SERVER_SECRET).server(&mut stream, 0).unwrap();
+            let mut connection = ServerConnection::from_stream(stream);
+            for i in 0..100u16 {
+                let message = session.open(&connection.receive().unwrap()).unwrap();
+                connection.send(&session.seal(&ServerMessage::new(i as u64, ServerPayload::Test { p16: i, p32: 0 })).unwrap()).unwrap();
+                assert_eq!(message, test_message(i));
+            }
+        });
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let mut session = KeyExchange::new().with_server_key(pinned).client(&mut stream).unwrap();
+        let mut connection = ClientConnection::from_stream(stream);
+        for i in 0..100u16 {
+            connection.send(&session.seal(&test_message(i)).unwrap()).unwrap();
+            assert_eq!(session.open(&connection.receive().unwrap()), Ok(ServerMessage::new(i as u64, ServerPayload::Test { p16: i, p32: 0 })));
+        }
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn v3_authentication() {
+        // V3 : [SecureSession::open] returns [`ErrorKind::AuthenticationFailed`] for altered, forged or wrongly keyed messages.
+        let (mut client, mut server) = sessions();
+        let sealed = client.seal(&test_message(1)).unwrap();
+        let (counter, data) = sealed.sealed().unwrap();
+
+        for i in 0..data.len() {
+            let mut altered = data.to_vec();
+            altered[i] ^= 0x01;
+            assert_eq!(server.open(&sealed.seal(counter, altered.into())), Err(ErrorKind::AuthenticationFailed.into()), "Byte {} altered", i);
+        }
+
+        // Other counter, truncated and forged data
+        assert_eq!(server.open(&sealed.seal(counter + 1, data.into())), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(server.open(&sealed.seal(counter, data[..data.len() - 1].into())), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(server.open(&sealed.seal(counter, Box::new([0u8; 17]))), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(server.open(&sealed.seal(counter, Box::new([]))), Err(ErrorKind::AuthenticationFailed.into()));
+
+        // Server key can't open client messages, nor other sessions
+        let mut wrong = ClientSession::new([1u8; 32], [2u8; 32]);
+        assert_eq!(wrong.open(&server.seal(&ServerMessage::new(0, ServerPayload::Accept)).unwrap()), Err(ErrorKind::AuthenticationFailed.into()));
+        let mut looped = ServerSession::new([3u8; 32], [3u8; 32]);
+        assert_eq!(looped.open(&sealed), Err(ErrorKind::AuthenticationFailed.into()));
+
+        // Failures don't consume the counter
+        assert_eq!(server.open(&sealed), Ok(test_message(1)));
+    }
+
+    #[test]
+    fn v4_replay() {
+        // V4 : [SecureSession::open] refuses replayed messages and those older than [REPLAY_WINDOW], accepting reordered ones once.
+        let (mut client, mut server) = sessions();
+        let sealed : Vec<ClientMessage> = (0..REPLAY_WINDOW as u16 + 4).map(|i| client.seal(&test_message(i)).unwrap()).collect();
+
+        assert_eq!(server.open(&sealed[1]), Ok(test_message(1)));
+        assert_eq!(server.open(&sealed[1]), Err(ErrorKind::AuthenticationFailed.into()));
+
+        // Reordered
+        assert_eq!(server.open(&sealed[0]), Ok(test_message(0)));
+        assert_eq!(server.open(&sealed[0]), Err(ErrorKind::AuthenticationFailed.into()));
+
+        // Jump ahead, keeping the window
+        let last = sealed.len() - 1;
+        assert_eq!(server.open(&sealed[last - 1]), Ok(test_message(last as u16 - 1)));
+        assert_eq!(server.open(&sealed[last - 1]), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(server.open(&sealed[3]), Ok(test_message(3)));
+        assert_eq!(server.open(&sealed[3]), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(server.open(&sealed[last]), Ok(test_message(last as u16)));
+
+        // Out of window
+        assert_eq!(server.open(&sealed[2]), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(server.open(&sealed[4]), Ok(test_message(4)));
+        assert_eq!(server.open(&sealed[3]), Err(ErrorKind::AuthenticationFailed.into()));
+    }
+
+    #[test]
+    fn v5_exchange_refused() {
+        // V5 : [KeyExchange] returns [`ErrorKind::InvalidMessage`] for unexpected messages and [`ErrorKind::AuthenticationFailed`] for refused keys.
+        let (client, server) = (KeyExchange::from_secret(CLIENT_SECRET), KeyExchange::from_secret(SERVER_SECRET));
+
+        let (answer, result) = server.respond(&test_message(0), 0);
+        assert_eq!(answer.payload, ServerPayload::KeyExchange { key: server.public_key() });
+        assert_eq!(result.map(|_| ()), Err(ErrorKind::InvalidMessage.into()));
+        assert_eq!(client.conclude(&ServerMessage::new(0, ServerPayload::Accept)).map(|_| ()), Err(ErrorKind::InvalidMessage.into()));
+
+        // Low order points
+        let (_, result) = server.respond(&ClientMessage::new(ClientPayload::KeyExchange { key: [0u8; 32] }), 0);
+        assert_eq!(result.map(|_| ()), Err(ErrorKind::AuthenticationFailed.into()));
+        assert_eq!(client.conclude(&ServerMessage::new(0, ServerPayload::KeyExchange { key: [0u8; 32] })).map(|_| ()), Err(ErrorKind::AuthenticationFailed.into()));
+
+        // Pinned key of server
+        let (answer, _) = server.respond(&client.offer(), 0);
+        let pinned = KeyExchange::from_secret(CLIENT_SECRET).with_server_key(server.public_key());
+        assert!(pinned.conclude(&answer).is_ok());
+        let pinned = KeyExchange::from_secret(CLIENT_SECRET).with_server_key(client.public_key());
+        assert_eq!(pinned.conclude(&answer).map(|_| ()), Err(ErrorKind::AuthenticationFailed.into()));
+    }
+
+    #[test]
+    fn v6_seal_greater_than_limit() {
+        // V6 : [SecureSession::seal] returns [`ErrorKind::MessageSizeGreaterThanLimit`] when sealed message exceed limit.
+        let (mut client, _) = sessions();
+        let sized = |len : usize| ClientMessage::new(ClientPayload::Sealed { counter: 0, data: vec![0u8; len].into_boxed_slice() });
+
+        let fits = (0..CLIENT_MSG_MAX_SIZE).rev().find(|len| client.seal(&sized(*len)).is_ok()).unwrap();
+        assert!(fits > CLIENT_MSG_MAX_SIZE - 64);
+        assert_eq!(client.seal(&sized(fits + 1)), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+
+        let mut message = test_message(0);
+        message.size = CLIENT_MSG_MAX_SIZE as u16 + 1;
+        assert_eq!(client.seal(&message), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+    }
+
+    #[test]
+    fn v7_unsealed() {
+        // V7 : [SecureSession::open] returns [`ErrorKind::InvalidMessage`] for unsealed messages and messages sealed twice.
+        let (mut client, mut server) = sessions();
+        assert_eq!(server.open(&test_message(0)), Err(ErrorKind::InvalidMessage.into()));
+
+        let once = client.seal(&test_message(0)).unwrap();
+        let twice = client.seal(&once).unwrap();
+        assert_eq!(server.open(&twice), Err(ErrorKind::InvalidMessage.into()));
+    }
+}
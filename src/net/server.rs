@@ -48,9 +48,121 @@ crate::write_messages_payloads!{
     /// Payload are packed for smaller transfer size.
     ServerPayload,
 
+    /// Part of a server message too large to be sent at once, see [`Fragmenter`](crate::net::Fragmenter).
+    Fragment {
+        /// Id of the fragmented message.
+        id : u32,
+
+        /// Index of this fragment.
+        index : u16,
+
+        /// Count of fragments of the message.
+        count : u16,
+
+        /// Bytes of the packed payload carried by this fragment.
+        data : Box<[u8]> [max crate::net::FRAGMENT_MAX_DATA_SIZE]
+    } = 65530,
+
+    /// Packed payload compressed by a [`Compressor`](crate::net::Compressor).
+    Compressed {
+        /// Id of the [`Codec`](crate::net::Codec) used.
+        codec : u8,
+
+        /// Size of the packed payload once decompressed.
+        size : u32,
+
+        /// Compressed bytes of the packed payload.
+        data : Box<[u8]>
+    } = 65528,
+
+    /// Codec selected by server for [`ServerPayload::Compressed`], answering [`ClientPayload::Compression`](crate::net::ClientPayload::Compression).
+    Compression { 
+        /// Id of the [`Codec`](crate::net::Codec) selected, 0 if none.
+        codec : u8 
+    } = 65527,
+
+    /// Public key of server answering [`ClientPayload::KeyExchange`](crate::net::ClientPayload::KeyExchange).
+    KeyExchange { 
+        /// X25519 public key of server.
+        key : [u8; 32] 
+    } = 65526,
+
+    /// Server message sealed by a `SecureSession` (feature `secure`).
+    Sealed {
+        /// Counter of the message, used as nonce and to reject replays.
+        counter : u64,
+
+        /// Encrypted packed message followed by its authentication tag.
+        data : Box<[u8]>
+    } = 65525,
+
+    /// Challenge answering [`ClientPayload::Login`](crate::net::ClientPayload::Login).
+    Challenge {
+        /// Random bytes client must prove knowledge of its secret with.
+        nonce : [u8; 32]
+    } = 65524,
+
+    /// Session issued after a successful login or resume.
+    Session {
+        /// Opaque token to send with [`ClientPayload::Resume`](crate::net::ClientPayload::Resume) on reconnect.
+        token : [u8; 32],
+
+        /// Server time in milliseconds when the token expires.
+        expires : u64
+    } = 65523,
+
+    /// Login or resume refused.
+    LoginDenied = 65522,
+
+    /// Keep-alive sent by a [`Heartbeat`](crate::net::Heartbeat), answered by [`ClientPayload::Pong`](crate::net::ClientPayload::Pong).
+    Ping {
+        /// Id of the ping, echoed by the pong.
+        id : u32
+    } = 65521,
+
+    /// Answer to [`ClientPayload::Ping`](crate::net::ClientPayload::Ping).
+    Pong {
+        /// Id of the ping answered.
+        id : u32
+    } = 65520,
+
+    /// Answer to [`ClientPayload::Time`](crate::net::ClientPayload::Time), sent with the server time in the message timestamp.
+    Time {
+        /// Client time in milliseconds when the request was sent, echoed.
+        sent : u64,
+
+        /// Server time in milliseconds when the request was received.
+        received : u64
+    } = 65519,
+
+    /// Server message answering a [`ClientPayload::Request`](crate::net::ClientPayload::Request), see [`reply`](crate::net::reply).
+    Reply {
+        /// Id of the request answered.
+        id : u32,
+
+        /// Packed server message of the reply.
+        data : Box<[u8]>
+    } = 65518,
+
+    /// Server accepted the [`ClientPayload::Hello`](crate::net::ClientPayload::Hello) of client.
+    /// 
+    /// Discriminant MUST never change so that any version can read it.
+    Accept = 65532,
+
+    /// Server rejected the [`ClientPayload::Hello`](crate::net::ClientPayload::Hello) of client.
+    /// 
+    /// Discriminant MUST never change so that any version can read it.
+    Reject {
+        /// Protocol version of server.
+        version : u16,
+
+        /// Schema hash of server payloads.
+        schema : u64
+    } = 65531,
+
      /// An error message sent by the server to the client.
     Error {
-        /// Possible error index according to the server error chart. 
+        /// Code of the error, see [`ServerErrorCode`](crate::net::ServerErrorCode).
         err : u32 
     } = 65533,
 
@@ -59,7 +171,7 @@ crate::write_messages_payloads!{
 
     /// Invalid or malformed payload that are suspicious.
     /// 
-    /// Since client to server communications are always handled by
-    /// TCP, no loss or modification of data should have arised.
+    /// Server messages are sent by TCP or by UDP datagrams. Datagrams may be lost, 
+    /// duplicated or reordered, and may also be corrupted since the UDP checksum is optional on IPv4 and weak.
     Invalid = 65535
 }
\ No newline at end of file
@@ -0,0 +1,532 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::{collections::HashMap, io, marker::PhantomData, net::{SocketAddr, ToSocketAddrs, UdpSocket}};
+
+use crate::net::{decoder::{decode_frame, Frame}, ClientMessage, Error, ErrorKind, Message, ServerMessage, MESSAGE_SIZE_TYPE_SIZE, UDP_MTU};
+
+/// Size of the sequence number written at the beginning of each datagram.
+pub(crate) const SEQUENCE_TYPE_SIZE : usize = size_of::<u32>();
+
+/// Count of previous sequence numbers remembered to detect duplicated datagrams.
+const SEQUENCE_WINDOW : u32 = u64::BITS;
+
+/// Default maximum count of peers a [`UdpChannel`] remembers sequences of.
+pub const UDP_MAX_PEERS : usize = 1024;
+
+/// Channel used by server to send [`ServerMessage`] and receive [`ClientMessage`] datagrams.
+pub type ServerUdpChannel<T = UdpSocket> = UdpChannel<ServerMessage, ClientMessage, T>;
+
+/// Channel used by client to send [`ClientMessage`] and receive [`ServerMessage`] datagrams.
+pub type ClientUdpChannel<T = UdpSocket> = UdpChannel<ClientMessage, ServerMessage, T>;
+
+/// Socket able to send and receive datagrams.
+/// 
+/// Implemented for [`UdpSocket`]. Can be implemented to simulate network conditions.
+pub trait DatagramSocket {
+    /// Send a datagram to the given address. See [`UdpSocket::send_to`].
+    fn send_to(&self, buf : &[u8], addr : SocketAddr) -> io::Result<usize>;
+
+    /// Receive a single datagram. See [`UdpSocket::recv_from`].
+    fn recv_from(&self, buf : &mut [u8]) -> io::Result<(usize, SocketAddr)>;
+}
+
+impl DatagramSocket for UdpSocket {
+    fn send_to(&self, buf : &[u8], addr : SocketAddr) -> io::Result<usize> {
+        UdpSocket::send_to(self, buf, addr)
+    }
+
+    fn recv_from(&self, buf : &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        UdpSocket::recv_from(self, buf)
+    }
+}
+
+/// Datagram received by [`UdpChannel::receive`].
+#[derive(Debug, PartialEq)]
+pub struct Datagram<R : Message> {
+    /// Address of the sender.
+    pub from : SocketAddr,
+
+    /// Sequence number of the datagram given by the sender.
+    pub sequence : u32,
+
+    /// True if a datagram with a greater sequence was already received from sender.
+    /// 
+    /// Messages that describe a state should usually be ignored since they are outdated.
+    pub out_of_order : bool,
+
+    /// Messages of the datagram in the order they were packed.
+    /// 
+    /// A malformed message ends the datagram with its error.
+    pub messages : Vec<Result<R, Error>>,
+}
+
+/// Sequences received from a peer.
+#[derive(Default)]
+pub(crate) struct ReceiveWindow {
+    /// Greatest sequence received.
+    latest : u32,
+
+    /// Bit n is set if sequence `latest - n` was received.
+    received : u64,
+}
+
+impl ReceiveWindow {
+    /// Register a sequence.
+    /// 
+    /// # Returns
+    /// - [`None`] if sequence is a duplicate or too old to tell.
+    /// - [`Some`] with true if sequence is older than latest.
+    pub(crate) fn register(&mut self, sequence : u32) -> Option<bool> {
+        if self.received == 0 {
+            self.latest = sequence;
+            self.received = 1;
+            return Some(false);
+        }
+
+        let distance = sequence.wrapping_sub(self.latest);
+
+        if distance != 0 && distance < u32::MAX / 2 {
+            // Newer sequence
+            self.received = if distance < SEQUENCE_WINDOW { (self.received << distance) | 1 } else { 1 };
+            self.latest = sequence;
+            Some(false)
+        } else {
+            // Same or older sequence
+            let age = self.latest.wrapping_sub(sequence);
+            if age >= SEQUENCE_WINDOW || self.received & (1 << age) != 0 {
+                None
+            } else {
+                self.received |= 1 << age;
+                Some(true)
+            }
+        }
+    }
+
+    /// Greatest sequence received.
+    pub(crate) fn latest(&self) -> u32 {
+        self.latest
+    }
+
+    /// Bit n is set if sequence `latest - n - 1` was received.
+    pub(crate) fn previous(&self) -> u32 {
+        (self.received >> 1) as u32
+    }
+}
+
+/// Unreliable datagram channel on [`UDP_PORT`](crate::net::UDP_PORT).
+/// 
+/// Messages `S` are packed one after the other in datagrams bounded by a MTU, each datagram starting with 
+/// a sequence number. Received datagrams are decoded into messages `R`, tolerating loss, duplication and reordering.
+/// 
+/// Use [`ServerUdpChannel`] or [`ClientUdpChannel`] according to the side of the channel.
+/// 
+/// # Example(s)
+/// ```no_run
+/// use ethos_core::net::{ ServerUdpChannel, ServerMessage, ServerPayload, UDP_PORT };
+/// 
+/// let mut channel = ServerUdpChannel::bind(("0.0.0.0", UDP_PORT)).unwrap();
+/// let client = "127.0.0.1:5000".parse().unwrap();
+/// channel.send(client, &[ServerMessage::new(0, ServerPayload::Test { p16: 1, p32: 2 })]).unwrap();
+/// ```
+pub struct UdpChannel<S : Message, R : Message, T : DatagramSocket = UdpSocket> {
+    /// Underlying socket.
+    socket : T,
+
+    /// Maximum size of a datagram.
+    mtu : usize,
+
+    /// Sequence of the next datagram sent.
+    sequence : u32,
+
+    /// Sequences received from each peer, with the count of datagrams received when it was last heard.
+    windows : HashMap<SocketAddr, (ReceiveWindow, u64)>,
+
+    /// Maximum count of peers in windows.
+    max_peers : usize,
+
+    /// Count of datagrams received.
+    received : u64,
+
+    /// Buffer used to pack and receive datagrams.
+    buffer : Vec<u8>,
+
+    message : PhantomData<(S, R)>,
+}
+
+impl<S : Message, R : Message> UdpChannel<S, R, UdpSocket> {
+    /// Bind a new [`UdpChannel`] to the given address with [`UDP_MTU`].
+    pub fn bind<A : ToSocketAddrs>(addr : A) -> Result<UdpChannel<S, R, UdpSocket>, Error> {
+        Ok(Self::new(UdpSocket::bind(addr)?, UDP_MTU))
+    }
+}
+
+impl<S : Message, R : Message, T : DatagramSocket> UdpChannel<S, R, T> {
+    /// Create a new [`UdpChannel`] from a socket and a maximum datagram size, remembering up to [`UDP_MAX_PEERS`] peers.
+    /// 
+    /// # Panic(s)
+    /// Will panic! if mtu can't hold a sequence and a size header.
+    pub fn new(socket : T, mtu : usize) -> UdpChannel<S, R, T> {
+        assert!(mtu > SEQUENCE_TYPE_SIZE + MESSAGE_SIZE_TYPE_SIZE, "mtu must be greater than {}", SEQUENCE_TYPE_SIZE + MESSAGE_SIZE_TYPE_SIZE);
+        UdpChannel { socket, mtu, sequence: 0, windows: HashMap::new(), max_peers: UDP_MAX_PEERS, received: 0, buffer: vec![0u8; mtu], message: PhantomData }
+    }
+
+    /// Set the maximum count of peers sequences are remembered of.
+    /// 
+    /// Once reached, the peer heard from the longest time ago is forgotten, so datagrams from many 
+    /// addresses, spoofed or not, can't grow memory without limit.
+    /// 
+    /// # Panic(s)
+    /// Will panic! if max_peers is 0.
+    pub fn with_max_peers(mut self, max_peers : usize) -> UdpChannel<S, R, T> {
+        assert!(max_peers > 0, "max_peers must be greater than 0");
+        self.max_peers = max_peers;
+        self
+    }
+
+    /// Reference to the underlying socket, to set timeouts or options.
+    pub fn socket(&self) -> &T {
+        &self.socket
+    }
+
+    /// Maximum size of a datagram.
+    pub fn mtu(&self) -> usize {
+        self.mtu
+    }
+
+    /// Count of peers sequences are remembered of.
+    pub fn peers(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Forget sequences received from a peer, usually when it disconnects.
+    pub fn forget(&mut self, addr : &SocketAddr) {
+        self.windows.remove(addr);
+    }
+
+    /// Pack messages in as few datagrams as possible and send them to an address.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: Count of datagrams sent.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) if a message can't fit in a datagram. Nothing is sent.
+    ///     2. [`ErrorKind::Io`](crate::net::ErrorKind::Io) if sending failed. 
+    pub fn send(&mut self, addr : SocketAddr, messages : &[S]) -> Result<usize, Error> {
+        let capacity = self.mtu - SEQUENCE_TYPE_SIZE;
+        if messages.iter().any(|msg| msg.size() > S::MAX_SIZE || MESSAGE_SIZE_TYPE_SIZE + msg.size() > capacity) {
+            return Err(ErrorKind::MessageSizeGreaterThanLimit.into());
+        }
+
+        let mut count = 0;
+        let mut len = SEQUENCE_TYPE_SIZE;
+
+        for msg in messages {
+            if len + MESSAGE_SIZE_TYPE_SIZE + msg.size() > self.mtu {
+                self.send_datagram(addr, len)?;
+                count += 1;
+                len = SEQUENCE_TYPE_SIZE;
+            }
+            len += MESSAGE_SIZE_TYPE_SIZE + msg.pack_bytes(&mut self.buffer[len..])?;
+        }
+
+        if len > SEQUENCE_TYPE_SIZE {
+            self.send_datagram(addr, len)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Write the sequence in buffer and send the first len bytes.
+    fn send_datagram(&mut self, addr : SocketAddr, len : usize) -> Result<(), Error> {
+        self.buffer[..SEQUENCE_TYPE_SIZE].copy_from_slice(&self.sequence.to_le_bytes());
+        self.sequence = self.sequence.wrapping_add(1);
+        self.socket.send_to(&self.buffer[..len], addr)?;
+        Ok(())
+    }
+
+    /// Block until a new datagram is received.
+    /// 
+    /// Duplicated datagrams are silently dropped.
+    /// 
+    /// # Returns
+    /// [`Result`] which is:
+    /// - [`Ok`]: [`Datagram`] received.
+    /// - [`Err`]:
+    ///     1. [`ErrorKind::InvalidMessage`](crate::net::ErrorKind::InvalidMessage) if datagram is too short to contain a sequence.
+    ///     2. [`ErrorKind::Io`](crate::net::ErrorKind::Io) if receiving failed, including read timeouts.
+    pub fn receive(&mut self) -> Result<Datagram<R>, Error> {
+        loop {
+            let (len, from) = self.socket.recv_from(&mut self.buffer)?;
+
+            if len < SEQUENCE_TYPE_SIZE {
+                return Err(Error::size(ErrorKind::InvalidMessage, SEQUENCE_TYPE_SIZE, len));
+            }
+
+            let sequence = u32::from_le_bytes(self.buffer[..SEQUENCE_TYPE_SIZE].try_into().unwrap());
+
+            if !self.windows.contains_key(&from) && self.windows.len() >= self.max_peers {
+                // Forget the peer heard from the longest time ago
+                if let Some(oldest) = self.windows.iter().min_by_key(|(_, (_, heard))| *heard).map(|(addr, _)| *addr) {
+                    self.windows.remove(&oldest);
+                }
+            }
+
+            self.received += 1;
+            let (window, heard) = self.windows.entry(from).or_default();
+            *heard = self.received;
+            
+            if let Some(out_of_order) = window.register(sequence) {
+                let messages = decode_datagram(&self.buffer[SEQUENCE_TYPE_SIZE..len]);
+                return Ok(Datagram { from, sequence, out_of_order, messages });
+            }
+        }
+    }
+}
+
+/// Decode every message of a datagram, without sequence.
+fn decode_datagram<R : Message>(mut bytes : &[u8]) -> Vec<Result<R, Error>> {
+    let mut messages = Vec::new();
+    let mut offset = 0;
+
+    while !bytes.is_empty() {
+        match decode_frame::<R>(bytes) {
+            Some(Frame::Message(result, size)) => {
+                messages.push(result);
+                bytes = &bytes[size..];
+                offset += size;
+            },
+            Some(Frame::Corrupted(err)) => {
+                messages.push(Err(err));
+                break;
+            },
+            None => {
+                messages.push(Err(Error::at(ErrorKind::IncompleteMessage, SEQUENCE_TYPE_SIZE + offset)));
+                break;
+            },
+        }
+    }
+
+    messages
+}
+
+
+/// This module test [UdpChannel] over loopback with a simulated lossy socket.
+/// 
+/// # Verification(s)
+/// V1 : [UdpChannel::send] messages are received by [UdpChannel::receive] in one datagram.
+/// V2 : [UdpChannel::send] splits messages in many datagrams bounded by MTU.
+/// V3 : [UdpChannel::send] returns [`ErrorKind::MessageSizeGreaterThanLimit`](crate::net::ErrorKind::MessageSizeGreaterThanLimit) for message bigger than MTU.
+/// V4 : [UdpChannel::receive] tolerates lost, duplicated and reordered datagrams.
+/// V5 : [UdpChannel::receive] reports malformed datagrams without panicking.
+/// V6 : [UdpChannel::new] refuses a MTU too small for a sequence and a size header.
+/// V7 : [UdpChannel::receive] forgets the peer heard from the longest time ago once [UdpChannel::with_max_peers] is reached.
+#[cfg(test)]
+mod tests {
+    use std::{cell::{Cell, RefCell}, io, net::{SocketAddr, UdpSocket}, time::Duration};
+
+    use crate::net::{udp::SEQUENCE_TYPE_SIZE, ClientUdpChannel, DatagramSocket, ErrorKind, ServerMessage, ServerPayload, ServerUdpChannel, MESSAGE_SIZE_TYPE_SIZE, UDP_MTU};
+
+    /// Action applied by [LossySocket] on a datagram sent.
+    #[derive(Clone, Copy)]
+    enum Fate { Deliver, Drop, Duplicate, Hold }
+
+    /// Socket that applies a [Fate] to each datagram sent. A held datagram is sent after the next one.
+    struct LossySocket {
+        inner : UdpSocket,
+        plan : Vec<Fate>,
+        count : Cell<usize>,
+        held : RefCell<Option<Vec<u8>>>,
+    }
+
+    impl DatagramSocket for LossySocket {
+        fn send_to(&self, buf : &[u8], addr : SocketAddr) -> io::Result<usize> {
+            let fate = self.plan[self.count.get() % self.plan.len()];
+            self.count.set(self.count.get() + 1);
+
+            match fate {
+                Fate::Drop => return Ok(buf.len()),
+                Fate::Hold => {
+                    *self.held.borrow_mut() = Some(buf.to_vec());
+                    return Ok(buf.len());
+                },
+                Fate::Deliver => { self.inner.send_to(buf, addr)?; },
+                Fate::Duplicate => {
+                    self.inner.send_to(buf, addr)?;
+                    self.inner.send_to(buf, addr)?;
+                },
+            }
+
+            if let Some(held) = self.held.borrow_mut().take() {
+                self.inner.send_to(&held, addr)?;
+            }
+            Ok(buf.len())
+        }
+
+        fn recv_from(&self, buf : &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+            self.inner.recv_from(buf)
+        }
+    }
+
+    fn loopback() -> (UdpSocket, UdpSocket) {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        client.set_read_timeout(Some(Duration::from_millis(200))).unwrap();
+        (server, client)
+    }
+
+    fn messages(count : u32) -> Vec<ServerMessage> {
+        (0..count).map(|i| ServerMessage::new(i as u64, ServerPayload::Error { err: i })).collect()
+    }
+
+    #[test]
+    fn v1_send_receive() {
+        // V1 : [UdpChannel::send] messages are received by [UdpChannel::receive] in one datagram.
+        let (server, client) = loopback();
+        let addr = client.local_addr().unwrap();
+        let mut server = ServerUdpChannel::new(server, UDP_MTU);
+        let mut client = ClientUdpChannel::new(client, UDP_MTU);
+
+        assert_eq!(server.send(addr, &messages(10)), Ok(1));
+
+        let datagram = client.receive().unwrap();
+        assert_eq!(datagram.sequence, 0);
+        assert!(!datagram.out_of_order);
+        assert_eq!(datagram.messages, messages(10).into_iter().map(Ok).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn v2_send_many_datagrams() {
+        // V2 : [UdpChannel::send] splits messages in many datagrams bounded by MTU.
+        let (server, client) = loopback();
+        let addr = client.local_addr().unwrap();
+        let msgs = messages(100);
+        let msg_size = MESSAGE_SIZE_TYPE_SIZE + msgs[0].size as usize;
+        
+        // 10 messages per datagram
+        let mut server = ServerUdpChannel::new(server, 4 + msg_size * 10 + msg_size - 1);
+        let mut client = ClientUdpChannel::new(client, UDP_MTU);
+
+        assert_eq!(server.send(addr, &msgs), Ok(10));
+
+        let mut received = Vec::new();
+        for sequence in 0..10 {
+            let datagram = client.receive().unwrap();
+            assert_eq!(datagram.sequence, sequence);
+            assert_eq!(datagram.messages.len(), 10);
+            received.extend(datagram.messages.into_iter().map(Result::unwrap));
+        }
+        assert_eq!(received, msgs);
+    }
+
+    #[test]
+    fn v3_send_greater_than_mtu() {
+        // V3 : [UdpChannel::send] returns [`ErrorKind::MessageSizeGreaterThanLimit`] for message bigger than MTU.
+        let (server, client) = loopback();
+        let addr = client.local_addr().unwrap();
+        let mut server = ServerUdpChannel::new(server, 8);
+
+        assert_eq!(server.send(addr, &messages(1)), Err(ErrorKind::MessageSizeGreaterThanLimit.into()));
+    }
+
+    #[test]
+    fn v4_receive_lossy() {
+        // V4 : [UdpChannel::receive] tolerates lost, duplicated and reordered datagrams.
+        let (server, client) = loopback();
+        let addr = client.local_addr().unwrap();
+        let plan = vec![Fate::Deliver, Fate::Hold, Fate::Deliver, Fate::Drop, Fate::Duplicate, 
+            Fate::Deliver, Fate::Hold, Fate::Duplicate, Fate::Deliver, Fate::Drop];
+        let mut server = ServerUdpChannel::new(LossySocket { inner: server, plan, count: Cell::new(0), held: RefCell::new(None) }, UDP_MTU);
+        let mut client = ClientUdpChannel::new(client, UDP_MTU);
+
+        // One message per datagram
+        for msg in messages(10) {
+            server.send(addr, &[msg]).unwrap();
+        }
+
+        let mut received = Vec::new();
+        while let Ok(datagram) = client.receive() {
+            let expected = ServerMessage::new(datagram.sequence as u64, ServerPayload::Error { err: datagram.sequence });
+            assert_eq!(datagram.messages, vec![Ok(expected)]);
+            received.push((datagram.sequence, datagram.out_of_order));
+        }
+
+        // 3 and 9 are lost, 1 and 6 arrive late, 4 and 7 duplicates are dropped.
+        assert_eq!(received, vec![(0, false), (2, false), (1, true), (4, false), (5, false), (7, false), (6, true), (8, false)]);
+    }
+
+    #[test]
+    fn v5_receive_malformed() {
+        // V5 : [UdpChannel::receive] reports malformed datagrams without panicking.
+        let (server, client) = loopback();
+        let addr = client.local_addr().unwrap();
+        let mut client = ClientUdpChannel::new(client, UDP_MTU);
+
+        // Too short for sequence
+        server.send_to(&[1, 2], addr).unwrap();
+        assert_eq!(client.receive(), Err(ErrorKind::InvalidMessage.into()));
+
+        // Unknown discriminant, then truncated message
+        server.send_to(&[0, 0, 0, 0, 2, 0, 1, 0, 14, 0, 1], addr).unwrap();
+        let datagram = client.receive().unwrap();
+        assert_eq!(datagram.messages, vec![Err(ErrorKind::InvalidMessage.into()), Err(ErrorKind::IncompleteMessage.into())]);
+
+        // Size header greater than datagram
+        server.send_to(&[1, 0, 0, 0, 255, 255], addr).unwrap();
+        let datagram = client.receive().unwrap();
+        assert_eq!(datagram.messages, vec![Err(ErrorKind::IncompleteMessage.into())]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn v6_new_mtu_too_small() {
+        // V6 : [UdpChannel::new] refuses a MTU too small for a sequence and a size header.
+        let (server, _) = loopback();
+        let _ = ServerUdpChannel::new(server, SEQUENCE_TYPE_SIZE + MESSAGE_SIZE_TYPE_SIZE);
+    }
+
+    #[test]
+    fn v7_max_peers() {
+        // V7 : [UdpChannel::receive] forgets the peer heard from the longest time ago once [UdpChannel::with_max_peers] is reached.
+        let (_, client) = loopback();
+        let addr = client.local_addr().unwrap();
+        let mut client = ClientUdpChannel::new(client, UDP_MTU).with_max_peers(2);
+        let peers : Vec<UdpSocket> = (0..3).map(|_| UdpSocket::bind("127.0.0.1:0").unwrap()).collect();
+
+        // First peer heard again before third peer comes, so second one is forgotten
+        for (sequence, peer) in [0, 1, 0, 2].into_iter().enumerate() {
+            peers[peer].send_to(&(sequence as u32).to_le_bytes(), addr).unwrap();
+            assert!(client.receive().is_ok());
+        }
+        assert_eq!(client.peers(), 2);
+
+        // Duplicate of first peer is still dropped, whilst forgotten second peer starts over
+        peers[0].send_to(&2u32.to_le_bytes(), addr).unwrap();
+        peers[1].send_to(&1u32.to_le_bytes(), addr).unwrap();
+        assert_eq!(client.receive().unwrap().from, peers[1].local_addr().unwrap());
+        assert_eq!(client.peers(), 2);
+    }
+}
@@ -0,0 +1,397 @@
+/* 
+Copyright (c) 2026  NickelAnge.Studio 
+Email               mathieu.grenier@nickelange.studio
+Git                 https://github.com/NickelAngeStudio/ethos-core
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+
+
+use std::{borrow::Cow, fmt::Debug, marker::PhantomData};
+
+use crate::net::{field::FIELD_LENGTH_TYPE_SIZE, PayloadField};
+
+/// Borrowed view of a [`PayloadField`], read straight from the packed bytes without allocation.
+/// 
+/// Used by the `<Payload>Ref<'a>` views generated by [`write_messages_payloads!`](crate::write_messages_payloads)
+/// and [`EthosPayload`](crate::net::EthosPayload). Scalars are read by value, [`String`] as a [`Cow<str>`] borrowed
+/// unless it holds invalid UTF-8, [`Vec<T>`], [`Box<[T]>`] and `[T; N]` as a lazy [`ListRef`].
+/// 
+/// Types generated by [`write_payload_struct!`](crate::write_payload_struct) and [`write_payload_enum!`](crate::write_payload_enum)
+/// are read by value.
+pub trait FieldRef<'a> : PayloadField {
+    /// View of the field.
+    type Ref : Clone + Debug + PartialEq;
+
+    /// View of field packed at the beginning of buffer.
+    /// 
+    /// Buffer must have been validated with [`read_field_size`](PayloadField::read_field_size).
+    /// 
+    /// # Returns
+    /// View and count of bytes read.
+    fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize);
+
+    /// View of field whose presence was read from bitmap.
+    fn read_bitmap_ref(buffer : &'a [u8], _present : bool) -> (Self::Ref, usize) {
+        Self::read_ref(buffer)
+    }
+
+    /// Owned field of a view.
+    fn from_ref(view : Self::Ref) -> Self;
+}
+
+/// Payload with a borrowed view, generated by [`write_messages_payloads!`](crate::write_messages_payloads) and 
+/// [`EthosPayload`](crate::net::EthosPayload) as `<Payload>Ref<'a>`.
+pub trait PayloadView<'a> : Sized {
+    /// Borrowed view of payload.
+    type Ref : Clone + Debug + PartialEq;
+
+    /// View of payload packed at the beginning of buffer.
+    /// 
+    /// Buffer must have been validated with [`deserialize_size`](tampon::Tampon::deserialize_size).
+    /// 
+    /// # Returns
+    /// View and count of bytes read, which is 0 for unknown discriminant.
+    fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize);
+
+    /// Owned payload of a view.
+    fn from_ref(view : Self::Ref) -> Self;
+}
+
+/// Implement [`FieldRef`] for types read by value.
+macro_rules! impl_value_field_ref {
+    ($($type : ty),+) => {
+        $(
+            impl<'a> FieldRef<'a> for $type {
+                type Ref = $type;
+
+                fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+                    <$type as PayloadField>::read_field(buffer)
+                }
+
+                fn from_ref(view : Self::Ref) -> Self {
+                    view
+                }
+            }
+        )+
+    };
+}
+
+impl_value_field_ref!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, bool);
+
+/// Implement [`FieldRef`] read by value for a type generated by [`write_payload_struct!`](crate::write_payload_struct)
+/// or [`write_payload_enum!`](crate::write_payload_enum).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_nested_field_ref {
+    ($type : ident) => {
+        impl<'a> $crate::net::FieldRef<'a> for $type {
+            type Ref = $type;
+
+            fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+                <$type as $crate::net::PayloadField>::read_field(buffer)
+            }
+
+            fn from_ref(view : Self::Ref) -> Self {
+                view
+            }
+        }
+    };
+}
+
+impl<'a> FieldRef<'a> for String {
+    type Ref = Cow<'a, str>;
+
+    /// Invalid UTF-8 sequences are replaced by [`char::REPLACEMENT_CHARACTER`], which allocates.
+    fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+        let size = FIELD_LENGTH_TYPE_SIZE + u16::read_field(buffer).0 as usize;
+        (String::from_utf8_lossy(&buffer[FIELD_LENGTH_TYPE_SIZE..size]), size)
+    }
+
+    fn from_ref(view : Self::Ref) -> Self {
+        view.into_owned()
+    }
+}
+
+impl<'a, T : FieldRef<'a>> FieldRef<'a> for Vec<T> {
+    type Ref = ListRef<'a, T>;
+
+    fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+        let len = u16::read_field(buffer).0 as usize;
+        let list = ListRef::new(&buffer[FIELD_LENGTH_TYPE_SIZE..], len);
+        let size = FIELD_LENGTH_TYPE_SIZE + list.as_bytes().len();
+        (list, size)
+    }
+
+    fn from_ref(view : Self::Ref) -> Self {
+        view.iter().map(T::from_ref).collect()
+    }
+}
+
+impl<'a, T : FieldRef<'a>> FieldRef<'a> for Box<[T]> {
+    type Ref = ListRef<'a, T>;
+
+    fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+        Vec::<T>::read_ref(buffer)
+    }
+
+    fn from_ref(view : Self::Ref) -> Self {
+        Vec::from_ref(view).into_boxed_slice()
+    }
+}
+
+impl<'a, T : FieldRef<'a>, const N : usize> FieldRef<'a> for [T; N] {
+    type Ref = ListRef<'a, T>;
+
+    fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+        let list = ListRef::new(buffer, N);
+        let size = list.as_bytes().len();
+        (list, size)
+    }
+
+    fn from_ref(view : Self::Ref) -> Self {
+        let mut elements = view.iter();
+        std::array::from_fn(|_| T::from_ref(elements.next().unwrap()))
+    }
+}
+
+impl<'a, T : FieldRef<'a>> FieldRef<'a> for Option<T> {
+    type Ref = Option<T::Ref>;
+
+    fn read_ref(buffer : &'a [u8]) -> (Self::Ref, usize) {
+        let (present, size) = bool::read_field(buffer);
+        let (field, field_size) = Self::read_bitmap_ref(&buffer[size..], present);
+        (field, size + field_size)
+    }
+
+    fn read_bitmap_ref(buffer : &'a [u8], present : bool) -> (Self::Ref, usize) {
+        if present {
+            let (field, size) = T::read_ref(buffer);
+            (Some(field), size)
+        } else {
+            (None, 0)
+        }
+    }
+
+    fn from_ref(view : Self::Ref) -> Self {
+        view.map(T::from_ref)
+    }
+}
+
+/// View of the elements of a [`Vec<T>`], [`Box<[T]>`] or `[T; N]` field, read lazily when iterated.
+/// 
+/// # Example(s)
+/// ```
+/// use ethos_core::{ net::EthosPayload, tampon::Tampon };
+/// 
+/// #[derive(Debug, PartialEq, Clone, EthosPayload)]
+/// pub enum Payload {
+///     #[ethos(id = 1)]
+///     Variable { text : String, list : Vec<u32>, blob : Box<[u8]> },
+/// 
+///     #[ethos(id = 65535)]
+///     Invalid,
+/// }
+/// 
+/// let payload = Payload::Variable { text: "ethos".into(), list: vec![1, 2, 3], blob: Box::new([4, 5]) };
+/// let mut buffer = vec![0u8; payload.bytes_size()];
+/// payload.serialize(&mut buffer);
+/// 
+/// let (view, _) = PayloadRef::read(&buffer);
+/// if let PayloadRef::Variable { text, list, blob } = view {
+///     assert_eq!(text, "ethos");
+///     assert_eq!(list.iter().sum::<u32>(), 6);
+///     assert_eq!(blob.as_bytes(), [4, 5]);
+/// }
+/// ```
+pub struct ListRef<'a, T> {
+    /// Packed elements, without length prefix.
+    bytes : &'a [u8],
+
+    /// Count of elements.
+    len : usize,
+
+    element : PhantomData<T>,
+}
+
+impl<'a, T : FieldRef<'a>> ListRef<'a, T> {
+    /// Create a view of `len` elements packed at the beginning of buffer, validated beforehand.
+    fn new(buffer : &'a [u8], len : usize) -> ListRef<'a, T> {
+        let size = match T::FIXED_SIZE {
+            Some(size) => size * len,
+            None => (0..len).fold(0, |size, _| size + T::read_field_size(&buffer[size..], usize::MAX).expect("Elements should have been validated")),
+        };
+        ListRef { bytes: &buffer[..size], len, element: PhantomData }
+    }
+
+    /// Count of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there is no element.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Packed elements, which are the elements themselves for lists of [`u8`].
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Iterator over views of elements.
+    pub fn iter(&self) -> ListIter<'a, T> {
+        ListIter { bytes: self.bytes, len: self.len, element: PhantomData }
+    }
+}
+
+impl<T> Clone for ListRef<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ListRef<'_, T> {}
+
+impl<T> PartialEq for ListRef<'_, T> {
+    fn eq(&self, other : &Self) -> bool {
+        self.len == other.len && self.bytes == other.bytes
+    }
+}
+
+impl<'a, T : FieldRef<'a>> Debug for ListRef<'a, T> {
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T : FieldRef<'a>> IntoIterator for ListRef<'a, T> {
+    type Item = T::Ref;
+    type IntoIter = ListIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over views of the elements of a [`ListRef`].
+pub struct ListIter<'a, T> {
+    /// Packed elements not read yet.
+    bytes : &'a [u8],
+
+    /// Count of elements not read yet.
+    len : usize,
+
+    element : PhantomData<T>,
+}
+
+impl<'a, T : FieldRef<'a>> Iterator for ListIter<'a, T> {
+    type Item = T::Ref;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let (element, size) = T::read_ref(self.bytes);
+        self.bytes = &self.bytes[size..];
+        self.len -= 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T : FieldRef<'a>> ExactSizeIterator for ListIter<'a, T> {}
+
+
+/// This module test [FieldRef] implementations.
+/// 
+/// # Verification(s)
+/// V1 : [FieldRef::read_ref] reads the same size as [PayloadField::read_field] and [FieldRef::from_ref] gives back the original field.
+/// V2 : [ListRef] elements are read lazily from the packed bytes.
+/// V3 : [String] view borrows valid UTF-8 and replaces invalid UTF-8.
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, fmt::Debug};
+
+    use crate::net::PayloadField;
+
+    use super::FieldRef;
+
+    fn round_trip<T : for<'a> FieldRef<'a> + PartialEq + Debug>(field : T) {
+        let mut buffer = vec![0u8; field.field_size()];
+        field.write_field(&mut buffer);
+
+        let (view, size) = T::read_ref(&buffer);
+        assert_eq!(size, buffer.len());
+        assert_eq!(T::from_ref(view), field);
+    }
+
+    #[test]
+    fn v1_round_trip() {
+        // V1 : [FieldRef::read_ref] reads the same size as [PayloadField::read_field] and [FieldRef::from_ref] gives back the original field.
+        round_trip(u8::MAX);
+        round_trip(-1.5f64);
+        round_trip(true);
+        round_trip(String::from("Héllo ethos"));
+        round_trip(vec![1u32, 2, 3]);
+        round_trip(vec![String::from("a"), String::new(), String::from("bc")]);
+        round_trip(vec![vec![1u8], vec![], vec![2, 3]]);
+        round_trip(Box::<[u8]>::from([4u8, 5, 6].as_slice()));
+        round_trip([String::from("x"), String::from("yz")]);
+        round_trip(Some(5u32));
+        round_trip(None::<String>);
+        round_trip(vec![Some(1u8), None, Some(2)]);
+    }
+
+    #[test]
+    fn v2_list_lazy() {
+        // V2 : [ListRef] elements are read lazily from the packed bytes.
+        let field = vec![String::from("a"), String::from("bcd")];
+        let mut buffer = vec![0u8; field.field_size()];
+        field.write_field(&mut buffer);
+
+        let (list, _) = Vec::<String>::read_ref(&buffer);
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+        assert_eq!(list.as_bytes(), &buffer[2..]);
+        assert_eq!(list.iter().len(), 2);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), ["a", "bcd"]);
+        assert_eq!(format!("{:?}", list), r#"["a", "bcd"]"#);
+
+        let blob = Box::<[u8]>::from([7u8; 10].as_slice());
+        let mut buffer = vec![0u8; blob.field_size()];
+        blob.write_field(&mut buffer);
+        let (list, _) = Box::<[u8]>::read_ref(&buffer);
+        assert_eq!(list.as_bytes().as_ptr(), buffer[2..].as_ptr());
+    }
+
+    #[test]
+    fn v3_str_view() {
+        // V3 : [String] view borrows valid UTF-8 and replaces invalid UTF-8.
+        let buffer = [2u8, 0, b'o', b'k'];
+        assert!(matches!(String::read_ref(&buffer), (Cow::Borrowed("ok"), 4)));
+
+        let buffer = [2u8, 0, 0xFF, b'a'];
+        assert_eq!(String::read_ref(&buffer), (Cow::Owned(String::from("\u{FFFD}a")), 4));
+    }
+}